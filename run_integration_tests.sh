@@ -0,0 +1,15 @@
+#!/usr/bin/env bash
+# Builds the ICVC backend and the mock SNS governance, then runs the PocketIC tests.
+# The PocketIC server binary is read from POCKET_IC_BIN, or ./pocket-ic in the canister root.
+set -e
+
+cargo build --target wasm32-unknown-unknown --release \
+    --package icvc_backend \
+    --package mock_sns_governance
+
+if [ -z "$POCKET_IC_BIN" ] && [ ! -f "src/icvc_backend/pocket-ic" ]; then
+    echo "PocketIC server not found, set POCKET_IC_BIN (see https://github.com/dfinity/pocketic)."
+    exit 1
+fi
+
+cargo test --package icvc_backend --test project_lifecycle -- --ignored "$@"
@@ -10,4 +10,10 @@ echo "NETWORK: $NETWORK"
 
 #./sns/scripts/proposals/custom_proposal_project_voting.sh "$ICVC_BACKEND_CANISTER"
 
-
+# Administrative actions governed by the DAO
+./sns/scripts/proposals/custom_proposal_category_management.sh "$NETWORK" "$PROPOSER_NEURON_ID" "$ICVC_BACKEND_CANISTER"
+./sns/scripts/proposals/custom_proposal_step_phase_config.sh "$NETWORK" "$PROPOSER_NEURON_ID" "$ICVC_BACKEND_CANISTER"
+./sns/scripts/proposals/custom_proposal_admin_management.sh "$NETWORK" "$PROPOSER_NEURON_ID" "$ICVC_BACKEND_CANISTER"
+./sns/scripts/proposals/custom_proposal_deadline_extension.sh "$NETWORK" "$PROPOSER_NEURON_ID" "$ICVC_BACKEND_CANISTER"
+./sns/scripts/proposals/custom_proposal_project_suspension.sh "$NETWORK" "$PROPOSER_NEURON_ID" "$ICVC_BACKEND_CANISTER"
+./sns/scripts/proposals/custom_proposal_storage_config.sh "$NETWORK" "$PROPOSER_NEURON_ID" "$ICVC_BACKEND_CANISTER"
@@ -0,0 +1,20 @@
+#!/bin/bash
+
+export NETWORK=$1
+export PROPOSER_NEURON_ID=$2
+export TARGET_CANISTER_ID=$3
+
+. ./sns/scripts/utils/setup_env.sh "$NETWORK" "$PROPOSER_NEURON_ID"
+
+TITLE="Register generic function on ICVC backend canister"
+URL="https://ic-vc.com/"
+SUMMARY="This proposal registers a generic function to add and remove admins."
+FUNCTION_ID=4005
+FUNCTION_NAME="Admin management"
+FUNCTION_DESC="Allow adding and removing ICVC admins"
+TARGET_CANISTER_ID="$TARGET_CANISTER_ID"
+TARGET_METHOD_NAME="execute_admin_proposal"
+VALIDATOR_CANISTER_ID="$TARGET_CANISTER_ID"
+VALIDATOR_METHOD_NAME="validate_admin_proposal"
+
+./sns/scripts/utils/create_custom_proposal.sh "$TITLE" "$URL" "$SUMMARY" "$FUNCTION_ID" "$FUNCTION_NAME" "$FUNCTION_DESC" "$TARGET_CANISTER_ID" "$TARGET_METHOD_NAME" "$VALIDATOR_CANISTER_ID" "$VALIDATOR_METHOD_NAME"
@@ -0,0 +1,20 @@
+#!/bin/bash
+
+export NETWORK=$1
+export PROPOSER_NEURON_ID=$2
+export TARGET_CANISTER_ID=$3
+
+. ./sns/scripts/utils/setup_env.sh "$NETWORK" "$PROPOSER_NEURON_ID"
+
+TITLE="Register generic function on ICVC backend canister"
+URL="https://ic-vc.com/"
+SUMMARY="This proposal registers a generic function to decide on project appeals."
+FUNCTION_ID=4002
+FUNCTION_NAME="Appeal decision"
+FUNCTION_DESC="Allow deciding on appeals of rejected ICVC project phases"
+TARGET_CANISTER_ID="$TARGET_CANISTER_ID" 
+TARGET_METHOD_NAME="execute_appeal_decision"
+VALIDATOR_CANISTER_ID="$TARGET_CANISTER_ID"
+VALIDATOR_METHOD_NAME="validate_appeal_decision"
+
+./sns/scripts/utils/create_custom_proposal.sh "$TITLE" "$URL" "$SUMMARY" "$FUNCTION_ID" "$FUNCTION_NAME" "$FUNCTION_DESC" "$TARGET_CANISTER_ID" "$TARGET_METHOD_NAME" "$VALIDATOR_CANISTER_ID" "$VALIDATOR_METHOD_NAME"
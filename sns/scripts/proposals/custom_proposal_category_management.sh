@@ -0,0 +1,20 @@
+#!/bin/bash
+
+export NETWORK=$1
+export PROPOSER_NEURON_ID=$2
+export TARGET_CANISTER_ID=$3
+
+. ./sns/scripts/utils/setup_env.sh "$NETWORK" "$PROPOSER_NEURON_ID"
+
+TITLE="Register generic function on ICVC backend canister"
+URL="https://ic-vc.com/"
+SUMMARY="This proposal registers a generic function to manage project categories."
+FUNCTION_ID=4003
+FUNCTION_NAME="Category management"
+FUNCTION_DESC="Allow creating and deactivating ICVC project categories"
+TARGET_CANISTER_ID="$TARGET_CANISTER_ID"
+TARGET_METHOD_NAME="execute_category_proposal"
+VALIDATOR_CANISTER_ID="$TARGET_CANISTER_ID"
+VALIDATOR_METHOD_NAME="validate_category_proposal"
+
+./sns/scripts/utils/create_custom_proposal.sh "$TITLE" "$URL" "$SUMMARY" "$FUNCTION_ID" "$FUNCTION_NAME" "$FUNCTION_DESC" "$TARGET_CANISTER_ID" "$TARGET_METHOD_NAME" "$VALIDATOR_CANISTER_ID" "$VALIDATOR_METHOD_NAME"
@@ -0,0 +1,20 @@
+#!/bin/bash
+
+export NETWORK=$1
+export PROPOSER_NEURON_ID=$2
+export TARGET_CANISTER_ID=$3
+
+. ./sns/scripts/utils/setup_env.sh "$NETWORK" "$PROPOSER_NEURON_ID"
+
+TITLE="Register generic function on ICVC backend canister"
+URL="https://ic-vc.com/"
+SUMMARY="This proposal registers a generic function to extend project phase deadlines."
+FUNCTION_ID=4006
+FUNCTION_NAME="Deadline extension"
+FUNCTION_DESC="Allow extending the deadlines of ICVC project phases"
+TARGET_CANISTER_ID="$TARGET_CANISTER_ID"
+TARGET_METHOD_NAME="execute_step_phase_deadline_extension"
+VALIDATOR_CANISTER_ID="$TARGET_CANISTER_ID"
+VALIDATOR_METHOD_NAME="validate_step_phase_deadline_extension"
+
+./sns/scripts/utils/create_custom_proposal.sh "$TITLE" "$URL" "$SUMMARY" "$FUNCTION_ID" "$FUNCTION_NAME" "$FUNCTION_DESC" "$TARGET_CANISTER_ID" "$TARGET_METHOD_NAME" "$VALIDATOR_CANISTER_ID" "$VALIDATOR_METHOD_NAME"
@@ -0,0 +1,20 @@
+#!/bin/bash
+
+export NETWORK=$1
+export PROPOSER_NEURON_ID=$2
+export TARGET_CANISTER_ID=$3
+
+. ./sns/scripts/utils/setup_env.sh "$NETWORK" "$PROPOSER_NEURON_ID"
+
+TITLE="Register generic function on ICVC backend canister"
+URL="https://ic-vc.com/"
+SUMMARY="This proposal registers a generic function to suspend projects."
+FUNCTION_ID=4007
+FUNCTION_NAME="Project suspension"
+FUNCTION_DESC="Allow suspending ICVC projects and lifting their suspension"
+TARGET_CANISTER_ID="$TARGET_CANISTER_ID"
+TARGET_METHOD_NAME="execute_project_suspension"
+VALIDATOR_CANISTER_ID="$TARGET_CANISTER_ID"
+VALIDATOR_METHOD_NAME="validate_project_suspension"
+
+./sns/scripts/utils/create_custom_proposal.sh "$TITLE" "$URL" "$SUMMARY" "$FUNCTION_ID" "$FUNCTION_NAME" "$FUNCTION_DESC" "$TARGET_CANISTER_ID" "$TARGET_METHOD_NAME" "$VALIDATOR_CANISTER_ID" "$VALIDATOR_METHOD_NAME"
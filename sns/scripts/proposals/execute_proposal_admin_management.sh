@@ -0,0 +1,19 @@
+#!/bin/bash
+
+NETWORK=$1
+PROPOSER_NEURON_ID=$2
+
+. ./sns/scripts/utils/setup_env.sh "$NETWORK" "$PROPOSER_NEURON_ID"
+
+FUNCTION_ID="4005"
+TITLE="Add ICVC admin"
+SUMMARY="This proposal adds a new ICVC admin."
+URL="https://ic-vc.com/"
+
+# Function Args
+ADMIN_PRINCIPAL=$3
+ADMIN_NAME=$4
+
+ARGS="(variant { Add = record { user_id = principal \"$ADMIN_PRINCIPAL\"; name = \"$ADMIN_NAME\" } })"
+
+./sns/scripts/utils/make_custom_execute_proposal.sh "$FUNCTION_ID" "$TITLE" "$SUMMARY" "$URL" "$ARGS"
@@ -0,0 +1,18 @@
+#!/bin/bash
+
+NETWORK=$1
+PROPOSER_NEURON_ID=$2
+
+. ./sns/scripts/utils/setup_env.sh "$NETWORK" "$PROPOSER_NEURON_ID"
+
+FUNCTION_ID="4003"
+TITLE="Create project category"
+SUMMARY="This proposal creates a new ICVC project category."
+URL="https://ic-vc.com/"
+
+# Function Args
+CATEGORY_NAME=$3
+
+ARGS="(variant { Create = record { name = \"$CATEGORY_NAME\" } })"
+
+./sns/scripts/utils/make_custom_execute_proposal.sh "$FUNCTION_ID" "$TITLE" "$SUMMARY" "$URL" "$ARGS"
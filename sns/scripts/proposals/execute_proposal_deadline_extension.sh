@@ -0,0 +1,29 @@
+#!/bin/bash
+
+NETWORK=$1
+PROPOSER_NEURON_ID=$2
+
+. ./sns/scripts/utils/setup_env.sh "$NETWORK" "$PROPOSER_NEURON_ID"
+
+FUNCTION_ID="4006"
+TITLE="Extend project phase deadline"
+SUMMARY="This proposal extends the open deadline of an ICVC project phase."
+URL="https://ic-vc.com/"
+
+# Function Args
+PROJECT_ID=$3
+PHASE_ID=$4
+NEW_END_DATE=$5 # Nanoseconds since epoch
+REASON=$6
+
+ARGS="(record {
+    project_id = $PROJECT_ID : nat64;
+    phase_id = $PHASE_ID : nat64;
+    extension = record {
+        deadline = variant { Open };
+        new_end_date = $NEW_END_DATE : nat64;
+        reason = \"$REASON\"
+    }
+})"
+
+./sns/scripts/utils/make_custom_execute_proposal.sh "$FUNCTION_ID" "$TITLE" "$SUMMARY" "$URL" "$ARGS"
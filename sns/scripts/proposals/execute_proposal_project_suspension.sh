@@ -0,0 +1,24 @@
+#!/bin/bash
+
+NETWORK=$1
+PROPOSER_NEURON_ID=$2
+
+. ./sns/scripts/utils/setup_env.sh "$NETWORK" "$PROPOSER_NEURON_ID"
+
+FUNCTION_ID="4007"
+TITLE="Suspend project"
+SUMMARY="This proposal suspends an ICVC project."
+URL="https://ic-vc.com/"
+
+# Function Args
+PROJECT_ID=$3
+SUSPENDED=${4:-true} # false lifts the suspension
+REASON=$5
+
+ARGS="(record {
+    project_id = $PROJECT_ID : nat64;
+    suspended = $SUSPENDED;
+    reason = \"$REASON\"
+})"
+
+./sns/scripts/utils/make_custom_execute_proposal.sh "$FUNCTION_ID" "$TITLE" "$SUMMARY" "$URL" "$ARGS"
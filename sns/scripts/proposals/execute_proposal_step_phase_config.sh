@@ -0,0 +1,28 @@
+#!/bin/bash
+
+NETWORK=$1
+PROPOSER_NEURON_ID=$2
+
+. ./sns/scripts/utils/setup_env.sh "$NETWORK" "$PROPOSER_NEURON_ID"
+
+FUNCTION_ID="4004"
+TITLE="Update step phase config"
+SUMMARY="This proposal updates the vote rules of an ICVC step phase."
+URL="https://ic-vc.com/"
+
+# Function Args
+STEP_PHASE_ID=$3
+MIN_YES_RATIO=${4:-5000}
+
+ARGS="(record {
+    step_phase_id = $STEP_PHASE_ID : nat64;
+    name = null;
+    vote_rules = opt record {
+        use_sns_decision = false;
+        min_yes_ratio = $MIN_YES_RATIO : nat64;
+        min_participation = 0 : nat64;
+        min_yes_votes = 0 : nat64
+    }
+})"
+
+./sns/scripts/utils/make_custom_execute_proposal.sh "$FUNCTION_ID" "$TITLE" "$SUMMARY" "$URL" "$ARGS"
@@ -0,0 +1,40 @@
+#!/bin/bash
+
+NETWORK=$1
+PROPOSER_NEURON_ID=$2
+
+. ./sns/scripts/utils/setup_env.sh "$NETWORK" "$PROPOSER_NEURON_ID"
+
+FUNCTION_ID="4008"
+TITLE="Update storage config"
+SUMMARY="This proposal updates the upload provider of the ICVC project documents."
+URL="https://ic-vc.com/"
+
+# Function Args
+PROVIDER=${3:-S3Compatible}
+ENDPOINT=$4
+BUCKET=$5
+
+# Handle optional fields
+ENDPOINT_FIELD="null"
+BUCKET_FIELD="null"
+
+if [ -n "$ENDPOINT" ]; then
+    ENDPOINT_FIELD="opt \"$ENDPOINT\""
+fi
+
+if [ -n "$BUCKET" ]; then
+    BUCKET_FIELD="opt \"$BUCKET\""
+fi
+
+ARGS="(record {
+    provider = opt variant { $PROVIDER };
+    endpoint = $ENDPOINT_FIELD;
+    bucket = $BUCKET_FIELD;
+    path_prefix = null;
+    cycles_budget = null;
+    auth_header = null;
+    auth_secret = null
+})"
+
+./sns/scripts/utils/make_custom_execute_proposal.sh "$FUNCTION_ID" "$TITLE" "$SUMMARY" "$URL" "$ARGS"
@@ -0,0 +1,112 @@
+//! This module defines the controller functions for appeals on rejected step phases.
+
+use crate::{
+    utils::authenticator::{
+        check_is_owner_or_admin, check_is_owner_or_governance_id, check_is_project_owner_or_admin,
+    },
+    APIError, ProjectId, StepPhaseId,
+};
+
+use super::{
+    service,
+    types::{Appeal, AppealCreate, AppealDecision, AppealDecisionPayload, AppealStatus},
+};
+
+/// Files an appeal against a rejected step phase. The appeal must be filed within the
+/// configured appeal window after the end of the assessment period.
+///
+/// # Arguments
+/// * `project_id` - The ID of the project.
+/// * `step_phase_id` - The ID of the rejected step phase.
+/// * `appeal_create` - The justification of the appeal.
+///
+/// # Returns
+/// * `Result<Appeal, APIError>` - The filed appeal or an error.
+#[ic_cdk::update(name = "fileAppeal")]
+pub fn file_appeal(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    appeal_create: AppealCreate,
+) -> Result<Appeal, APIError> {
+    let caller_id = ic_cdk::caller();
+    check_is_project_owner_or_admin(caller_id, project_id)?;
+
+    service::file_appeal(caller_id, project_id, step_phase_id, appeal_create)
+}
+
+/// Decides a pending appeal. The appeal can be rejected, the phase can be reassessed, with its
+/// grades and vote result discarded, or approved, which opens the next phase or funds the
+/// project after a voted last phase.
+///
+/// # Arguments
+/// * `project_id` - The ID of the project.
+/// * `step_phase_id` - The ID of the appealed step phase.
+/// * `decision` - The outcome of the appeal and the reason for it.
+///
+/// # Returns
+/// * `Result<Appeal, APIError>` - The decided appeal or an error.
+#[ic_cdk::update(name = "decideAppeal")]
+pub async fn decide_appeal(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    decision: AppealDecision,
+) -> Result<Appeal, APIError> {
+    let caller_id = ic_cdk::caller();
+    check_is_owner_or_admin(caller_id)?;
+
+    service::decide_appeal(caller_id, project_id, step_phase_id, decision).await
+}
+
+/// Retrieves the appeal filed against a step phase.
+///
+/// # Arguments
+/// * `project_id` - The ID of the project.
+/// * `step_phase_id` - The ID of the step phase.
+///
+/// # Returns
+/// * `Result<Appeal, APIError>` - The appeal or an error.
+#[ic_cdk::query(name = "getAppealByStepPhaseId")]
+pub fn get_appeal_by_step_phase_id(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+) -> Result<Appeal, APIError> {
+    service::get_appeal_by_step_phase_id(project_id, step_phase_id)
+}
+
+/// Retrieves all appeals, optionally filtered by status.
+///
+/// # Arguments
+/// * `status` - The status of the appeals to retrieve (optional).
+///
+/// # Returns
+/// * `Vec<Appeal>` - A list of appeals.
+#[ic_cdk::query(name = "getAllAppeals")]
+pub fn get_all_appeals(status: Option<AppealStatus>) -> Vec<Appeal> {
+    service::get_all_appeals(status)
+}
+
+#[ic_cdk::update]
+fn validate_appeal_decision(payload: AppealDecisionPayload) -> Result<String, String> {
+    let caller_id = ic_cdk::caller();
+
+    match check_is_owner_or_governance_id(caller_id) {
+        Ok(_) => service::validate_appeal_decision(payload),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+#[ic_cdk::update]
+async fn execute_appeal_decision(payload: AppealDecisionPayload) {
+    let caller_id = ic_cdk::caller();
+
+    match check_is_owner_or_governance_id(caller_id) {
+        Ok(_) => {
+            if let Err(err) = service::execute_appeal_decision(caller_id, payload).await {
+                ic_cdk::println!("Unable to execute appeal decision: {}", err.to_string());
+            }
+        }
+        Err(err) => {
+            ic_cdk::println!("Unable to execute appeal decision: {}", err.to_string());
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod controller;
+#[doc(hidden)]
+pub mod service;
+pub mod types;
+pub mod types_storage;
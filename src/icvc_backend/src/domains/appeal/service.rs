@@ -0,0 +1,286 @@
+use crate::{
+    domains::{
+        audit::{
+            self,
+            types::{AuditAction, AuditTarget},
+        },
+        core,
+        icvc_configuration::{self, constants::MAX_APPEAL_JUSTIFICATION_BYTES},
+        step,
+    },
+    repository, APIError, AssessmentMethod, Project, ProjectId, ProjectStatus, StepPhase,
+    StepPhaseId, StepPhaseStatus, StepPhaseUpdate, UserId,
+};
+
+use super::types::{
+    Appeal, AppealCreate, AppealDecision, AppealDecisionPayload, AppealOutcome, AppealStatus,
+};
+
+pub fn file_appeal(
+    caller_id: UserId,
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    appeal_create: AppealCreate,
+) -> Result<Appeal, APIError> {
+    check_appeal_justification(&appeal_create.justification)?;
+
+    let project = repository::retrieve_project_by_id(project_id)
+        .ok_or_else(|| APIError::NotFound(format!("Project with id {} not found.", project_id)))?;
+
+    let step_phase =
+        repository::get_step_phase_by_id(project_id, step_phase_id).ok_or_else(|| {
+            APIError::NotFound(format!(
+                "Step phase with id: {} for project id: {}, not found.",
+                step_phase_id, project_id
+            ))
+        })?;
+
+    if project.current_phase != step_phase_id
+        || project.status != ProjectStatus::NotFunded
+        || step_phase.status != StepPhaseStatus::NotApproved
+    {
+        return Err(APIError::BadRequest(format!(
+            "Only the rejected current phase of a not funded project can be appealed. Project status: {}, step phase status: {}.",
+            project.status, step_phase.status
+        )));
+    }
+
+    // The appeal window starts when the assessment period ends.
+    let appeal_deadline = step_phase.end_assessment_date
+        + icvc_configuration::service::get_appeal_duration() * 1_000_000_000;
+
+    if ic_cdk::api::time() > appeal_deadline {
+        return Err(APIError::BadRequest(format!(
+            "The appeal window for step phase with id: {} of project id: {} has ended.",
+            step_phase_id, project_id
+        )));
+    }
+
+    let appeal = repository::insert_appeal(project_id, step_phase_id, caller_id, appeal_create)
+        .ok_or_else(|| {
+            APIError::BadRequest(format!(
+                "An appeal for step phase with id: {} of project id: {} already exists.",
+                step_phase_id, project_id
+            ))
+        })?;
+
+    repository::update_step_phase_status(project_id, step_phase_id, StepPhaseStatus::UnderAppeal);
+    repository::update_project_status(project.user_id, project_id, ProjectStatus::UnderAppeal);
+    audit::service::record(
+        caller_id,
+        AuditAction::AppealFiled,
+        AuditTarget::step_phase(project_id, step_phase_id),
+        Some(format!(
+            "phase: {}, project: {}",
+            step_phase.status, project.status
+        )),
+        Some(format!("justification: {}", appeal.justification)),
+    );
+
+    Ok(appeal)
+}
+
+pub async fn decide_appeal(
+    caller_id: UserId,
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    decision: AppealDecision,
+) -> Result<Appeal, APIError> {
+    let (project, step_phase) = check_appeal_decision(project_id, step_phase_id, &decision)?;
+    let outcome = decision.outcome.clone();
+
+    let appeal = repository::update_appeal_decision(
+        project_id,
+        step_phase_id,
+        Some(caller_id),
+        Some(decision),
+    )
+    .ok_or_else(|| {
+        APIError::NotFound(format!(
+            "Appeal for step phase with id: {} of project id: {} not found.",
+            step_phase_id, project_id
+        ))
+    })?;
+
+    match outcome {
+        AppealOutcome::Reject => {
+            repository::update_step_phase_status(
+                project_id,
+                step_phase_id,
+                StepPhaseStatus::NotApproved,
+            );
+            repository::update_project_status(
+                project.user_id,
+                project_id,
+                ProjectStatus::NotFunded,
+            );
+        }
+        AppealOutcome::Reassess => {
+            repository::update_project_status(project.user_id, project_id, ProjectStatus::Open);
+
+            // The phase is assessed again from scratch.
+            repository::remove_step_phase_grades(project_id, step_phase_id);
+            repository::remove_step_phase_vote_result(project_id, step_phase_id);
+
+            if let Err(e) =
+                step::service::start_step_phase_assessment(&step_phase, StepPhaseUpdate::default())
+                    .await
+            {
+                // Put the appeal back so it can be decided again.
+                repository::update_appeal_decision(project_id, step_phase_id, None, None);
+                repository::update_project_status(
+                    project.user_id,
+                    project_id,
+                    ProjectStatus::UnderAppeal,
+                );
+                return Err(e);
+            }
+        }
+        AppealOutcome::Advance => {
+            repository::update_project_status(project.user_id, project_id, ProjectStatus::Open);
+            let project = repository::retrieve_project_by_id(project_id).unwrap_or(project);
+
+            // Approves the phase, then opens the next one or funds the project after the last
+            // phase, see check_appeal_decision.
+            core::service::update_phase_status(&project, step_phase, true);
+        }
+    }
+    audit::service::record(
+        caller_id,
+        AuditAction::AppealDecided,
+        AuditTarget::step_phase(project_id, step_phase_id),
+        None,
+        Some(format!(
+            "outcome: {}, reason: {}",
+            appeal
+                .outcome
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            appeal.decision_reason.as_deref().unwrap_or_default()
+        )),
+    );
+
+    Ok(appeal)
+}
+
+pub fn validate_appeal_decision(payload: AppealDecisionPayload) -> Result<String, String> {
+    match check_appeal_decision(payload.project_id, payload.phase_id, &payload.decision) {
+        Ok((project, _)) => {
+            let appeal =
+                repository::get_appeal_by_step_phase_id(payload.project_id, payload.phase_id);
+            let justification = appeal
+                .map(|appeal| appeal.justification)
+                .unwrap_or_default();
+
+            Ok(format!(
+                "Appeal decision: {} for project \"{}\" (id {}) on phase {}.\n\nFounder justification: {}\n\nDecision reason: {}",
+                payload.decision.outcome,
+                project.title,
+                project.id,
+                payload.phase_id,
+                justification,
+                payload.decision.reason
+            ))
+        }
+        Err(e) => Err(format!("Unable to validate appeal decision: {}", e)),
+    }
+}
+
+pub async fn execute_appeal_decision(
+    caller_id: UserId,
+    payload: AppealDecisionPayload,
+) -> Result<Appeal, APIError> {
+    decide_appeal(
+        caller_id,
+        payload.project_id,
+        payload.phase_id,
+        payload.decision,
+    )
+    .await
+}
+
+pub fn get_appeal_by_step_phase_id(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+) -> Result<Appeal, APIError> {
+    match repository::get_appeal_by_step_phase_id(project_id, step_phase_id) {
+        Some(appeal) => Ok(appeal),
+        None => Err(APIError::NotFound(format!(
+            "Appeal for step phase with id: {} of project id: {} not found.",
+            step_phase_id, project_id
+        ))),
+    }
+}
+
+pub fn get_all_appeals(status: Option<AppealStatus>) -> Vec<Appeal> {
+    repository::get_all_appeals(status)
+}
+
+fn check_appeal_decision(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    decision: &AppealDecision,
+) -> Result<(Project, StepPhase), APIError> {
+    step::service::check_admin_reason(&decision.reason)?;
+
+    let appeal = get_appeal_by_step_phase_id(project_id, step_phase_id)?;
+
+    if appeal.status != AppealStatus::Pending {
+        return Err(APIError::BadRequest(format!(
+            "Appeal for step phase with id: {} of project id: {} was already decided: {}.",
+            step_phase_id, project_id, appeal.status
+        )));
+    }
+
+    let project = repository::retrieve_project_by_id(project_id)
+        .ok_or_else(|| APIError::NotFound(format!("Project with id {} not found.", project_id)))?;
+
+    let step_phase =
+        repository::get_step_phase_by_id(project_id, step_phase_id).ok_or_else(|| {
+            APIError::NotFound(format!(
+                "Step phase with id: {} for project id: {}, not found.",
+                step_phase_id, project_id
+            ))
+        })?;
+
+    if project.status != ProjectStatus::UnderAppeal
+        || step_phase.status != StepPhaseStatus::UnderAppeal
+    {
+        return Err(APIError::BadRequest(format!(
+            "Project id: {} and step phase id: {} should be Under Appeal. Project status: {}, step phase status: {}.",
+            project_id, step_phase_id, project.status, step_phase.status
+        )));
+    }
+
+    // Only a vote closes the last phase, a graded one can't fund the project.
+    if decision.outcome == AppealOutcome::Advance
+        && core::service::check_next_phase(&project).is_none()
+        && step_phase.assessment_method != AssessmentMethod::Vote
+    {
+        return Err(APIError::BadRequest(format!(
+            "Step phase id: {} is the last phase of project id: {} and is not assessed by a vote, it can't be advanced.",
+            step_phase_id, project_id
+        )));
+    }
+
+    Ok((project, step_phase))
+}
+
+fn check_appeal_justification(justification: &str) -> Result<(), APIError> {
+    if justification.trim().is_empty() {
+        return Err(APIError::BadRequest(
+            "A justification is required to file an appeal.".to_string(),
+        ));
+    }
+
+    if justification.len() > MAX_APPEAL_JUSTIFICATION_BYTES {
+        return Err(APIError::BadRequest(format!(
+            "Justification length: {}. Max allowed length: {}.",
+            justification.len(),
+            MAX_APPEAL_JUSTIFICATION_BYTES
+        )));
+    }
+
+    Ok(())
+}
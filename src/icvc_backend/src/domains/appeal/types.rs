@@ -0,0 +1,72 @@
+use std::fmt;
+
+use candid::{CandidType, Deserialize};
+use serde::Serialize;
+
+use crate::{ProjectId, StepPhaseId, UserId};
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct AppealCreate {
+    pub justification: String,
+}
+
+#[derive(CandidType, Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub enum AppealOutcome {
+    Reject,
+    Reassess,
+    Advance,
+}
+
+impl fmt::Display for AppealOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            AppealOutcome::Reject => write!(f, "Reject"),
+            AppealOutcome::Reassess => write!(f, "Reassess"),
+            AppealOutcome::Advance => write!(f, "Advance"),
+        }
+    }
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AppealDecision {
+    pub outcome: AppealOutcome,
+    pub reason: String,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AppealDecisionPayload {
+    pub project_id: ProjectId,
+    pub phase_id: StepPhaseId,
+    pub decision: AppealDecision,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Clone, Debug)]
+pub enum AppealStatus {
+    Pending,
+    Accepted,
+    Rejected,
+}
+
+impl fmt::Display for AppealStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            AppealStatus::Pending => write!(f, "Pending"),
+            AppealStatus::Accepted => write!(f, "Accepted"),
+            AppealStatus::Rejected => write!(f, "Rejected"),
+        }
+    }
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct Appeal {
+    pub project_id: ProjectId,
+    pub step_phase_id: StepPhaseId,
+    pub justification: String,
+    pub status: AppealStatus,
+    pub outcome: Option<AppealOutcome>,
+    pub decision_reason: Option<String>,
+    pub filed_by: UserId,
+    pub filed_at: u64,
+    pub decided_by: Option<UserId>,
+    pub decided_at: Option<u64>,
+}
@@ -0,0 +1,32 @@
+use candid::{CandidType, Deserialize};
+use candid::{Decode, Encode};
+use ic_stable_structures::{storable::Bound, Storable};
+use std::borrow::Cow;
+
+use crate::UserId;
+
+use super::types::{AppealOutcome, AppealStatus};
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct AppealModel {
+    pub justification: String,
+    pub status: AppealStatus,
+    pub outcome: Option<AppealOutcome>,
+    pub decision_reason: Option<String>,
+    pub filed_by: UserId,
+    pub filed_at: u64,
+    pub decided_by: Option<UserId>,
+    pub decided_at: Option<u64>,
+}
+
+impl Storable for AppealModel {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
@@ -0,0 +1,4 @@
+pub const MAX_AUDIT_SUMMARY_BYTES: usize = 1024;
+pub const MAX_AUDIT_LOG_PAGE_SIZE: usize = 100;
+// Entries read by a single getAuditLog call, whether they match the filter or not
+pub const MAX_AUDIT_LOG_SCANNED_ENTRIES: usize = 10 * MAX_AUDIT_LOG_PAGE_SIZE;
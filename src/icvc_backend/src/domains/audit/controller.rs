@@ -0,0 +1,33 @@
+//! This module defines the controller functions for the audit log of the state changes.
+
+use crate::{utils::authenticator::check_is_owner_or_admin, APIError};
+
+use super::{
+    service,
+    types::{AuditEntryId, AuditLogFilter, AuditLogPage},
+};
+
+/// Retrieves the audit log of the state changes, oldest entries first. Only the owner and the
+/// admins can read it.
+///
+/// # Arguments
+/// * `filter` - The actor, action, project, phase and time range the entries must match.
+/// * `start_at` - The ID of the entry to start from (optional).
+/// * `limit` - The maximum number of entries to retrieve, at most 100 (optional).
+///
+/// At most 1000 entries are read per call, so a page can hold fewer entries than requested
+/// while more of them match: the log is read to the end once `next_start_at` is not set.
+///
+/// # Returns
+/// * `Result<AuditLogPage, APIError>` - The matching entries with the start of the next page
+///   or an error.
+#[ic_cdk::query(name = "getAuditLog")]
+pub fn get_audit_log(
+    filter: AuditLogFilter,
+    start_at: Option<AuditEntryId>,
+    limit: Option<usize>,
+) -> Result<AuditLogPage, APIError> {
+    check_is_owner_or_admin(ic_cdk::caller())?;
+
+    Ok(service::get_audit_log(filter, start_at, limit))
+}
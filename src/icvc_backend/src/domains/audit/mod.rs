@@ -0,0 +1,6 @@
+pub mod constants;
+pub mod controller;
+#[doc(hidden)]
+pub mod service;
+pub mod types;
+pub mod types_storage;
@@ -0,0 +1,147 @@
+use candid::Principal;
+
+use crate::repository;
+
+use super::{
+    constants::{MAX_AUDIT_LOG_PAGE_SIZE, MAX_AUDIT_LOG_SCANNED_ENTRIES, MAX_AUDIT_SUMMARY_BYTES},
+    types::{AuditAction, AuditEntry, AuditEntryId, AuditLogFilter, AuditLogPage, AuditTarget},
+    types_storage::AuditEntryModel,
+};
+
+const TRUNCATED_NOTICE: &str = "…";
+
+/// Appends an entry to the audit log. Entries are never updated nor removed.
+pub fn record(
+    actor: Principal,
+    action: AuditAction,
+    target: AuditTarget,
+    before: Option<String>,
+    after: Option<String>,
+) -> AuditEntry {
+    repository::insert_audit_entry(AuditEntryModel {
+        actor,
+        action,
+        target,
+        before: before.map(truncate_summary),
+        after: after.map(truncate_summary),
+        timestamp: ic_cdk::api::time(),
+    })
+}
+
+/// Entries matching the filter from `start_at` on, oldest first. At most
+/// `MAX_AUDIT_LOG_PAGE_SIZE` entries are returned and at most `MAX_AUDIT_LOG_SCANNED_ENTRIES`
+/// entries are read, the next page starts at the `next_start_at` of the page.
+pub fn get_audit_log(
+    filter: AuditLogFilter,
+    start_at: Option<AuditEntryId>,
+    limit: Option<usize>,
+) -> AuditLogPage {
+    let limit = limit
+        .unwrap_or(MAX_AUDIT_LOG_PAGE_SIZE)
+        .min(MAX_AUDIT_LOG_PAGE_SIZE);
+
+    repository::get_audit_entries(
+        filter.project_id,
+        start_at.unwrap_or(0),
+        limit,
+        MAX_AUDIT_LOG_SCANNED_ENTRIES,
+        |entry| {
+            filter.actor.is_none_or(|actor| entry.actor == actor)
+                && filter
+                    .action
+                    .as_ref()
+                    .is_none_or(|action| entry.action == *action)
+                && filter
+                    .step_phase_id
+                    .is_none_or(|step_phase_id| entry.target.step_phase_id == Some(step_phase_id))
+                && filter.from.is_none_or(|from| entry.timestamp >= from)
+                && filter.to.is_none_or(|to| entry.timestamp <= to)
+        },
+    )
+}
+
+// Cuts the summary on a char boundary, so large values don't fill the log.
+fn truncate_summary(summary: String) -> String {
+    if summary.len() <= MAX_AUDIT_SUMMARY_BYTES {
+        return summary;
+    }
+
+    let mut end = MAX_AUDIT_SUMMARY_BYTES - TRUNCATED_NOTICE.len();
+    while !summary.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    format!("{}{}", &summary[..end], TRUNCATED_NOTICE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn insert_entry(actor: Principal) -> AuditEntry {
+        repository::insert_audit_entry(AuditEntryModel {
+            actor,
+            action: AuditAction::StepUpdated,
+            target: AuditTarget::default(),
+            before: None,
+            after: None,
+            timestamp: 0,
+        })
+    }
+
+    #[test]
+    fn audit_log_pages_stop_after_the_scanned_entries() {
+        let actor = Principal::from_slice(&[1]);
+        for _ in 0..MAX_AUDIT_LOG_SCANNED_ENTRIES {
+            insert_entry(Principal::anonymous());
+        }
+        let first_match = insert_entry(actor);
+        let last_match = insert_entry(actor);
+        let filter = AuditLogFilter {
+            actor: Some(actor),
+            ..Default::default()
+        };
+
+        // Only entries of other actors fit in the scanned entries.
+        let page = get_audit_log(filter.clone(), None, None);
+        assert!(page.entries.is_empty());
+        assert_eq!(page.next_start_at, Some(first_match.id));
+
+        let page = get_audit_log(filter.clone(), page.next_start_at, Some(1));
+        assert_eq!(page.entries.len(), 1);
+        assert_eq!(page.entries[0].id, first_match.id);
+        assert_eq!(page.next_start_at, Some(last_match.id));
+
+        let page = get_audit_log(filter, page.next_start_at, None);
+        assert_eq!(page.entries.len(), 1);
+        assert_eq!(page.entries[0].id, last_match.id);
+        assert_eq!(page.next_start_at, None);
+    }
+
+    #[test]
+    fn short_summary_is_kept() {
+        let summary = "a".repeat(MAX_AUDIT_SUMMARY_BYTES);
+
+        assert_eq!(truncate_summary(summary.clone()), summary);
+    }
+
+    #[test]
+    fn long_summary_is_truncated_with_the_notice() {
+        let truncated = truncate_summary("a".repeat(MAX_AUDIT_SUMMARY_BYTES + 1));
+
+        assert_eq!(truncated.len(), MAX_AUDIT_SUMMARY_BYTES);
+        assert!(truncated.ends_with(TRUNCATED_NOTICE));
+    }
+
+    #[test]
+    fn long_summary_is_truncated_on_a_char_boundary() {
+        // "é" takes two bytes, the cut before the notice falls in the middle of one.
+        let truncated = truncate_summary("é".repeat(MAX_AUDIT_SUMMARY_BYTES));
+
+        assert!(truncated.len() <= MAX_AUDIT_SUMMARY_BYTES);
+        assert_eq!(
+            truncated,
+            format!("{}{}", "é".repeat(510), TRUNCATED_NOTICE)
+        );
+    }
+}
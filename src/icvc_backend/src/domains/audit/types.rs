@@ -0,0 +1,138 @@
+use candid::{CandidType, Deserialize, Principal};
+
+use crate::{ProjectId, StepId, StepPhaseId};
+
+pub type AuditEntryId = u64;
+
+/// State change recorded in the audit log.
+#[derive(CandidType, Deserialize, PartialEq, Clone, Debug)]
+pub enum AuditAction {
+    // Projects
+    ProjectCreated,
+    ProjectUpdated,
+    ProjectDeleted,
+    ProjectSuspended,
+    ProjectSuspensionLifted,
+    ProjectReviewerAssigned,
+    ProjectReviewerRemoved,
+    // Steps and phases
+    StepUpdated,
+    StepGraded,
+    StepPhaseSubmitted,
+    StepPhaseStatusChanged,
+    StepPhaseDeadlineExtended,
+    StepPhaseReopened,
+    StepPhaseResetToOpen,
+    // Documents
+    DocumentUploadRequested,
+    DocumentUploadConfirmed,
+    DocumentDeleted,
+    FileUploadCreated,
+    FileUploadCommitted,
+    // Appeals
+    AppealFiled,
+    AppealDecided,
+    // SNS
+    VoteProposalSubmitted,
+    VoteProposalExecuted,
+    // Configuration
+    CanisterConfigUpdated,
+    OwnerChanged,
+    ICVCConfigUpdated,
+    ProjectsUpdateTimerPaused,
+    ProjectsUpdateTimerResumed,
+    CategoryCreated,
+    CategoryDeactivated,
+    StepPhaseConfigUpdated,
+    ProposalTemplateUpdated,
+    StorageConfigUpdated,
+    DocumentTypeCreated,
+    DocumentTypeUpdated,
+    // Users
+    AdminAdded,
+    UserUpdated,
+    UserDeleted,
+}
+
+/// Entities changed by an audited operation. `entity_id` identifies the changed entity when it
+/// isn't a project, phase or step, e.g. a user principal or a document type ID.
+#[derive(CandidType, Deserialize, Clone, Debug, Default)]
+pub struct AuditTarget {
+    pub project_id: Option<ProjectId>,
+    pub step_phase_id: Option<StepPhaseId>,
+    pub step_id: Option<StepId>,
+    pub entity_id: Option<String>,
+}
+
+impl AuditTarget {
+    pub fn project(project_id: ProjectId) -> Self {
+        Self {
+            project_id: Some(project_id),
+            ..Default::default()
+        }
+    }
+
+    pub fn step_phase(project_id: ProjectId, step_phase_id: StepPhaseId) -> Self {
+        Self {
+            project_id: Some(project_id),
+            step_phase_id: Some(step_phase_id),
+            ..Default::default()
+        }
+    }
+
+    pub fn step(project_id: ProjectId, step_phase_id: StepPhaseId, step_id: StepId) -> Self {
+        Self {
+            project_id: Some(project_id),
+            step_phase_id: Some(step_phase_id),
+            step_id: Some(step_id),
+            ..Default::default()
+        }
+    }
+
+    pub fn entity(entity_id: impl ToString) -> Self {
+        Self {
+            entity_id: Some(entity_id.to_string()),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_entity(self, entity_id: impl ToString) -> Self {
+        Self {
+            entity_id: Some(entity_id.to_string()),
+            ..self
+        }
+    }
+}
+
+/// Entry of the audit log. The summaries describe the changed values before and after the
+/// operation, the actor of the operations run by the timer is the canister itself.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct AuditEntry {
+    pub id: AuditEntryId,
+    pub actor: Principal,
+    pub action: AuditAction,
+    pub target: AuditTarget,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub timestamp: u64,
+}
+
+/// Page of the audit log. `next_start_at` is set when the log wasn't read to the end, the
+/// next page starts at this ID even if the page holds fewer entries than requested.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct AuditLogPage {
+    pub entries: Vec<AuditEntry>,
+    pub next_start_at: Option<AuditEntryId>,
+}
+
+/// Filter of the audit log, only the entries matching all the fields set are returned. The
+/// timestamps are inclusive.
+#[derive(CandidType, Deserialize, Clone, Debug, Default)]
+pub struct AuditLogFilter {
+    pub actor: Option<Principal>,
+    pub action: Option<AuditAction>,
+    pub project_id: Option<ProjectId>,
+    pub step_phase_id: Option<StepPhaseId>,
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+}
@@ -0,0 +1,28 @@
+use candid::{CandidType, Deserialize, Principal};
+use candid::{Decode, Encode};
+use ic_stable_structures::{storable::Bound, Storable};
+use std::borrow::Cow;
+
+use super::types::{AuditAction, AuditTarget};
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct AuditEntryModel {
+    pub actor: Principal,
+    pub action: AuditAction,
+    pub target: AuditTarget,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub timestamp: u64,
+}
+
+impl Storable for AuditEntryModel {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
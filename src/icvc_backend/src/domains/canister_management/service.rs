@@ -1,6 +1,12 @@
 use candid::Principal;
 
-use crate::{repository, APIError};
+use crate::{
+    domains::audit::{
+        self,
+        types::{AuditAction, AuditTarget},
+    },
+    repository, APIError,
+};
 
 use super::{types::CanisterConfigUpdate, types_storage::CanisterConfig};
 
@@ -8,27 +14,42 @@ pub fn get_canister_config() -> CanisterConfig {
     repository::get_canister_config()
 }
 
-pub fn validate_update_canister_config(canister_config_update: CanisterConfigUpdate) -> Result<String, String>  {
+pub fn validate_update_canister_config(
+    canister_config_update: CanisterConfigUpdate,
+) -> Result<String, String> {
     let current_canister_config = repository::get_canister_config();
-    
-    match repository::update_canister_config(canister_config_update.clone()){
-        Ok(_) =>  {
+
+    match repository::update_canister_config(canister_config_update.clone()) {
+        Ok(_) => {
             let _ = repository::set_canister_config(current_canister_config.clone());
             Ok(format!(
-                "Update canister config is valid for votting with values: {:?}", canister_config_update
+                "Update canister config is valid for votting with values: {:?}",
+                canister_config_update
             ))
-        },
-        _ =>  Err(format!(
-            "Unable to validate proposal: update canister config with values: {:?}", canister_config_update
-        ))
+        }
+        _ => Err(format!(
+            "Unable to validate proposal: update canister config with values: {:?}",
+            canister_config_update
+        )),
     }
 }
 
 pub fn update_canister_config(
     update_canister_config: CanisterConfigUpdate,
 ) -> Result<CanisterConfig, APIError> {
+    let previous_config = repository::get_canister_config();
+
     match repository::update_canister_config(update_canister_config) {
-        Ok(config) => Ok(config),
+        Ok(config) => {
+            audit::service::record(
+                ic_cdk::caller(),
+                AuditAction::CanisterConfigUpdated,
+                AuditTarget::default(),
+                Some(format!("{:?}", previous_config)),
+                Some(format!("{:?}", config)),
+            );
+            Ok(config)
+        }
         Err(_) => Err(APIError::InternalServerError(
             "Unable to update config".to_string(),
         )),
@@ -36,8 +57,19 @@ pub fn update_canister_config(
 }
 
 pub fn set_owner(owner: Principal) -> Result<bool, APIError> {
+    let previous_owner = repository::get_canister_config().owner;
+
     match repository::set_owner(owner) {
-        Ok(_) => Ok(true),
+        Ok(_) => {
+            audit::service::record(
+                ic_cdk::caller(),
+                AuditAction::OwnerChanged,
+                AuditTarget::default(),
+                previous_owner.map(|owner| owner.to_text()),
+                Some(owner.to_text()),
+            );
+            Ok(true)
+        }
         Err(_) => Err(APIError::InternalServerError(
             "Unable to remove owner".to_string(),
         )),
@@ -0,0 +1,54 @@
+//! This module defines the controller functions for the projects update scheduler.
+
+use crate::{utils::authenticator::check_is_owner_or_admin, APIError};
+
+use super::{
+    service,
+    types::{ProjectsUpdateSummary, ProjectsUpdateTimerStatus},
+};
+
+/// Pauses the timer that processes project and step phase deadlines.
+///
+/// # Returns
+/// * `Result<ProjectsUpdateTimerStatus, APIError>` - The status of the timer or an error.
+#[ic_cdk::update(name = "pauseProjectsUpdateTimer")]
+pub fn pause_projects_update_timer() -> Result<ProjectsUpdateTimerStatus, APIError> {
+    let caller_id = ic_cdk::caller();
+    check_is_owner_or_admin(caller_id)?;
+
+    service::pause_update_projects_timer()
+}
+
+/// Resumes the timer that processes project and step phase deadlines, using the configured
+/// interval.
+///
+/// # Returns
+/// * `Result<ProjectsUpdateTimerStatus, APIError>` - The status of the timer or an error.
+#[ic_cdk::update(name = "resumeProjectsUpdateTimer")]
+pub fn resume_projects_update_timer() -> Result<ProjectsUpdateTimerStatus, APIError> {
+    let caller_id = ic_cdk::caller();
+    check_is_owner_or_admin(caller_id)?;
+
+    service::resume_update_projects_timer()
+}
+
+/// Processes a batch of due project and step phase deadlines right away.
+///
+/// # Returns
+/// * `Result<ProjectsUpdateSummary, APIError>` - The transitions applied during the run or an error.
+#[ic_cdk::update(name = "runProjectsUpdate")]
+pub fn run_projects_update() -> Result<ProjectsUpdateSummary, APIError> {
+    let caller_id = ic_cdk::caller();
+    check_is_owner_or_admin(caller_id)?;
+
+    Ok(service::run_update_projects())
+}
+
+/// Retrieves the status of the projects update timer.
+///
+/// # Returns
+/// * `Result<ProjectsUpdateTimerStatus, APIError>` - The status of the timer.
+#[ic_cdk::query(name = "getProjectsUpdateTimerStatus")]
+pub fn get_projects_update_timer_status() -> Result<ProjectsUpdateTimerStatus, APIError> {
+    Ok(service::get_update_projects_timer_status())
+}
@@ -1,3 +1,4 @@
+pub mod controller;
 pub mod service;
 pub mod types;
 pub mod types_storage;
@@ -2,21 +2,35 @@ use std::{cell::RefCell, time::Duration};
 
 use crate::{
     domains::{
+        audit::{
+            self,
+            types::{AuditAction, AuditTarget},
+        },
+        icvc_configuration,
         project::types::{Project, ProjectStatus},
+        sns_integration,
         step::{
             self,
-            types::{AssessmentMethod, StepPhase, StepPhaseStatus},
+            types::{AssessmentMethod, StepPhase, StepPhaseId, StepPhaseStatus},
         },
     },
     repository,
 };
+
+use super::types::{APIError, PhaseTransition, ProjectsUpdateSummary, ProjectsUpdateTimerStatus};
 use ic_cdk_timers::TimerId;
 
+const MAX_DUE_PHASES_PER_BATCH: usize = 50;
+
 thread_local! {
     static TIMER_IDS: RefCell<Vec<TimerId>> = RefCell::new(Vec::new());
+    static SNS_NEURONS_TIMER_ID: RefCell<Option<TimerId>> = const { RefCell::new(None) };
 }
 
 pub fn start_update_projects_timer(secs: u64) {
+    // Never keep more than one interval running.
+    stop_update_projects_timer();
+
     let secs = Duration::from_secs(secs);
     ic_cdk::println!("Timer canister: Starting a new timer with {secs:?} interval...");
     let timer_id = ic_cdk_timers::set_timer_interval(secs, update_projects_status_and_phases);
@@ -24,56 +38,226 @@ pub fn start_update_projects_timer(secs: u64) {
     TIMER_IDS.with(|timer_ids| timer_ids.borrow_mut().push(timer_id));
 }
 
-//TODO: Move the code bellow to another module
-//Check project deadlines, update status and phases
-pub fn update_projects_status_and_phases() {
-    let projects = repository::retrieve_all_projects(None, None);
-    let current_time = ic_cdk::api::time();
+pub fn stop_update_projects_timer() {
+    TIMER_IDS.with(|timer_ids| {
+        for timer_id in timer_ids.borrow_mut().drain(..) {
+            ic_cdk_timers::clear_timer(timer_id);
+        }
+    });
+}
 
-    //ic_cdk::println!("len: {} ", projects.len());
+/// Restarts the timer with the configured interval, unless the scheduler is paused.
+pub fn restart_update_projects_timer() {
+    if icvc_configuration::service::is_projects_update_timer_paused() {
+        ic_cdk::println!("Timer canister: Scheduler is paused, timer not started.");
+        stop_update_projects_timer();
+        return;
+    }
 
-    for project in projects {
-        if project.status != ProjectStatus::Open {
-            continue;
+    let config = icvc_configuration::service::get_icvc_config();
+    start_update_projects_timer(config.projects_update_timer_interval);
+}
+
+/// Refreshes the SNS neurons on their own interval, so operators see problems before
+/// proposals fail without adding inter-canister calls to every projects update.
+pub fn start_refresh_sns_neurons_timer(secs: u64) {
+    let secs = Duration::from_secs(secs);
+    ic_cdk::println!("Timer canister: Refreshing the SNS neurons every {secs:?}...");
+    let timer_id = ic_cdk_timers::set_timer_interval(secs, refresh_sns_neurons);
+
+    if let Some(previous_timer_id) =
+        SNS_NEURONS_TIMER_ID.with(|cell| cell.borrow_mut().replace(timer_id))
+    {
+        ic_cdk_timers::clear_timer(previous_timer_id);
+    }
+}
+
+/// Restarts the SNS neurons timer with the configured interval.
+pub fn restart_refresh_sns_neurons_timer() {
+    start_refresh_sns_neurons_timer(
+        icvc_configuration::service::get_sns_neurons_refresh_interval(),
+    );
+}
+
+fn refresh_sns_neurons() {
+    ic_cdk::spawn(async {
+        if let Err(e) = sns_integration::service::refresh_sns_neurons().await {
+            ic_cdk::println!("Unable to refresh the SNS neurons: {}", e);
         }
+    });
+}
+
+pub fn pause_update_projects_timer() -> Result<ProjectsUpdateTimerStatus, APIError> {
+    repository::set_projects_update_timer_paused(true).map_err(|_| {
+        APIError::InternalServerError("Unable to pause the projects update timer".to_string())
+    })?;
+    stop_update_projects_timer();
+    audit::service::record(
+        ic_cdk::caller(),
+        AuditAction::ProjectsUpdateTimerPaused,
+        AuditTarget::default(),
+        None,
+        None,
+    );
+
+    Ok(get_update_projects_timer_status())
+}
+
+pub fn resume_update_projects_timer() -> Result<ProjectsUpdateTimerStatus, APIError> {
+    repository::set_projects_update_timer_paused(false).map_err(|_| {
+        APIError::InternalServerError("Unable to resume the projects update timer".to_string())
+    })?;
+    restart_update_projects_timer();
+    audit::service::record(
+        ic_cdk::caller(),
+        AuditAction::ProjectsUpdateTimerResumed,
+        AuditTarget::default(),
+        None,
+        None,
+    );
+
+    Ok(get_update_projects_timer_status())
+}
+
+pub fn get_update_projects_timer_status() -> ProjectsUpdateTimerStatus {
+    let config = icvc_configuration::service::get_icvc_config();
+
+    ProjectsUpdateTimerStatus {
+        paused: config.projects_update_timer_paused.unwrap_or(false),
+        running: TIMER_IDS.with(|timer_ids| !timer_ids.borrow().is_empty()),
+        interval: config.projects_update_timer_interval,
+    }
+}
+
+/// Processes one batch of due phases on demand, even while the scheduler is paused.
+pub fn run_update_projects() -> ProjectsUpdateSummary {
+    process_due_phases(ic_cdk::api::time())
+}
+
+//TODO: Move the code bellow to another module
+//Check due phase deadlines, update status and phases
+pub fn update_projects_status_and_phases() {
+    // A batch scheduled before pausing may still fire.
+    if icvc_configuration::service::is_projects_update_timer_paused() {
+        return;
+    }
+
+    let summary = process_due_phases(ic_cdk::api::time());
 
-        let step_phases = repository::get_all_phases_by_project(project.id);
-        process_phases(&project, step_phases, current_time);
+    if summary.has_more {
+        ic_cdk_timers::set_timer(Duration::ZERO, update_projects_status_and_phases);
     }
 }
 
-fn process_phases(project: &Project, phases: Vec<StepPhase>, current_time: u64) {
-    for phase in phases {
-        if project.current_phase != phase.id {
+fn process_due_phases(current_time: u64) -> ProjectsUpdateSummary {
+    let due_deadlines =
+        repository::get_due_step_phase_deadlines(current_time, MAX_DUE_PHASES_PER_BATCH);
+    let mut summary = ProjectsUpdateSummary {
+        run_at: current_time,
+        processed: due_deadlines.len() as u64,
+        transitions: vec![],
+        vote_phases_checked: 0,
+        pending_proposals_retried: 0,
+        has_more: due_deadlines.len() == MAX_DUE_PHASES_PER_BATCH,
+    };
+
+    for (deadline, project_id, step_phase_id) in due_deadlines {
+        // Phases that move to another open or submitted state are indexed again by the repository.
+        repository::remove_step_phase_deadline(deadline, project_id, step_phase_id);
+
+        let project = match repository::retrieve_project_by_id(project_id) {
+            Some(project) => project,
+            None => continue,
+        };
+
+        if project.status != ProjectStatus::Open || project.current_phase != step_phase_id {
             continue;
         }
 
-        /*
-        ic_cdk::println!(
-            "Checking project id {} - status: {} - phase: {} - status: {} ",
-            project.id,
-            project.status,
-            phase.id,
-            phase.status
-        );
-        */
-        match phase.status {
-            StepPhaseStatus::Open if current_time > phase.end_open_date => {
-                repository::update_project_status(
-                    project.user_id,
-                    project.id,
-                    ProjectStatus::NotSubmitted,
-                );
-                repository::update_step_phase_status(
-                    project.id,
-                    phase.id,
-                    StepPhaseStatus::NotSubmitted,
-                );
+        if let Some(phase) = repository::get_step_phase_by_id(project_id, step_phase_id) {
+            let previous_phase_status = phase.status.clone();
+
+            // Vote phases are finalized asynchronously once the SNS proposal is checked.
+            if phase.status == StepPhaseStatus::Submitted
+                && phase.assessment_method == AssessmentMethod::Vote
+            {
+                summary.vote_phases_checked += 1;
+            }
+
+            process_phase(&project, phase, current_time);
+
+            if let Some(transition) =
+                get_phase_transition(&project, step_phase_id, previous_phase_status)
+            {
+                summary.transitions.push(transition);
             }
-            StepPhaseStatus::Submitted => update_submitted_phase(project, phase, current_time),
-            _ => (),
         }
     }
+
+    summary.pending_proposals_retried =
+        sns_integration::service::process_pending_proposals(current_time);
+
+    summary
+}
+
+fn get_phase_transition(
+    project: &Project,
+    step_phase_id: StepPhaseId,
+    previous_phase_status: StepPhaseStatus,
+) -> Option<PhaseTransition> {
+    let new_project_status = repository::retrieve_project_by_id(project.id)?.status;
+    let new_phase_status = repository::get_step_phase_by_id(project.id, step_phase_id)?.status;
+
+    if new_project_status == project.status && new_phase_status == previous_phase_status {
+        return None;
+    }
+
+    Some(PhaseTransition {
+        project_id: project.id,
+        step_phase_id,
+        previous_project_status: project.status.clone(),
+        new_project_status,
+        previous_phase_status,
+        new_phase_status,
+    })
+}
+
+/// Rebuilds the phase deadline index when it is empty, e.g. after upgrading from a version
+/// without it.
+pub fn init_step_phase_deadline_index() {
+    if repository::is_step_phase_deadline_index_empty() {
+        let count = repository::rebuild_step_phase_deadline_index();
+        ic_cdk::println!("Indexed {} step phase deadlines.", count);
+    }
+}
+
+fn process_phase(project: &Project, phase: StepPhase, current_time: u64) {
+    /*
+    ic_cdk::println!(
+        "Checking project id {} - status: {} - phase: {} - status: {} ",
+        project.id,
+        project.status,
+        phase.id,
+        phase.status
+    );
+    */
+    match phase.status {
+        StepPhaseStatus::Open if current_time > phase.end_open_date => {
+            repository::update_project_status(
+                project.user_id,
+                project.id,
+                ProjectStatus::NotSubmitted,
+            );
+            repository::update_step_phase_status(
+                project.id,
+                phase.id,
+                StepPhaseStatus::NotSubmitted,
+            );
+            audit_phase_transition(project, phase.id, phase.status);
+        }
+        StepPhaseStatus::Submitted => update_submitted_phase(project, phase, current_time),
+        _ => (),
+    }
 }
 
 fn update_submitted_phase(project: &Project, phase: StepPhase, current_time: u64) {
@@ -82,6 +266,14 @@ fn update_submitted_phase(project: &Project, phase: StepPhase, current_time: u64
         return;
     }
 
+    if phase.assessment_method == AssessmentMethod::Vote {
+        // Executed proposals are handled by SNS governance, this covers the ones that never are.
+        ic_cdk::spawn(sns_integration::service::finalize_expired_vote_phase(
+            project.id, phase.id,
+        ));
+        return;
+    }
+
     let approved = match phase.assessment_method {
         //AssessmentMethod::Vote => true,
         AssessmentMethod::Grade => {
@@ -98,6 +290,8 @@ fn update_submitted_phase(project: &Project, phase: StepPhase, current_time: u64
 
 // Update the status of the project and phase based on assessment results
 pub fn update_phase_status(project: &Project, phase: StepPhase, approved: bool) {
+    let (step_phase_id, previous_phase_status) = (phase.id, phase.status.clone());
+
     if approved {
         if let Some(next_phase) = check_next_phase(project) {
             let _ = match step::service::create_step_phase(project.user_id, project.id, next_phase)
@@ -124,11 +318,37 @@ pub fn update_phase_status(project: &Project, phase: StepPhase, approved: bool)
         repository::update_project_status(project.user_id, project.id, ProjectStatus::NotFunded);
         repository::update_step_phase_status(project.id, phase.id, StepPhaseStatus::NotApproved);
     }
+
+    audit_phase_transition(project, step_phase_id, previous_phase_status);
+}
+
+/// Records the new status of a phase and of its project, if any of them changed.
+fn audit_phase_transition(
+    project: &Project,
+    step_phase_id: StepPhaseId,
+    previous_phase_status: StepPhaseStatus,
+) {
+    if let Some(transition) = get_phase_transition(project, step_phase_id, previous_phase_status) {
+        audit::service::record(
+            ic_cdk::caller(),
+            AuditAction::StepPhaseStatusChanged,
+            AuditTarget::step_phase(project.id, step_phase_id),
+            Some(format!(
+                "phase: {:?}, project: {:?}",
+                transition.previous_phase_status, transition.previous_project_status
+            )),
+            Some(format!(
+                "phase: {:?}, project: {:?}",
+                transition.new_phase_status, transition.new_project_status
+            )),
+        );
+    }
 }
 
 // Finalize the project if no more phases are left
 fn finalize_project(project: &Project, last_phase: StepPhase) {
     if last_phase.assessment_method == AssessmentMethod::Vote {
+        repository::update_step_phase_status(project.id, last_phase.id, StepPhaseStatus::Approved);
         repository::update_project_status(project.user_id, project.id, ProjectStatus::Funded);
         ic_cdk::println!("Project {} Funded", project.id);
     } else {
@@ -136,7 +356,7 @@ fn finalize_project(project: &Project, last_phase: StepPhase) {
     }
 }
 
-fn check_next_phase(project: &Project) -> Option<u64> {
+pub fn check_next_phase(project: &Project) -> Option<u64> {
     let next_phase = project.current_phase + 1;
     let phase_count = repository::get_step_phases_config_count();
 
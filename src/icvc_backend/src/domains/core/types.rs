@@ -2,6 +2,8 @@ use std::fmt;
 
 use candid::{CandidType, Deserialize};
 
+use crate::{ProjectId, ProjectStatus, StepPhaseId, StepPhaseStatus};
+
 #[derive(CandidType, Deserialize, Debug)]
 pub enum APIError {
     BadRequest(String),
@@ -33,3 +35,30 @@ impl fmt::Display for APIError {
         }
     }
 }
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct PhaseTransition {
+    pub project_id: ProjectId,
+    pub step_phase_id: StepPhaseId,
+    pub previous_project_status: ProjectStatus,
+    pub new_project_status: ProjectStatus,
+    pub previous_phase_status: StepPhaseStatus,
+    pub new_phase_status: StepPhaseStatus,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ProjectsUpdateSummary {
+    pub run_at: u64,
+    pub processed: u64,
+    pub transitions: Vec<PhaseTransition>,
+    pub vote_phases_checked: u64,
+    pub pending_proposals_retried: u64,
+    pub has_more: bool,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ProjectsUpdateTimerStatus {
+    pub paused: bool,
+    pub running: bool,
+    pub interval: u64,
+}
@@ -1 +1,15 @@
 pub const MAX_CATEGORY_BYTES: usize = 32;
+pub const MAX_ADMIN_REASON_BYTES: usize = 1000;
+pub const MAX_BASIS_POINTS: u64 = 10_000;
+pub const DEFAULT_PROJECT_VOTE_FUNCTION_ID: u64 = 4001;
+pub const MIN_GENERIC_FUNCTION_ID: u64 = 1000;
+pub const PENDING_PROPOSAL_BASE_BACKOFF: u64 = 60; // 1 minute in seconds
+pub const PENDING_PROPOSAL_MAX_BACKOFF: u64 = 24 * 60 * 60; // 1 day in seconds
+pub const MAX_STEP_PHASE_NAME_BYTES: usize = 64;
+// SNS governance limits for proposals
+pub const MAX_PROPOSAL_TITLE_BYTES: usize = 256;
+pub const MAX_PROPOSAL_SUMMARY_BYTES: usize = 30000;
+pub const MAX_PROPOSAL_URL_BYTES: usize = 2048;
+pub const MAX_APPEAL_JUSTIFICATION_BYTES: usize = 5000;
+pub const DEFAULT_APPEAL_DURATION: u64 = 7 * 24 * 60 * 60; // 7 days in seconds
+pub const DEFAULT_SNS_NEURONS_REFRESH_INTERVAL: u64 = 6 * 60 * 60; // 6 hours in seconds
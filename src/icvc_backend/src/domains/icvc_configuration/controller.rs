@@ -1,8 +1,14 @@
-use crate::{utils::authenticator::check_is_owner_or_governance_id, APIError, ICVCConfigUpdate};
+use crate::{
+    utils::authenticator::check_is_owner_or_governance_id, APIError, DocumentTypeId,
+    ICVCConfigUpdate, StepConfig, StepId, StepPhaseId,
+};
 
 use super::{
     service,
-    types::{Category, CategoryCreate},
+    types::{
+        Category, CategoryCreate, CategoryProposalPayload, ProposalTemplate, StepPhaseConfig,
+        StepPhaseConfigProposalPayload, VoteRules,
+    },
     types_storage::ICVCConfig,
 };
 
@@ -44,3 +50,119 @@ pub fn delete_category(category_id: u64) -> Result<Category, APIError> {
     check_is_owner_or_governance_id(caller_id)?;
     service::desactivate_category_by_id(category_id)
 }
+
+#[ic_cdk::query(name = "getAllStepPhaseConfigs")]
+pub fn get_all_step_phase_configs() -> Result<Vec<StepPhaseConfig>, APIError> {
+    Ok(service::get_all_step_phase_configs())
+}
+
+#[ic_cdk::update(name = "updateStepPhaseVoteRules")]
+pub fn update_step_phase_vote_rules(
+    step_phase_id: StepPhaseId,
+    vote_rules: VoteRules,
+) -> Result<StepPhaseConfig, APIError> {
+    let caller_id = ic_cdk::caller();
+    check_is_owner_or_governance_id(caller_id)?;
+    service::update_step_phase_vote_rules(step_phase_id, vote_rules)
+}
+
+#[ic_cdk::update(name = "updateStepPhaseName")]
+pub fn update_step_phase_name(
+    step_phase_id: StepPhaseId,
+    name: String,
+) -> Result<StepPhaseConfig, APIError> {
+    let caller_id = ic_cdk::caller();
+    check_is_owner_or_governance_id(caller_id)?;
+    service::update_step_phase_name(step_phase_id, name)
+}
+
+/// Sets the document types a step requires, in the order they are listed to the projects.
+/// The projects already in the step phase keep their documents, and have to upload the newly
+/// required ones before submitting it.
+///
+/// # Arguments
+/// * `step_phase_id` - The ID of the step phase.
+/// * `step_id` - The ID of the step.
+/// * `required_upload_files` - The IDs of the active document types required by the step.
+///
+/// # Returns
+/// * `Result<StepConfig, APIError>` - The updated step config or an error.
+#[ic_cdk::update(name = "updateStepRequiredUploadFiles")]
+pub fn update_step_required_upload_files(
+    step_phase_id: StepPhaseId,
+    step_id: StepId,
+    required_upload_files: Vec<DocumentTypeId>,
+) -> Result<StepConfig, APIError> {
+    let caller_id = ic_cdk::caller();
+    check_is_owner_or_governance_id(caller_id)?;
+    service::update_step_required_upload_files(step_phase_id, step_id, required_upload_files)
+}
+
+#[ic_cdk::update(name = "updateProjectVoteProposalTemplate")]
+pub fn update_project_vote_proposal_template(
+    template: ProposalTemplate,
+) -> Result<ICVCConfig, APIError> {
+    let caller_id = ic_cdk::caller();
+    check_is_owner_or_governance_id(caller_id)?;
+    service::update_project_vote_proposal_template(template)
+}
+
+#[ic_cdk::update]
+fn validate_category_proposal(payload: CategoryProposalPayload) -> Result<String, String> {
+    let caller_id = ic_cdk::caller();
+
+    match check_is_owner_or_governance_id(caller_id) {
+        Ok(_) => service::validate_category_proposal(payload),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+#[ic_cdk::update]
+fn execute_category_proposal(payload: CategoryProposalPayload) {
+    let caller_id = ic_cdk::caller();
+
+    match check_is_owner_or_governance_id(caller_id) {
+        Ok(_) => {
+            if let Err(err) = service::execute_category_proposal(payload) {
+                ic_cdk::println!("Unable to execute category proposal: {}", err.to_string());
+            }
+        }
+        Err(err) => {
+            ic_cdk::println!("Unable to execute category proposal: {}", err.to_string());
+        }
+    }
+}
+
+#[ic_cdk::update]
+fn validate_step_phase_config_proposal(
+    payload: StepPhaseConfigProposalPayload,
+) -> Result<String, String> {
+    let caller_id = ic_cdk::caller();
+
+    match check_is_owner_or_governance_id(caller_id) {
+        Ok(_) => service::validate_step_phase_config_proposal(payload),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+#[ic_cdk::update]
+fn execute_step_phase_config_proposal(payload: StepPhaseConfigProposalPayload) {
+    let caller_id = ic_cdk::caller();
+
+    match check_is_owner_or_governance_id(caller_id) {
+        Ok(_) => {
+            if let Err(err) = service::execute_step_phase_config_proposal(payload) {
+                ic_cdk::println!(
+                    "Unable to execute step phase config proposal: {}",
+                    err.to_string()
+                );
+            }
+        }
+        Err(err) => {
+            ic_cdk::println!(
+                "Unable to execute step phase config proposal: {}",
+                err.to_string()
+            );
+        }
+    }
+}
@@ -1,19 +1,36 @@
 use crate::{
     domains::{
+        audit::{
+            self,
+            types::{AuditAction, AuditTarget},
+        },
+        core,
         icvc_configuration::types::{
             CheckBoxConfig, CheckBoxConfigCreate, DecimalValueConfig, DecimalValueConfigCreate,
-            QuestionConfig, QuestionConfigCreate, StepConfigCreate, StepConfigCreateDefault,
-            StepPhaseConfigCreate,
+            ProposalTemplate, QuestionConfig, QuestionConfigCreate, StepConfigCreate,
+            StepConfigCreateDefault, StepPhaseConfig, StepPhaseConfigCreate,
+            StepPhaseConfigProposalPayload, VoteRules,
+        },
+        storage::{
+            self,
+            constants::{
+                COVER_PHOTO_DOCUMENT_TYPE_ID, EXPENDITURE_PLAN_DOCUMENT_TYPE_ID,
+                FINANCIAL_MODELS_DOCUMENT_TYPE_ID, LOGO_DOCUMENT_TYPE_ID,
+                PITCH_DECK_DOCUMENT_TYPE_ID, PRODUCT_DEMO_DOCUMENT_TYPE_ID,
+            },
         },
-        project::types::DocumentType,
     },
     repository::{self, generate_category_id},
-    APIError, AssessmentMethod, ICVCConfigUpdate, StepId, StepPhaseId,
+    APIError, AssessmentMethod, DocumentTypeId, ICVCConfigUpdate, StepConfig, StepId, StepPhaseId,
 };
 
 use super::{
-    constants::MAX_CATEGORY_BYTES,
-    types::{Category, CategoryCreate},
+    constants::{
+        DEFAULT_APPEAL_DURATION, DEFAULT_SNS_NEURONS_REFRESH_INTERVAL, MAX_BASIS_POINTS,
+        MAX_CATEGORY_BYTES, MAX_PROPOSAL_SUMMARY_BYTES, MAX_PROPOSAL_TITLE_BYTES,
+        MAX_PROPOSAL_URL_BYTES, MAX_STEP_PHASE_NAME_BYTES, MIN_GENERIC_FUNCTION_ID,
+    },
+    types::{Category, CategoryCreate, CategoryProposalPayload},
     types_storage::ICVCConfig,
 };
 
@@ -32,24 +49,401 @@ pub fn get_assessment_duration() -> u64 {
     repository::get_icvc_config().assessment_duration
 }
 
+pub fn get_appeal_duration() -> u64 {
+    repository::get_icvc_config()
+        .appeal_duration
+        .unwrap_or(DEFAULT_APPEAL_DURATION)
+}
+
+pub fn get_sns_neurons_refresh_interval() -> u64 {
+    repository::get_icvc_config()
+        .sns_neurons_refresh_interval
+        .unwrap_or(DEFAULT_SNS_NEURONS_REFRESH_INTERVAL)
+}
+
 pub fn get_icvc_config() -> ICVCConfig {
     repository::get_icvc_config()
 }
 
 pub fn update_icvc_config(update_icvc_config: ICVCConfigUpdate) -> Result<ICVCConfig, APIError> {
+    if update_icvc_config.projects_update_timer_interval == Some(0) {
+        return Err(APIError::BadRequest(
+            "The projects update timer interval must be greater than 0.".to_string(),
+        ));
+    }
+
+    if update_icvc_config.sns_neurons_refresh_interval == Some(0) {
+        return Err(APIError::BadRequest(
+            "The SNS neurons refresh interval must be greater than 0.".to_string(),
+        ));
+    }
+
+    let interval_updated = update_icvc_config.projects_update_timer_interval.is_some();
+    let sns_neurons_interval_updated = update_icvc_config.sns_neurons_refresh_interval.is_some();
+    let previous_config = repository::get_icvc_config();
+
     match repository::update_icvc_config(update_icvc_config) {
-        Ok(config) => Ok(config),
+        Ok(config) => {
+            audit::service::record(
+                ic_cdk::caller(),
+                AuditAction::ICVCConfigUpdated,
+                AuditTarget::default(),
+                Some(format!("{:?}", previous_config)),
+                Some(format!("{:?}", config)),
+            );
+            // Apply the new interval right away instead of on the next upgrade.
+            if interval_updated {
+                core::service::restart_update_projects_timer();
+            }
+            if sns_neurons_interval_updated {
+                core::service::restart_refresh_sns_neurons_timer();
+            }
+            Ok(config)
+        }
         Err(_) => Err(APIError::InternalServerError(
             "Unable to update config".to_string(),
         )),
     }
 }
 
+pub fn is_projects_update_timer_paused() -> bool {
+    repository::get_icvc_config()
+        .projects_update_timer_paused
+        .unwrap_or(false)
+}
+
+pub fn get_all_step_phase_configs() -> Vec<StepPhaseConfig> {
+    repository::get_all_step_phase_configs()
+}
+
+/// Vote rules of a step phase, phases configured before vote rules existed follow the SNS decision.
+pub fn get_step_phase_vote_rules(step_phase_id: StepPhaseId) -> VoteRules {
+    repository::get_step_phase_config_by_id(step_phase_id)
+        .and_then(|step_phase_config| step_phase_config.vote_rules)
+        .unwrap_or_default()
+}
+
+pub fn update_step_phase_vote_rules(
+    step_phase_id: StepPhaseId,
+    vote_rules: VoteRules,
+) -> Result<StepPhaseConfig, APIError> {
+    check_step_phase_vote_rules(step_phase_id, &vote_rules)?;
+    let previous_vote_rules = get_step_phase_vote_rules(step_phase_id);
+
+    let step_phase_config = repository::update_step_phase_vote_rules(step_phase_id, vote_rules)
+        .ok_or_else(|| {
+            APIError::NotFound(format!(
+                "Step phase config with id: {} not found.",
+                step_phase_id
+            ))
+        })?;
+    audit_step_phase_config_update(
+        step_phase_id,
+        None,
+        format!("vote rules: {:?}", previous_vote_rules),
+        format!(
+            "vote rules: {:?}",
+            step_phase_config.vote_rules.clone().unwrap_or_default()
+        ),
+    );
+
+    Ok(step_phase_config)
+}
+
+fn check_step_phase_vote_rules(
+    step_phase_id: StepPhaseId,
+    vote_rules: &VoteRules,
+) -> Result<(), APIError> {
+    if vote_rules.min_yes_ratio > MAX_BASIS_POINTS
+        || vote_rules.min_participation > MAX_BASIS_POINTS
+    {
+        return Err(APIError::BadRequest(format!(
+            "Vote ratios are expressed in basis points and can't be greater than {}.",
+            MAX_BASIS_POINTS
+        )));
+    }
+
+    let step_phase_config =
+        repository::get_step_phase_config_by_id(step_phase_id).ok_or_else(|| {
+            APIError::NotFound(format!(
+                "Step phase config with id: {} not found.",
+                step_phase_id
+            ))
+        })?;
+
+    if step_phase_config.assessment_method != AssessmentMethod::Vote {
+        return Err(APIError::BadRequest(format!(
+            "Step phase config with id: {} is assessed by {}, not by vote.",
+            step_phase_id, step_phase_config.assessment_method
+        )));
+    }
+
+    Ok(())
+}
+
+/// Name of a step phase, phases configured before names existed are named after their id.
+pub fn get_step_phase_name(step_phase_id: StepPhaseId) -> String {
+    repository::get_step_phase_config_by_id(step_phase_id)
+        .and_then(|step_phase_config| step_phase_config.name)
+        .unwrap_or_else(|| format!("Phase {}", step_phase_id))
+}
+
+pub fn update_step_phase_name(
+    step_phase_id: StepPhaseId,
+    name: String,
+) -> Result<StepPhaseConfig, APIError> {
+    check_step_phase_name(&name)?;
+    let previous_name = get_step_phase_name(step_phase_id);
+
+    let step_phase_config = repository::update_step_phase_name(step_phase_id, name.clone())
+        .ok_or_else(|| {
+            APIError::NotFound(format!(
+                "Step phase config with id: {} not found.",
+                step_phase_id
+            ))
+        })?;
+    audit_step_phase_config_update(
+        step_phase_id,
+        None,
+        format!("name: {}", previous_name),
+        format!("name: {}", name),
+    );
+
+    Ok(step_phase_config)
+}
+
+pub fn update_step_required_upload_files(
+    step_phase_id: StepPhaseId,
+    step_id: StepId,
+    required_upload_files: Vec<DocumentTypeId>,
+) -> Result<StepConfig, APIError> {
+    for (index, document_type_id) in required_upload_files.iter().enumerate() {
+        if required_upload_files[..index].contains(document_type_id) {
+            return Err(APIError::BadRequest(format!(
+                "The document type with id: {} can only be required once.",
+                document_type_id
+            )));
+        }
+
+        let document_type = storage::service::get_document_type(*document_type_id)?;
+        if !document_type.active {
+            return Err(APIError::BadRequest(format!(
+                "The document type \"{}\" is inactive and can't be required.",
+                document_type.name
+            )));
+        }
+    }
+
+    let previous_step_config = repository::get_step_config_by_id(step_phase_id, step_id);
+
+    let step_config = repository::update_step_required_upload_files(
+        step_phase_id,
+        step_id,
+        required_upload_files,
+    )
+    .ok_or_else(|| {
+        APIError::NotFound(format!(
+            "Step config with id: {} for step phase id: {}, not found.",
+            step_id, step_phase_id
+        ))
+    })?;
+    audit_step_phase_config_update(
+        step_phase_id,
+        Some(step_id),
+        format!(
+            "required document types: {:?}",
+            previous_step_config
+                .map(|step_config| step_config.required_upload_files)
+                .unwrap_or_default()
+        ),
+        format!(
+            "required document types: {:?}",
+            step_config.required_upload_files
+        ),
+    );
+
+    Ok(step_config)
+}
+
+fn audit_step_phase_config_update(
+    step_phase_id: StepPhaseId,
+    step_id: Option<StepId>,
+    before: String,
+    after: String,
+) {
+    audit::service::record(
+        ic_cdk::caller(),
+        AuditAction::StepPhaseConfigUpdated,
+        AuditTarget {
+            step_phase_id: Some(step_phase_id),
+            step_id,
+            ..Default::default()
+        },
+        Some(before),
+        Some(after),
+    );
+}
+
+fn check_step_phase_name(name: &str) -> Result<(), APIError> {
+    if name.trim().is_empty() || name.len() > MAX_STEP_PHASE_NAME_BYTES {
+        return Err(APIError::BadRequest(format!(
+            "Step phase name must be between 1 and {} bytes.",
+            MAX_STEP_PHASE_NAME_BYTES
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn validate_step_phase_config_proposal(
+    payload: StepPhaseConfigProposalPayload,
+) -> Result<String, String> {
+    match check_step_phase_config_proposal(&payload) {
+        Ok(()) => {
+            let mut changes = vec![];
+            if let Some(name) = &payload.name {
+                changes.push(format!("- Name: {}", name));
+            }
+            if let Some(vote_rules) = &payload.vote_rules {
+                changes.push(format!(
+                    "- Vote rules: follow the SNS decision: {}, minimum yes ratio: {} bp, minimum participation: {} bp, minimum yes votes: {}",
+                    vote_rules.use_sns_decision,
+                    vote_rules.min_yes_ratio,
+                    vote_rules.min_participation,
+                    vote_rules.min_yes_votes
+                ));
+            }
+
+            Ok(format!(
+                "Update the configuration of step phase \"{}\" (id {}):\n\n{}",
+                get_step_phase_name(payload.step_phase_id),
+                payload.step_phase_id,
+                changes.join("\n")
+            ))
+        }
+        Err(e) => Err(format!(
+            "Unable to validate step phase config update: {}",
+            e
+        )),
+    }
+}
+
+pub fn execute_step_phase_config_proposal(
+    payload: StepPhaseConfigProposalPayload,
+) -> Result<StepPhaseConfig, APIError> {
+    // Everything is checked first so the update is never applied halfway.
+    check_step_phase_config_proposal(&payload)?;
+
+    if let Some(name) = payload.name {
+        update_step_phase_name(payload.step_phase_id, name)?;
+    }
+    if let Some(vote_rules) = payload.vote_rules {
+        update_step_phase_vote_rules(payload.step_phase_id, vote_rules)?;
+    }
+
+    repository::get_step_phase_config_by_id(payload.step_phase_id).ok_or_else(|| {
+        APIError::NotFound(format!(
+            "Step phase config with id: {} not found.",
+            payload.step_phase_id
+        ))
+    })
+}
+
+fn check_step_phase_config_proposal(
+    payload: &StepPhaseConfigProposalPayload,
+) -> Result<(), APIError> {
+    repository::get_step_phase_config_by_id(payload.step_phase_id).ok_or_else(|| {
+        APIError::NotFound(format!(
+            "Step phase config with id: {} not found.",
+            payload.step_phase_id
+        ))
+    })?;
+
+    if payload.name.is_none() && payload.vote_rules.is_none() {
+        return Err(APIError::BadRequest(
+            "The proposal doesn't change anything.".to_string(),
+        ));
+    }
+
+    if let Some(name) = &payload.name {
+        check_step_phase_name(name)?;
+    }
+    if let Some(vote_rules) = &payload.vote_rules {
+        check_step_phase_vote_rules(payload.step_phase_id, vote_rules)?;
+    }
+
+    Ok(())
+}
+
+pub fn get_project_vote_proposal_template() -> ProposalTemplate {
+    repository::get_icvc_config()
+        .project_vote_proposal_template
+        .unwrap_or_default()
+}
+
+pub fn update_project_vote_proposal_template(
+    template: ProposalTemplate,
+) -> Result<ICVCConfig, APIError> {
+    let mut errors = vec![];
+
+    if template.title.trim().is_empty() || template.title.len() > MAX_PROPOSAL_TITLE_BYTES {
+        errors.push(APIError::BadRequest(format!(
+            "Proposal title template must be between 1 and {} bytes.",
+            MAX_PROPOSAL_TITLE_BYTES
+        )));
+    }
+    if template.summary.trim().is_empty() || template.summary.len() > MAX_PROPOSAL_SUMMARY_BYTES {
+        errors.push(APIError::BadRequest(format!(
+            "Proposal summary template must be between 1 and {} bytes.",
+            MAX_PROPOSAL_SUMMARY_BYTES
+        )));
+    }
+    if template.url.len() > MAX_PROPOSAL_URL_BYTES || template.url.contains("{submission_url}") {
+        errors.push(APIError::BadRequest(format!(
+            "Proposal url template can't be longer than {} bytes nor contain {{submission_url}}.",
+            MAX_PROPOSAL_URL_BYTES
+        )));
+    }
+    if template.function_id < MIN_GENERIC_FUNCTION_ID {
+        errors.push(APIError::BadRequest(format!(
+            "Generic function ids start at {}.",
+            MIN_GENERIC_FUNCTION_ID
+        )));
+    }
+
+    if !errors.is_empty() {
+        return Err(APIError::MultipleErrors(errors));
+    }
+
+    let previous_template = get_project_vote_proposal_template();
+
+    let config = repository::set_project_vote_proposal_template(template).map_err(|_| {
+        APIError::InternalServerError("Unable to update the proposal template".to_string())
+    })?;
+    audit::service::record(
+        ic_cdk::caller(),
+        AuditAction::ProposalTemplateUpdated,
+        AuditTarget::default(),
+        Some(format!("{:?}", previous_template)),
+        Some(format!(
+            "{:?}",
+            config
+                .project_vote_proposal_template
+                .clone()
+                .unwrap_or_default()
+        )),
+    );
+
+    Ok(config)
+}
+
 pub fn init_default_step_phases_config() {
     let default_step_phases_config = vec![
         //Step phase 0 - Application Phase
         StepPhaseConfigCreate {
+            name: Some("Application Phase".to_string()),
             assessment_method: AssessmentMethod::Vote,
+            vote_rules: Some(VoteRules::default()),
             steps: vec![
                 //Step 0
                 StepConfigCreateDefault {
@@ -76,16 +470,18 @@ pub fn init_default_step_phases_config() {
                     checkboxes: vec![],
                     decimal_values: vec![DecimalValueConfigCreate { default_value: 0.0 }],
                     required_upload_files: vec![
-                        DocumentType::PitchDeck,
-                        DocumentType::Logo,
-                        DocumentType::CoverPhoto,
+                        PITCH_DECK_DOCUMENT_TYPE_ID,
+                        LOGO_DOCUMENT_TYPE_ID,
+                        COVER_PHOTO_DOCUMENT_TYPE_ID,
                     ],
                 },
             ],
         },
         //Step phase 1 - Evaluation Phase
         StepPhaseConfigCreate {
+            name: Some("Evaluation Phase".to_string()),
             assessment_method: AssessmentMethod::Grade,
+            vote_rules: None,
             steps: vec![
                 //step 0 - Business Model
                 StepConfigCreateDefault {
@@ -152,7 +548,7 @@ pub fn init_default_step_phases_config() {
                     questions: vec![],
                     checkboxes: vec![],
                     decimal_values: vec![],
-                    required_upload_files: vec![DocumentType::FinancialModels],
+                    required_upload_files: vec![FINANCIAL_MODELS_DOCUMENT_TYPE_ID],
                 },
                 //step 4 - Legal, Regulatory and Compliance
                 StepConfigCreateDefault {
@@ -246,7 +642,7 @@ pub fn init_default_step_phases_config() {
                     questions: vec![],
                     checkboxes: vec![],
                     decimal_values: vec![],
-                    required_upload_files: vec![DocumentType::ProductDemo],
+                    required_upload_files: vec![PRODUCT_DEMO_DOCUMENT_TYPE_ID],
                 },
                 //step 11 - 2-Year Roadmap and Expenditure
                 StepConfigCreateDefault {
@@ -260,7 +656,7 @@ pub fn init_default_step_phases_config() {
                     ],
                     checkboxes: vec![],
                     decimal_values: vec![],
-                    required_upload_files: vec![DocumentType::ExpenditurePlan],
+                    required_upload_files: vec![EXPENDITURE_PLAN_DOCUMENT_TYPE_ID],
                 },
                 //step 12 - Money raised and valuation
                 StepConfigCreateDefault {
@@ -276,7 +672,9 @@ pub fn init_default_step_phases_config() {
         },
         //Step phase 2 - Completion Phase
         StepPhaseConfigCreate {
+            name: Some("Completion Phase".to_string()),
             assessment_method: AssessmentMethod::Vote,
+            vote_rules: Some(VoteRules::default()),
             steps: vec![
                 StepConfigCreateDefault {
                     questions: vec![],
@@ -368,7 +766,16 @@ pub fn create_category(category_create: CategoryCreate) -> Result<Category, APIE
     let category_id: u64 = generate_category_id();
 
     match repository::insert_category(category_id, category_create) {
-        Some(category) => Ok(category),
+        Some(category) => {
+            audit::service::record(
+                ic_cdk::caller(),
+                AuditAction::CategoryCreated,
+                AuditTarget::entity(category.id),
+                None,
+                Some(category.name.clone()),
+            );
+            Ok(category)
+        }
         None => Err(APIError::BadRequest(
             "Failed to save the category. Category ID already exists.".to_string(),
         )),
@@ -393,7 +800,16 @@ pub fn get_all_categories() -> Result<Vec<Category>, APIError> {
 
 pub fn desactivate_category_by_id(category_id: u64) -> Result<Category, APIError> {
     match repository::desactivate_category_by_id(category_id) {
-        Some(category) => Ok(category),
+        Some(category) => {
+            audit::service::record(
+                ic_cdk::caller(),
+                AuditAction::CategoryDeactivated,
+                AuditTarget::entity(category.id),
+                Some(category.name.clone()),
+                None,
+            );
+            Ok(category)
+        }
         None => Err(APIError::NotFound(format!(
             "Category with id {} not found.",
             category_id
@@ -401,6 +817,69 @@ pub fn desactivate_category_by_id(category_id: u64) -> Result<Category, APIError
     }
 }
 
+pub fn validate_category_proposal(payload: CategoryProposalPayload) -> Result<String, String> {
+    match check_category_proposal(&payload) {
+        Ok(()) => Ok(match payload {
+            CategoryProposalPayload::Create(category_create) => {
+                format!("Create the project category \"{}\".", category_create.name)
+            }
+            CategoryProposalPayload::Deactivate(category_id) => {
+                let name = get_category_by_id(category_id)
+                    .map(|category| category.name)
+                    .unwrap_or_default();
+                format!(
+                    "Deactivate the project category \"{}\" (id {}).",
+                    name, category_id
+                )
+            }
+        }),
+        Err(e) => Err(format!("Unable to validate category proposal: {}", e)),
+    }
+}
+
+pub fn execute_category_proposal(payload: CategoryProposalPayload) -> Result<Category, APIError> {
+    check_category_proposal(&payload)?;
+
+    match payload {
+        CategoryProposalPayload::Create(category_create) => create_category(category_create),
+        CategoryProposalPayload::Deactivate(category_id) => desactivate_category_by_id(category_id),
+    }
+}
+
+fn check_category_proposal(payload: &CategoryProposalPayload) -> Result<(), APIError> {
+    match payload {
+        CategoryProposalPayload::Create(category_create) => {
+            if category_create.name.trim().is_empty() {
+                return Err(APIError::BadRequest(
+                    "Category name can't be empty.".to_string(),
+                ));
+            }
+            check_category_length(&category_create.name)?;
+
+            let name_taken = repository::get_all_categories()
+                .iter()
+                .any(|category| category.name.eq_ignore_ascii_case(&category_create.name));
+            if name_taken {
+                return Err(APIError::BadRequest(format!(
+                    "Category \"{}\" already exists.",
+                    category_create.name
+                )));
+            }
+        }
+        CategoryProposalPayload::Deactivate(category_id) => {
+            let category = get_category_by_id(*category_id)?;
+            if !category.active {
+                return Err(APIError::BadRequest(format!(
+                    "Category with id {} is already inactive.",
+                    category_id
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
 pub fn ini_default_categories() {
     let default_categories = vec![
         CategoryCreate {
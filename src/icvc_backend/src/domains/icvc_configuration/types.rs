@@ -1,6 +1,8 @@
 use candid::{CandidType, Deserialize};
 
-use crate::{AssessmentMethod, DocumentType, StepId, StepPhaseId};
+use crate::{AssessmentMethod, DocumentTypeId, StepId, StepPhaseId};
+
+use super::constants::DEFAULT_PROJECT_VOTE_FUNCTION_ID;
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct ICVCConfigUpdate {
@@ -9,18 +11,78 @@ pub struct ICVCConfigUpdate {
     pub grade_min_value: Option<u32>,
     pub grade_max_value: Option<u32>,
     pub projects_update_timer_interval: Option<u64>,
+    pub appeal_duration: Option<u64>,
+    pub sns_neurons_refresh_interval: Option<u64>,
 }
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct StepPhaseConfigCreate {
+    pub name: Option<String>,
     pub assessment_method: AssessmentMethod,
+    pub vote_rules: Option<VoteRules>,
     pub steps: Vec<StepConfigCreateDefault>,
 }
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct StepPhaseConfig {
     pub id: StepPhaseId,
+    pub name: Option<String>,
     pub assessment_method: AssessmentMethod,
+    pub vote_rules: Option<VoteRules>,
+}
+
+/// Rules used to decide whether a vote phase is approved from the SNS proposal tally.
+/// Ratios are expressed in basis points (10000 = 100%).
+#[derive(CandidType, Deserialize, PartialEq, Clone, Debug)]
+pub struct VoteRules {
+    /// Follow the SNS decision on the proposal and ignore the thresholds below.
+    pub use_sns_decision: bool,
+    /// Minimum share of yes votes among the votes cast, yes must be strictly above it.
+    pub min_yes_ratio: u64,
+    /// Minimum share of the total voting power that has to vote.
+    pub min_participation: u64,
+    /// Minimum amount of yes voting power.
+    pub min_yes_votes: u64,
+}
+
+impl Default for VoteRules {
+    fn default() -> Self {
+        VoteRules {
+            use_sns_decision: true,
+            min_yes_ratio: 5_000,
+            min_participation: 0,
+            min_yes_votes: 0,
+        }
+    }
+}
+
+/// Template of the SNS proposals submitted for project votes.
+///
+/// `title`, `summary` and `url` can contain the placeholders `{project_id}`, `{project_title}`,
+/// `{project_moto}`, `{project_description}`, `{phase_id}`, `{phase_name}`, `{submission_url}`,
+/// `{key_metrics}`, `{categories}`, `{team}` and `{links}`, where `{submission_url}` is the
+/// rendered `url`.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ProposalTemplate {
+    pub title: String,
+    pub summary: String,
+    pub url: String,
+    pub function_id: u64,
+}
+
+impl Default for ProposalTemplate {
+    fn default() -> Self {
+        ProposalTemplate {
+            title: "ICVC project vote: {project_title} - {phase_name}".to_string(),
+            summary: "# {project_title}\n\n_{project_moto}_\n\n{project_description}\n\n\
+                ## {phase_name}\n\nReview the submission: {submission_url}\n\n\
+                ## Key metrics\n\n{key_metrics}\n\n## Categories\n\n{categories}\n\n\
+                ## Team\n\n{team}\n\n## Links\n\n{links}"
+                .to_string(),
+            url: "https://ic-vc.com/projects/{project_id}".to_string(),
+            function_id: DEFAULT_PROJECT_VOTE_FUNCTION_ID,
+        }
+    }
 }
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
@@ -28,7 +90,7 @@ pub struct StepConfigCreateDefault {
     pub questions: Vec<QuestionConfigCreate>,
     pub checkboxes: Vec<CheckBoxConfigCreate>,
     pub decimal_values: Vec<DecimalValueConfigCreate>,
-    pub required_upload_files: Vec<DocumentType>,
+    pub required_upload_files: Vec<DocumentTypeId>,
 }
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
@@ -36,7 +98,7 @@ pub struct StepConfigCreate {
     pub questions: Vec<QuestionConfig>,
     pub checkboxes: Vec<CheckBoxConfig>,
     pub decimal_values: Vec<DecimalValueConfig>,
-    pub required_upload_files: Vec<DocumentType>,
+    pub required_upload_files: Vec<DocumentTypeId>,
 }
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
@@ -46,7 +108,7 @@ pub struct StepConfig {
     pub questions: Vec<QuestionConfig>,
     pub checkboxes: Vec<CheckBoxConfig>,
     pub decimal_values: Vec<DecimalValueConfig>,
-    pub required_upload_files: Vec<DocumentType>,
+    pub required_upload_files: Vec<DocumentTypeId>,
 }
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
@@ -93,3 +155,17 @@ pub struct Category {
     pub name: String,
     pub active: bool,
 }
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum CategoryProposalPayload {
+    Create(CategoryCreate),
+    Deactivate(u64),
+}
+
+/// Changes to the configuration of a step phase, fields left empty are kept as they are.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StepPhaseConfigProposalPayload {
+    pub step_phase_id: StepPhaseId,
+    pub name: Option<String>,
+    pub vote_rules: Option<VoteRules>,
+}
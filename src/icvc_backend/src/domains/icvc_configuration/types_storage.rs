@@ -4,9 +4,13 @@ use ic_stable_structures::{storable::Bound, Storable};
 use std::borrow::Cow;
 
 use crate::domains::step::types::AssessmentMethod;
-use crate::DocumentType;
+use crate::domains::storage::types_storage::LegacyDocumentType;
+use crate::DocumentTypeId;
 
-use super::types::{CheckBoxConfig, DecimalValueConfig, QuestionConfig};
+use super::constants::{DEFAULT_APPEAL_DURATION, DEFAULT_SNS_NEURONS_REFRESH_INTERVAL};
+use super::types::{
+    CheckBoxConfig, DecimalValueConfig, ProposalTemplate, QuestionConfig, VoteRules,
+};
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct ICVCConfig {
@@ -15,6 +19,10 @@ pub struct ICVCConfig {
     pub grade_min_value: u32,
     pub grade_max_value: u32,
     pub projects_update_timer_interval: u64,
+    pub appeal_duration: Option<u64>,
+    pub projects_update_timer_paused: Option<bool>,
+    pub project_vote_proposal_template: Option<ProposalTemplate>,
+    pub sns_neurons_refresh_interval: Option<u64>,
 }
 
 impl Storable for ICVCConfig {
@@ -37,13 +45,19 @@ impl Default for ICVCConfig {
             grade_min_value: 0,
             grade_max_value: 10,
             projects_update_timer_interval: 3600,
+            appeal_duration: Some(DEFAULT_APPEAL_DURATION),
+            projects_update_timer_paused: Some(false),
+            project_vote_proposal_template: Some(ProposalTemplate::default()),
+            sns_neurons_refresh_interval: Some(DEFAULT_SNS_NEURONS_REFRESH_INTERVAL),
         }
     }
 }
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct StepPhaseConfigModel {
+    pub name: Option<String>,
     pub assessement_method: AssessmentMethod,
+    pub vote_rules: Option<VoteRules>,
 }
 
 impl Storable for StepPhaseConfigModel {
@@ -61,9 +75,25 @@ impl Storable for StepPhaseConfigModel {
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct StepConfigModel {
     pub questions: Vec<QuestionConfig>,
-    pub required_upload_files: Vec<DocumentType>,
+    /// Required document types of the steps configured before they became configurable.
+    pub required_upload_files: Option<Vec<LegacyDocumentType>>,
     pub checkboxes: Vec<CheckBoxConfig>,
     pub numeric_values: Vec<DecimalValueConfig>,
+    pub required_document_types: Option<Vec<DocumentTypeId>>,
+}
+
+impl StepConfigModel {
+    pub fn required_document_types(&self) -> Vec<DocumentTypeId> {
+        match &self.required_document_types {
+            Some(required_document_types) => required_document_types.clone(),
+            None => self
+                .required_upload_files
+                .iter()
+                .flatten()
+                .map(LegacyDocumentType::document_type_id)
+                .collect(),
+        }
+    }
 }
 
 impl Storable for StepConfigModel {
@@ -95,3 +125,52 @@ impl Storable for CategoryModel {
 
     const BOUND: Bound = Bound::Unbounded;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::storage::constants::{
+        FINANCIAL_MODELS_DOCUMENT_TYPE_ID, LOGO_DOCUMENT_TYPE_ID,
+    };
+
+    // Step config as stored before the document types became configurable.
+    #[derive(CandidType)]
+    struct BaselineStepConfigModel {
+        questions: Vec<QuestionConfig>,
+        required_upload_files: Vec<LegacyDocumentType>,
+        checkboxes: Vec<CheckBoxConfig>,
+        numeric_values: Vec<DecimalValueConfig>,
+    }
+
+    #[test]
+    fn baseline_step_configs_read_their_former_document_types() {
+        let baseline = BaselineStepConfigModel {
+            questions: vec![],
+            required_upload_files: vec![LegacyDocumentType::FinancialModels],
+            checkboxes: vec![],
+            numeric_values: vec![],
+        };
+
+        let model = StepConfigModel::from_bytes(Cow::Owned(Encode!(&baseline).unwrap()));
+
+        assert_eq!(
+            model.required_document_types(),
+            vec![FINANCIAL_MODELS_DOCUMENT_TYPE_ID]
+        );
+    }
+
+    #[test]
+    fn required_document_types_take_precedence() {
+        let model = StepConfigModel {
+            questions: vec![],
+            required_upload_files: Some(vec![LegacyDocumentType::FinancialModels]),
+            checkboxes: vec![],
+            numeric_values: vec![],
+            required_document_types: Some(vec![LOGO_DOCUMENT_TYPE_ID]),
+        };
+
+        let model = StepConfigModel::from_bytes(model.to_bytes());
+
+        assert_eq!(model.required_document_types(), vec![LOGO_DOCUMENT_TYPE_ID]);
+    }
+}
@@ -1,7 +1,10 @@
+pub mod appeal;
+pub mod audit;
 pub mod canister_management;
 pub mod core;
 pub mod icvc_configuration;
 pub mod project;
 pub mod sns_integration;
 pub mod step;
+pub mod storage;
 pub mod user;
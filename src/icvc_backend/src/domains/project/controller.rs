@@ -1,12 +1,17 @@
 //! This module defines the controller functions for projects queries and updates.
 
 use crate::{
-    utils::authenticator::check_is_project_owner_or_admin, APIError, ProjectAndStepPhase, UserId,
+    utils::authenticator::{
+        check_is_owner_or_admin, check_is_owner_or_governance_id, check_is_project_owner_or_admin,
+    },
+    APIError, ProjectAndStepPhase, UserId,
 };
 
 use super::{
     service,
-    types::{Project, ProjectCreate, ProjectId, ProjectUpdate},
+    types::{
+        Project, ProjectCreate, ProjectId, ProjectReviewer, ProjectSuspensionPayload, ProjectUpdate,
+    },
 };
 
 /// Creates a new project with the specified details.
@@ -100,3 +105,80 @@ pub fn get_user_projects() -> Result<Vec<Project>, APIError> {
 pub fn get_project_and_step_phase(project_id: ProjectId) -> Result<ProjectAndStepPhase, APIError> {
     service::get_project_and_step_phase_by_id(project_id)
 }
+
+/// Assigns a reviewer to a project. Reviewers can download the documents of the project.
+///
+/// # Arguments
+/// * `project_id` - The ID of the project.
+/// * `reviewer_id` - The principal of the reviewer.
+///
+/// # Returns
+/// * `Result<ProjectReviewer, APIError>` - The reviewer assignment or an error.
+#[ic_cdk::update(name = "assignProjectReviewer")]
+pub fn assign_project_reviewer(
+    project_id: ProjectId,
+    reviewer_id: UserId,
+) -> Result<ProjectReviewer, APIError> {
+    let caller_id = ic_cdk::caller();
+    check_is_owner_or_admin(caller_id)?;
+
+    service::assign_project_reviewer(caller_id, project_id, reviewer_id)
+}
+
+/// Removes a reviewer from a project.
+///
+/// # Arguments
+/// * `project_id` - The ID of the project.
+/// * `reviewer_id` - The principal of the reviewer.
+///
+/// # Returns
+/// * `Result<ProjectReviewer, APIError>` - The removed reviewer assignment or an error.
+#[ic_cdk::update(name = "removeProjectReviewer")]
+pub fn remove_project_reviewer(
+    project_id: ProjectId,
+    reviewer_id: UserId,
+) -> Result<ProjectReviewer, APIError> {
+    check_is_owner_or_admin(ic_cdk::caller())?;
+
+    service::remove_project_reviewer(project_id, reviewer_id)
+}
+
+/// Retrieves the reviewers assigned to a project.
+///
+/// # Arguments
+/// * `project_id` - The ID of the project.
+///
+/// # Returns
+/// * `Result<Vec<ProjectReviewer>, APIError>` - The reviewers of the project or an error.
+#[ic_cdk::query(name = "getProjectReviewers")]
+pub fn get_project_reviewers(project_id: ProjectId) -> Result<Vec<ProjectReviewer>, APIError> {
+    check_is_project_owner_or_admin(ic_cdk::caller(), project_id)?;
+
+    service::get_project_reviewers(project_id)
+}
+
+#[ic_cdk::update]
+fn validate_project_suspension(payload: ProjectSuspensionPayload) -> Result<String, String> {
+    let caller_id = ic_cdk::caller();
+
+    match check_is_owner_or_governance_id(caller_id) {
+        Ok(_) => service::validate_project_suspension(payload),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+#[ic_cdk::update]
+fn execute_project_suspension(payload: ProjectSuspensionPayload) {
+    let caller_id = ic_cdk::caller();
+
+    match check_is_owner_or_governance_id(caller_id) {
+        Ok(_) => {
+            if let Err(err) = service::execute_project_suspension(payload) {
+                ic_cdk::println!("Unable to execute project suspension: {}", err.to_string());
+            }
+        }
+        Err(err) => {
+            ic_cdk::println!("Unable to execute project suspension: {}", err.to_string());
+        }
+    }
+}
@@ -1,10 +1,24 @@
+use candid::Principal;
+
 use crate::{
-    domains::step,
+    domains::{
+        audit::{
+            self,
+            types::{AuditAction, AuditTarget},
+        },
+        step,
+    },
     repository::{self, generate_project_id},
     APIError, ProjectAndStepPhase, UserId,
 };
 
-use super::types::{Project, ProjectCreate, ProjectId, ProjectUpdate};
+use super::{
+    types::{
+        Project, ProjectCreate, ProjectId, ProjectReviewer, ProjectStatus,
+        ProjectSuspensionPayload, ProjectUpdate,
+    },
+    types_storage::ProjectReviewerModel,
+};
 
 pub fn create_project(user_id: UserId, project_create: ProjectCreate) -> Result<Project, APIError> {
     let project_id: ProjectId = generate_project_id();
@@ -12,7 +26,16 @@ pub fn create_project(user_id: UserId, project_create: ProjectCreate) -> Result<
     match repository::insert_project(user_id, project_id, project_create) {
         Some(project) => {
             match step::service::create_step_phase(user_id, project_id, project.current_phase) {
-                Ok(_) => Ok(project),
+                Ok(_) => {
+                    audit::service::record(
+                        user_id,
+                        AuditAction::ProjectCreated,
+                        AuditTarget::project(project_id),
+                        None,
+                        Some(project_summary(&project)),
+                    );
+                    Ok(project)
+                }
                 Err(e) => {
                     //If the initialization of the step fails we remove the project inserted.
                     repository::delete_project(user_id, project_id);
@@ -32,17 +55,27 @@ pub fn update_project(
     project_id: ProjectId,
     update_project: ProjectUpdate,
 ) -> Result<Project, APIError> {
-    let user_id = match repository::retrieve_project_by_id(project_id) {
-        Some(project) => project.user_id,
+    let previous_project = match repository::retrieve_project_by_id(project_id) {
+        Some(project) => project,
         None => Err(APIError::NotFound(format!(
             "Project with id {} not found",
             project_id
         )))?,
     };
+    let user_id = previous_project.user_id;
 
     // After removing the admin permission, we use the caller id instead of the user id retrieved.
     match repository::update_project(caller_id, user_id, project_id, update_project) {
-        Some(project) => Ok(project),
+        Some(project) => {
+            audit::service::record(
+                caller_id,
+                AuditAction::ProjectUpdated,
+                AuditTarget::project(project_id),
+                Some(project_summary(&previous_project)),
+                Some(project_summary(&project)),
+            );
+            Ok(project)
+        }
         None => Err(APIError::NotFound(format!(
             "Project with id {} not found.",
             project_id
@@ -52,7 +85,16 @@ pub fn update_project(
 
 pub fn delete_project(user_id: UserId, project_id: ProjectId) -> Result<Project, APIError> {
     match repository::delete_project(user_id, project_id) {
-        Some(project) => Ok(project),
+        Some(project) => {
+            audit::service::record(
+                ic_cdk::caller(),
+                AuditAction::ProjectDeleted,
+                AuditTarget::project(project_id),
+                Some(project_summary(&project)),
+                None,
+            );
+            Ok(project)
+        }
         None => Err(APIError::NotFound(format!(
             "Project with id {} not found.",
             project_id
@@ -70,6 +112,81 @@ pub fn get_project_by_id(project_id: ProjectId) -> Result<Project, APIError> {
     }
 }
 
+/// Assigns a reviewer to a project, the reviewer can then download the project documents.
+pub fn assign_project_reviewer(
+    caller_id: UserId,
+    project_id: ProjectId,
+    reviewer_id: UserId,
+) -> Result<ProjectReviewer, APIError> {
+    let project = get_project_by_id(project_id)?;
+
+    if reviewer_id == Principal::anonymous() {
+        return Err(APIError::BadRequest(
+            "The anonymous principal can't be assigned as a reviewer.".to_string(),
+        ));
+    }
+
+    if reviewer_id == project.user_id {
+        return Err(APIError::BadRequest(format!(
+            "The owner of project with id {} can't be assigned as its reviewer.",
+            project_id
+        )));
+    }
+
+    if repository::is_project_reviewer(project_id, reviewer_id) {
+        return Err(APIError::BadRequest(format!(
+            "User {} is already a reviewer of project with id {}.",
+            reviewer_id, project_id
+        )));
+    }
+
+    let reviewer = repository::insert_project_reviewer(
+        project_id,
+        reviewer_id,
+        ProjectReviewerModel {
+            assigned_by: caller_id,
+            assigned_at: ic_cdk::api::time(),
+        },
+    );
+    audit::service::record(
+        caller_id,
+        AuditAction::ProjectReviewerAssigned,
+        AuditTarget::project(project_id).with_entity(reviewer_id),
+        None,
+        None,
+    );
+
+    Ok(reviewer)
+}
+
+pub fn remove_project_reviewer(
+    project_id: ProjectId,
+    reviewer_id: UserId,
+) -> Result<ProjectReviewer, APIError> {
+    let reviewer =
+        repository::remove_project_reviewer(project_id, reviewer_id).ok_or_else(|| {
+            APIError::NotFound(format!(
+                "User {} is not a reviewer of project with id {}.",
+                reviewer_id, project_id
+            ))
+        })?;
+    audit::service::record(
+        ic_cdk::caller(),
+        AuditAction::ProjectReviewerRemoved,
+        AuditTarget::project(project_id).with_entity(reviewer_id),
+        None,
+        None,
+    );
+
+    Ok(reviewer)
+}
+
+pub fn get_project_reviewers(project_id: ProjectId) -> Result<Vec<ProjectReviewer>, APIError> {
+    get_project_by_id(project_id)?;
+
+    Ok(repository::get_project_reviewers(project_id))
+}
+
 pub fn get_project_and_step_phase_by_id(
     project_id: ProjectId,
 ) -> Result<ProjectAndStepPhase, APIError> {
@@ -117,3 +234,109 @@ pub fn get_user_projects(user_id: UserId) -> Result<Vec<Project>, APIError> {
 
     Ok(projects)
 }
+
+pub fn validate_project_suspension(payload: ProjectSuspensionPayload) -> Result<String, String> {
+    match check_project_suspension(&payload) {
+        Ok(project) => Ok(format!(
+            "{} project \"{}\" (id {}), currently {}.\n\nReason: {}",
+            if payload.suspended {
+                "Suspend"
+            } else {
+                "Lift the suspension of"
+            },
+            project.title,
+            project.id,
+            project.status,
+            payload.reason
+        )),
+        Err(e) => Err(format!("Unable to validate project suspension: {}", e)),
+    }
+}
+
+/// Suspends an open project or reopens a suspended one. Lifting the suspension delays the
+/// deadlines of the current phase by the time the project was suspended.
+pub fn execute_project_suspension(payload: ProjectSuspensionPayload) -> Result<Project, APIError> {
+    let project = check_project_suspension(&payload)?;
+
+    let new_status = if payload.suspended {
+        ProjectStatus::Suspended
+    } else {
+        ProjectStatus::Open
+    };
+    repository::update_project_status(project.user_id, project.id, new_status.clone());
+    audit::service::record(
+        ic_cdk::caller(),
+        if payload.suspended {
+            AuditAction::ProjectSuspended
+        } else {
+            AuditAction::ProjectSuspensionLifted
+        },
+        AuditTarget::project(project.id),
+        Some(format!("status: {:?}", project.status)),
+        Some(format!(
+            "status: {:?}, reason: {}",
+            new_status, payload.reason
+        )),
+    );
+
+    if let Some(suspended_at) = project.suspended_at.filter(|_| !payload.suspended) {
+        step::service::shift_step_phase_deadlines(
+            ic_cdk::caller(),
+            project.id,
+            project.current_phase,
+            suspended_at,
+            payload.reason.clone(),
+        );
+    }
+
+    if !payload.suspended {
+        // Due deadlines were skipped while the project was suspended.
+        repository::insert_step_phase_deadline(
+            ic_cdk::api::time(),
+            project.id,
+            project.current_phase,
+        );
+    }
+
+    ic_cdk::println!(
+        "Project id {} suspended: {}. Reason: {}",
+        project.id,
+        payload.suspended,
+        payload.reason
+    );
+
+    get_project_by_id(project.id)
+}
+
+fn check_project_suspension(payload: &ProjectSuspensionPayload) -> Result<Project, APIError> {
+    step::service::check_admin_reason(&payload.reason)?;
+    let project = get_project_by_id(payload.project_id)?;
+
+    let expected_status = if payload.suspended {
+        ProjectStatus::Open
+    } else {
+        ProjectStatus::Suspended
+    };
+
+    if project.status != expected_status {
+        return Err(APIError::BadRequest(format!(
+            "Project with id {} is {} and should be {}.",
+            project.id, project.status, expected_status
+        )));
+    }
+
+    Ok(project)
+}
+
+// Short description of a project for the audit log, the description is only measured.
+fn project_summary(project: &Project) -> String {
+    format!(
+        "title: \"{}\", moto: \"{}\", description: {} bytes, categories: {:?}, team members: {}, links: {}",
+        project.title,
+        project.moto,
+        project.description.len(),
+        project.categories,
+        project.team_members.len(),
+        project.links.len()
+    )
+}
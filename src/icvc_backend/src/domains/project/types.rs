@@ -2,9 +2,10 @@ use std::fmt;
 
 use candid::{CandidType, Deserialize};
 
-use crate::{domains::step::types::StepPhaseId, StepPhase, UserId};
+use crate::{domains::step::types::StepPhaseId, DocumentTypeId, StepPhase, UserId};
 
 pub type ProjectId = u64;
+pub type DocumentId = u64;
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct ProjectCreate {
@@ -41,6 +42,7 @@ pub struct Project {
     pub created_at: u64,
     pub update_by: Option<UserId>,
     pub update_at: Option<u64>,
+    pub suspended_at: Option<u64>,
 }
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
@@ -66,6 +68,8 @@ pub enum ProjectStatus {
     Funded,
     NotFunded,
     NotSubmitted,
+    UnderAppeal,
+    Suspended,
 }
 
 impl fmt::Display for ProjectStatus {
@@ -75,6 +79,8 @@ impl fmt::Display for ProjectStatus {
             ProjectStatus::NotFunded => write!(f, "Not Funded"),
             ProjectStatus::Funded => write!(f, "Funded"),
             ProjectStatus::NotSubmitted => write!(f, "Not Submitted"),
+            ProjectStatus::UnderAppeal => write!(f, "Under Appeal"),
+            ProjectStatus::Suspended => write!(f, "Suspended"),
         }
     }
 }
@@ -95,32 +101,71 @@ pub struct TeamMember {
     pub profile_picture: String,
 }
 
+/// A document of a step. Without a status nothing was uploaded yet, the metadata is the one
+/// recorded when the upload was confirmed. A step has up to the max files of the document type
+/// for each type, every version of a document is stored under its own key.
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct UploadFile {
     pub filename: Option<String>,
-    pub document_type: DocumentType,
+    pub document_type: DocumentTypeId,
     pub s3_key: Option<String>,
+    pub status: Option<UploadStatus>,
+    pub size: Option<u64>,
+    pub sha256: Option<String>,
+    pub mime_type: Option<String>,
+    pub confirmed_at: Option<u64>,
+    pub rejection_reason: Option<String>,
+    pub document_id: Option<DocumentId>,
+    pub version: Option<u32>,
+    pub previous_versions: Option<Vec<UploadFileVersion>>,
+}
+
+/// A version of a document replaced by a newer upload, it stays in the storage under its key.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct UploadFileVersion {
+    pub version: u32,
+    pub filename: Option<String>,
+    pub s3_key: Option<String>,
+    pub size: Option<u64>,
+    pub sha256: Option<String>,
+    pub mime_type: Option<String>,
+    pub confirmed_at: Option<u64>,
+    pub replaced_at: u64,
 }
 
 #[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
-pub enum DocumentType {
-    PitchDeck,
-    Logo,
-    CoverPhoto,
-    FinancialModels,
-    ProductDemo,
-    ExpenditurePlan,
+pub enum UploadStatus {
+    /// An upload URL was generated, the upload isn't confirmed yet.
+    Pending,
+    /// The upload was confirmed and matches the limits of its document type.
+    Uploaded,
+    /// The confirmed file doesn't match the limits of its document type.
+    Rejected,
 }
 
-impl ToString for DocumentType {
-    fn to_string(&self) -> String {
-        match self {
-            DocumentType::PitchDeck => "PitchDeck".to_string(),
-            DocumentType::Logo => "Logo".to_string(),
-            DocumentType::CoverPhoto => "CoverPhoto".to_string(),
-            DocumentType::FinancialModels => "FinancialModels".to_string(),
-            DocumentType::ProductDemo => "ProductDemo".to_string(),
-            DocumentType::ExpenditurePlan => "ExpenditurePlan".to_string(),
+impl fmt::Display for UploadStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            UploadStatus::Pending => write!(f, "Pending"),
+            UploadStatus::Uploaded => write!(f, "Uploaded"),
+            UploadStatus::Rejected => write!(f, "Rejected"),
         }
     }
 }
+
+/// Suspends an open project, or lifts the suspension of a suspended one, through an SNS proposal.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ProjectSuspensionPayload {
+    pub project_id: ProjectId,
+    pub suspended: bool,
+    pub reason: String,
+}
+
+/// Reviewer assigned to a project. Reviewers can download the documents of the project.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ProjectReviewer {
+    pub project_id: ProjectId,
+    pub reviewer_id: UserId,
+    pub assigned_by: UserId,
+    pub assigned_at: u64,
+}
@@ -22,6 +22,7 @@ pub struct ProjectModel {
     pub created_at: u64,
     pub update_by: Option<UserId>,
     pub update_at: Option<u64>,
+    pub suspended_at: Option<u64>,
 }
 
 impl Storable for ProjectModel {
@@ -35,3 +36,21 @@ impl Storable for ProjectModel {
 
     const BOUND: Bound = Bound::Unbounded;
 }
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ProjectReviewerModel {
+    pub assigned_by: UserId,
+    pub assigned_at: u64,
+}
+
+impl Storable for ProjectReviewerModel {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
@@ -1,6 +1,8 @@
 use crate::{
-    utils::authenticator::check_is_owner_or_governance_id, APIError, ListProposalsResponse, ProposalData,
-    ProjectProposalPayload,
+    utils::authenticator::{check_is_owner_or_admin, check_is_owner_or_governance_id},
+    APIError, ListProposalsResponse, PendingProposal, ProjectBallot, ProjectId,
+    ProjectProposalPayload, ProposalData, ProposalExecutionReceipt, SnsNeuronsHealth, StepPhaseId,
+    StepPhaseVotingBreakdown,
 };
 
 use super::service;
@@ -15,8 +17,89 @@ async fn get_sns_proposal_by_id(proposal_id: u64) -> Result<ProposalData, APIErr
     service::get_sns_proposal_by_id(proposal_id).await
 }
 
+#[ic_cdk::query(name = "getProposalExecutionReceipt")]
+fn get_proposal_execution_receipt(proposal_id: u64) -> Result<ProposalExecutionReceipt, APIError> {
+    service::get_proposal_execution_receipt(proposal_id)
+}
+
+/// Retrieves the proposals that failed to be created, or whose voting deadline couldn't be
+/// synced, and are waiting to be retried by the timer.
+///
+/// # Returns
+/// * `Result<Vec<PendingProposal>, APIError>` - The pending proposals or an error.
+#[ic_cdk::query(name = "getPendingProposals")]
+fn get_pending_proposals() -> Result<Vec<PendingProposal>, APIError> {
+    check_is_owner_or_admin(ic_cdk::caller())?;
+
+    Ok(service::get_pending_proposals())
+}
+
+/// Retrieves the ballots cast on the proposal of a vote phase, with participation stats.
+///
+/// # Arguments
+/// * `project_id` - The ID of the project.
+/// * `step_phase_id` - The ID of the step phase.
+///
+/// # Returns
+/// * `StepPhaseVotingBreakdown` - The ballots and stats, empty if no ballots were stored.
+#[ic_cdk::query(name = "getStepPhaseVotingBreakdown")]
+fn get_step_phase_voting_breakdown(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+) -> StepPhaseVotingBreakdown {
+    service::get_step_phase_voting_breakdown(project_id, step_phase_id)
+}
+
+/// Retrieves the ballots cast on all the vote phases of a project.
+///
+/// # Arguments
+/// * `project_id` - The ID of the project.
+///
+/// # Returns
+/// * `Vec<StepPhaseVotingBreakdown>` - The ballots and stats of each vote phase.
+#[ic_cdk::query(name = "getProjectVotingBreakdown")]
+fn get_project_voting_breakdown(project_id: ProjectId) -> Vec<StepPhaseVotingBreakdown> {
+    service::get_project_voting_breakdown(project_id)
+}
+
+/// Retrieves how a neuron voted across all the projects.
+///
+/// # Arguments
+/// * `neuron_id` - The ID of the neuron as a hex string.
+///
+/// # Returns
+/// * `Result<Vec<ProjectBallot>, APIError>` - The ballots of the neuron or an error.
+#[ic_cdk::query(name = "getNeuronBallots")]
+fn get_neuron_ballots(neuron_id: String) -> Result<Vec<ProjectBallot>, APIError> {
+    service::get_neuron_ballots(neuron_id)
+}
+
+/// Retrieves the neurons the canister makes proposals with, as last checked, and whether
+/// they can still pay for proposals.
+///
+/// # Returns
+/// * `SnsNeuronsHealth` - The neurons and how many more proposals they can make.
+#[ic_cdk::query(name = "getSnsNeuronsHealth")]
+fn get_sns_neurons_health() -> SnsNeuronsHealth {
+    service::get_sns_neurons_health()
+}
+
+/// Lists the neurons of the canister on SNS governance again and checks their stake,
+/// dissolve delay and permissions.
+///
+/// # Returns
+/// * `Result<SnsNeuronsHealth, APIError>` - The refreshed neurons or an error.
+#[ic_cdk::update(name = "refreshSnsNeurons")]
+async fn refresh_sns_neurons() -> Result<SnsNeuronsHealth, APIError> {
+    check_is_owner_or_admin(ic_cdk::caller())?;
+
+    service::refresh_sns_neurons().await
+}
+
 #[ic_cdk::update]
-fn validate_project_vote_proposal(proposal_payload: ProjectProposalPayload) -> Result<String, String> {
+fn validate_project_vote_proposal(
+    proposal_payload: ProjectProposalPayload,
+) -> Result<String, String> {
     let caller_id = ic_cdk::caller();
 
     match check_is_owner_or_governance_id(caller_id) {
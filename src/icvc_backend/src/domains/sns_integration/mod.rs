@@ -3,3 +3,4 @@ pub mod controller;
 pub mod service;
 pub mod types;
 pub mod types_sns_governance;
+pub mod types_storage;
@@ -1,69 +1,128 @@
-use candid::Encode;
+use candid::{Decode, Encode};
 use ic_cdk::api::call::RejectionCode;
 
-use super::{types::ProjectProposalPayload, types_sns_governance::ProposalId};
+use super::{
+    types::{
+        BallotVote, PendingProposal, ProjectBallot, ProjectProposalPayload,
+        ProposalExecutionReceipt, SnsNeuron, SnsNeuronsHealth, SnsProposalStatus,
+        StepPhaseVotingBreakdown, VotingStats,
+    },
+    types_sns_governance::{
+        DissolveState, ListNeurons, ListNeuronsResponse, NervousSystemParameters, Neuron, NeuronId,
+        ProposalId,
+    },
+    types_storage::{BallotModel, PendingProposalModel, SnsNeuronIdKey, SnsNeuronModel},
+};
 use crate::{
     domains::{
         self,
+        audit::{
+            self,
+            types::{AuditAction, AuditTarget},
+        },
         canister_management::{self, types_storage::CanisterConfig},
+        icvc_configuration::{
+            self,
+            constants::{
+                MAX_BASIS_POINTS, MAX_PROPOSAL_SUMMARY_BYTES, MAX_PROPOSAL_TITLE_BYTES,
+                MAX_PROPOSAL_URL_BYTES, PENDING_PROPOSAL_BASE_BACKOFF,
+                PENDING_PROPOSAL_MAX_BACKOFF,
+            },
+            types::VoteRules,
+        },
         step,
     },
-    repository, APIError, Action, Command, Command1, ExecuteGenericNervousSystemFunction,
-    GetProposalArguments, ListProposals, ListProposalsResponse, ManageNeuron, ManageNeuronResponse,
-    ProjectId, Proposal, ProposalData, StepPhaseId, StepPhaseVoteResultCreate,
+    repository, APIError, Action, AssessmentMethod, Command, Command1,
+    ExecuteGenericNervousSystemFunction, GetProposal, GetProposalResponse, ListProposals,
+    ListProposalsResponse, ManageNeuron, ManageNeuronResponse, Project, ProjectId, ProjectStatus,
+    Proposal, ProposalData, Result1, StepPhase, StepPhaseId, StepPhaseStatus, StepPhaseUpdate,
+    StepPhaseVoteResultCreate,
 };
+use candid::Principal;
 use domains::core;
 
-pub fn validate_project_vote_proposal(proposal_payload: ProjectProposalPayload) -> Result<String, String> {
+const LIST_PROPOSALS_PAGE_SIZE: u32 = 100;
+const MAX_PENDING_PROPOSALS_PER_BATCH: usize = 10;
+const LIST_NEURONS_PAGE_SIZE: u32 = 100;
+// `NeuronPermissionType::SubmitProposal` of SNS governance.
+const NEURON_PERMISSION_SUBMIT_PROPOSAL: i32 = 3;
+// SNS governance error types that are specific to the neuron making the proposal: not
+// authorized, not found, precondition failed (stake or dissolve delay), neuron locked and
+// insufficient funds.
+const NEURON_GOVERNANCE_ERROR_TYPES: [i32; 5] = [3, 4, 12, 14, 15];
+const TRUNCATED_SUMMARY_NOTICE: &str = "\n\n_The summary was truncated._";
+
+pub fn validate_project_vote_proposal(
+    proposal_payload: ProjectProposalPayload,
+) -> Result<String, String> {
     let project_id = proposal_payload.project_id;
     let step_phase_id = proposal_payload.phase_id;
 
-    match repository::retrieve_project_by_id(project_id) {
-        Some(project) => {
-            if project.current_phase == step_phase_id {
-                Ok(format!(
-                    "Project with id {} on phase: {} is valid for votting.",
-                    project.id, project.current_phase
-                ))
-            } else {
-                Err(format!(
-                    "Unable to validate proposal, project id {} is not on phase id {}",
-                    project.id, step_phase_id
-                ))
-            }
-        }
-        _ => Err(format!(
+    let project = repository::retrieve_project_by_id(project_id).ok_or_else(|| {
+        format!(
             "Unable to validate proposal, project id {} not found",
             project_id
-        )),
+        )
+    })?;
+
+    if project.status != ProjectStatus::Open {
+        return Err(format!(
+            "Unable to validate proposal, project id {} is {}, not Open",
+            project.id, project.status
+        ));
+    }
+
+    if project.current_phase != step_phase_id {
+        return Err(format!(
+            "Unable to validate proposal, project id {} is not on phase id {}",
+            project.id, step_phase_id
+        ));
+    }
+
+    let step_phase =
+        repository::get_step_phase_by_id(project_id, step_phase_id).ok_or_else(|| {
+            format!(
+                "Unable to validate proposal, step phase id {} of project id {} not found",
+                step_phase_id, project_id
+            )
+        })?;
+
+    if step_phase.status != StepPhaseStatus::Submitted {
+        return Err(format!(
+            "Unable to validate proposal, step phase id {} is {}, not Submitted",
+            step_phase_id, step_phase.status
+        ));
     }
+
+    if step_phase.assessment_method != AssessmentMethod::Vote {
+        return Err(format!(
+            "Unable to validate proposal, step phase id {} is assessed by {}, not by vote",
+            step_phase_id, step_phase.assessment_method
+        ));
+    }
+
+    // The stored proposal is still being voted on until it gets its receipt, written when it is
+    // executed or finalized.
+    if let Some(proposal) = repository::get_proposal_by_step_phase_id(project_id, step_phase_id) {
+        if repository::get_proposal_execution_receipt(proposal.proposal_id).is_none() {
+            return Err(format!(
+                "Unable to validate proposal, step phase id {} of project id {} already has the active proposal id {}",
+                step_phase_id, project_id, proposal.proposal_id
+            ));
+        }
+    }
+
+    Ok(render_project_vote_summary(&project, step_phase_id))
 }
 
 pub async fn execute_project_vote_proposal(proposal_payload: ProjectProposalPayload) {
     let project_id = proposal_payload.project_id;
     let step_phase_id = proposal_payload.phase_id;
 
-    let project = match repository::retrieve_project_by_id(project_id) {
-        Some(project) => project,
-        None => {
-            ic_cdk::println!(
-                "Unable to execute proposal, project id {} not found",
-                project_id
-            );
-            return;
-        }
-    };
-
-    let step_phase = match repository::get_step_phase_by_id(project_id, step_phase_id) {
-        Some(step_phase) => step_phase,
-        None => {
-            ic_cdk::println!(
-                "Unable to execute proposal, step phase id {} not found",
-                project_id
-            );
-            return;
-        }
-    };
+    if let Err(e) = check_vote_phase_is_submitted(project_id, step_phase_id) {
+        ic_cdk::println!("Unable to execute proposal: {}", e);
+        return;
+    }
 
     let proposal = match step::service::get_proposal_by_phase_id(project_id, step_phase_id) {
         Ok(proposal) => proposal,
@@ -73,34 +132,84 @@ pub async fn execute_project_vote_proposal(proposal_payload: ProjectProposalPayl
         }
     };
 
+    if let Some(receipt) = repository::get_proposal_execution_receipt(proposal.proposal_id) {
+        ic_cdk::println!(
+            "Proposal {} was already executed at {}, nothing to do.",
+            receipt.proposal_id,
+            receipt.executed_at
+        );
+        return;
+    }
+
     let sns_proposal = match get_sns_proposal_by_id(proposal.proposal_id).await {
         Ok(proposal) => proposal,
-        _ => {
-            ic_cdk::println!("Unable to found sns proposal id {}", proposal.proposal_id);
+        Err(e) => {
+            ic_cdk::println!(
+                "Unable to found sns proposal id {}: {}",
+                proposal.proposal_id,
+                e
+            );
             return;
         }
     };
 
-    if let Some(tally) = &sns_proposal.latest_tally {
-        // Assuming 50% + 1 votes is needed to pass
-        let yes_votes = tally.yes;
-        let total_votes = tally.total;
-        let yes_percentage = yes_votes as f64 / total_votes as f64 * 100.0;
+    // The stored proposal must be the one SNS governance is executing right now.
+    if let Err(e) = check_sns_proposal_payload(&sns_proposal, &proposal_payload) {
+        ic_cdk::println!(
+            "Unable to execute proposal id {}: {}",
+            proposal.proposal_id,
+            e
+        );
+        return;
+    }
+    let status = get_sns_proposal_status(&sns_proposal);
+    if status != SnsProposalStatus::Adopted {
+        ic_cdk::println!(
+            "Unable to execute proposal id {}, its status is {} instead of Adopted",
+            proposal.proposal_id,
+            status
+        );
+        return;
+    }
 
-        // Check if yes votes exceed 50% of the total votes
-        let approved = yes_percentage > 50.0;
+    // The phase may have been finalized while waiting for the SNS.
+    let (project, step_phase) = match check_vote_phase_is_submitted(project_id, step_phase_id) {
+        Ok(project_and_phase) => project_and_phase,
+        Err(e) => {
+            ic_cdk::println!("Unable to execute proposal: {}", e);
+            return;
+        }
+    };
+
+    if sns_proposal.latest_tally.is_some() {
+        let vote_rules = icvc_configuration::service::get_step_phase_vote_rules(step_phase_id);
+        let step_phase_vote_result = evaluate_vote_result(&sns_proposal, &vote_rules);
+        let approved = step_phase_vote_result.approved;
 
-        let step_phase_vote_result = StepPhaseVoteResultCreate {
-            yes: yes_votes,
-            no: tally.no,
-            total: total_votes,
+        if repository::insert_proposal_execution_receipt(
+            proposal.proposal_id,
+            project_id,
+            step_phase_id,
             approved,
-        };
+        )
+        .is_none()
+        {
+            return;
+        }
+
+        ingest_sns_ballots(project_id, step_phase_id, &sns_proposal);
         step::service::put_step_phase_vote_result(
             project_id,
             step_phase_id,
             step_phase_vote_result,
         );
+        audit::service::record(
+            ic_cdk::caller(),
+            AuditAction::VoteProposalExecuted,
+            AuditTarget::step_phase(project_id, step_phase_id).with_entity(proposal.proposal_id),
+            None,
+            Some(format!("approved: {}", approved)),
+        );
         core::service::update_phase_status(&project, step_phase, approved);
 
         ic_cdk::println!(
@@ -117,39 +226,543 @@ pub async fn execute_project_vote_proposal(proposal_payload: ProjectProposalPayl
     }
 }
 
-pub async fn get_sns_proposal_by_id_old(proposal_id: u64) -> Result<ProposalData, APIError> {
-    let canister_config = canister_management::service::get_canister_config();
-    let gov_canister_id = match canister_config.sns_governance_id {
-        Some(id) => id,
-        None => {
-            return Err(APIError::InternalServerError(
-                "Governance canister ID not set".to_string(),
+pub fn get_proposal_execution_receipt(
+    proposal_id: u64,
+) -> Result<ProposalExecutionReceipt, APIError> {
+    match repository::get_proposal_execution_receipt(proposal_id) {
+        Some(receipt) => Ok(receipt),
+        None => Err(APIError::NotFound(format!(
+            "Execution receipt for proposal id {} not found",
+            proposal_id
+        ))),
+    }
+}
+
+fn check_vote_phase_is_submitted(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+) -> Result<(Project, StepPhase), String> {
+    let project = repository::retrieve_project_by_id(project_id)
+        .ok_or_else(|| format!("project id {} not found", project_id))?;
+
+    let step_phase = repository::get_step_phase_by_id(project_id, step_phase_id)
+        .ok_or_else(|| format!("step phase id {} not found", step_phase_id))?;
+
+    if project.current_phase != step_phase_id || step_phase.status != StepPhaseStatus::Submitted {
+        return Err(format!(
+            "project id {} on phase id {} is not waiting for a vote, step phase status: {}",
+            project_id, step_phase_id, step_phase.status
+        ));
+    }
+
+    Ok((project, step_phase))
+}
+
+fn check_sns_proposal_payload(
+    sns_proposal: &ProposalData,
+    proposal_payload: &ProjectProposalPayload,
+) -> Result<(), String> {
+    let function = match sns_proposal
+        .proposal
+        .as_ref()
+        .and_then(|p| p.action.as_ref())
+    {
+        Some(Action::ExecuteGenericNervousSystemFunction(function)) => function,
+        _ => return Err("the sns proposal is not a generic function proposal".to_string()),
+    };
+
+    let function_id = icvc_configuration::service::get_project_vote_proposal_template().function_id;
+    if function.function_id != function_id {
+        return Err(format!(
+            "unexpected function id {}, expected {}",
+            function.function_id, function_id
+        ));
+    }
+
+    let payload = Decode!(&function.payload, ProjectProposalPayload)
+        .map_err(|e| format!("unable to decode the sns proposal payload: {}", e))?;
+
+    if payload.project_id != proposal_payload.project_id
+        || payload.phase_id != proposal_payload.phase_id
+    {
+        return Err(format!(
+            "the sns proposal is for project id {} on phase id {}",
+            payload.project_id, payload.phase_id
+        ));
+    }
+
+    Ok(())
+}
+
+/// Finalizes a vote phase whose assessment period is over but whose proposal was never
+/// executed, e.g. because it was rejected. Phases still waiting on the SNS are checked again later.
+pub async fn finalize_expired_vote_phase(project_id: ProjectId, step_phase_id: StepPhaseId) {
+    let proposal = match step::service::get_proposal_by_phase_id(project_id, step_phase_id) {
+        Ok(proposal) => proposal,
+        Err(e) => {
+            ic_cdk::println!("Unable to finalize vote phase: {:?}", e);
+            return;
+        }
+    };
+
+    let sns_proposal = match get_sns_proposal_by_id(proposal.proposal_id).await {
+        Ok(sns_proposal) => sns_proposal,
+        Err(e) => {
+            ic_cdk::println!(
+                "Unable to fetch sns proposal id {}, retrying later: {}",
+                proposal.proposal_id,
+                e
+            );
+            schedule_vote_phase_check(project_id, step_phase_id);
+            return;
+        }
+    };
+
+    // The phase may have changed while waiting for the SNS.
+    let project = match repository::retrieve_project_by_id(project_id) {
+        Some(project) if project.status == ProjectStatus::Open => project,
+        _ => return,
+    };
+    let step_phase = match repository::get_step_phase_by_id(project_id, step_phase_id) {
+        Some(step_phase)
+            if project.current_phase == step_phase_id
+                && step_phase.status == StepPhaseStatus::Submitted =>
+        {
+            step_phase
+        }
+        _ => return,
+    };
+
+    match get_sns_proposal_status(&sns_proposal) {
+        SnsProposalStatus::Open => {
+            // Wait for quiet may have pushed the voting deadline.
+            let voting_end_time = step::service::calculate_voting_end_time(&sns_proposal);
+            if voting_end_time > ic_cdk::api::time() {
+                repository::update_step_phase(
+                    project_id,
+                    step_phase_id,
+                    StepPhaseUpdate {
+                        end_assessment_date: Some(voting_end_time),
+                        ..Default::default()
+                    },
+                );
+            } else {
+                schedule_vote_phase_check(project_id, step_phase_id);
+            }
+        }
+        // Execution is up to SNS governance, check again in case it never happens.
+        SnsProposalStatus::Adopted => schedule_vote_phase_check(project_id, step_phase_id),
+        status => {
+            if repository::get_proposal_execution_receipt(proposal.proposal_id).is_some() {
+                return;
+            }
+
+            let mut step_phase_vote_result = evaluate_vote_result(
+                &sns_proposal,
+                &icvc_configuration::service::get_step_phase_vote_rules(step_phase_id),
+            );
+            // Only an executed proposal, missed by the executor, can still approve the phase.
+            if status != SnsProposalStatus::Executed {
+                step_phase_vote_result.approved = false;
+            }
+            let approved = step_phase_vote_result.approved;
+
+            repository::insert_proposal_execution_receipt(
+                proposal.proposal_id,
+                project_id,
+                step_phase_id,
+                approved,
+            );
+            ingest_sns_ballots(project_id, step_phase_id, &sns_proposal);
+            step::service::put_step_phase_vote_result(
+                project_id,
+                step_phase_id,
+                step_phase_vote_result,
+            );
+            core::service::update_phase_status(&project, step_phase, approved);
+
+            ic_cdk::println!(
+                "Proposal {} {}, finalized project id {} on phase id {}",
+                proposal.proposal_id,
+                status,
+                project_id,
+                step_phase_id
+            );
+        }
+    }
+}
+
+/// Copies the ballots of a decided SNS proposal into the canister, so they are kept after
+/// governance prunes them. Returns the number of ballots stored.
+pub fn ingest_sns_ballots(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    proposal: &ProposalData,
+) -> usize {
+    let proposal_id = proposal.id.as_ref().map(|id| id.id).unwrap_or_default();
+
+    let ballots = proposal
+        .ballots
+        .iter()
+        .filter_map(|(neuron_id, ballot)| {
+            let neuron_id = parse_sns_neuron_id(neuron_id).ok()?;
+            let vote = match ballot.vote {
+                1 => BallotVote::Yes,
+                2 => BallotVote::No,
+                _ => BallotVote::Unspecified,
+            };
+
+            Some((
+                neuron_id,
+                BallotModel {
+                    proposal_id,
+                    vote,
+                    voting_power: ballot.voting_power,
+                    cast_timestamp_seconds: ballot.cast_timestamp_seconds,
+                },
             ))
+        })
+        .collect();
+
+    repository::put_step_phase_ballots(project_id, step_phase_id, ballots)
+}
+
+pub fn get_step_phase_voting_breakdown(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+) -> StepPhaseVotingBreakdown {
+    let ballots = repository::get_step_phase_ballots(project_id, step_phase_id);
+
+    StepPhaseVotingBreakdown {
+        project_id,
+        step_phase_id,
+        stats: get_voting_stats(&ballots),
+        ballots,
+    }
+}
+
+/// Groups the stored ballots of a project by step phase.
+pub fn get_project_voting_breakdown(project_id: ProjectId) -> Vec<StepPhaseVotingBreakdown> {
+    let mut breakdowns: Vec<StepPhaseVotingBreakdown> = vec![];
+
+    for ballot in repository::get_project_ballots(project_id) {
+        match breakdowns.last_mut() {
+            Some(breakdown) if breakdown.step_phase_id == ballot.step_phase_id => {
+                breakdown.ballots.push(ballot)
+            }
+            _ => breakdowns.push(StepPhaseVotingBreakdown {
+                project_id,
+                step_phase_id: ballot.step_phase_id,
+                ballots: vec![ballot],
+                stats: VotingStats::default(),
+            }),
         }
+    }
+
+    for breakdown in breakdowns.iter_mut() {
+        breakdown.stats = get_voting_stats(&breakdown.ballots);
+    }
+
+    breakdowns
+}
+
+pub fn get_neuron_ballots(neuron_id: String) -> Result<Vec<ProjectBallot>, APIError> {
+    let neuron_id = parse_sns_neuron_id(&neuron_id)?;
+
+    Ok(repository::get_neuron_ballots(neuron_id))
+}
+
+fn get_voting_stats(ballots: &[ProjectBallot]) -> VotingStats {
+    let mut stats = VotingStats {
+        eligible_neurons: ballots.len() as u64,
+        ..Default::default()
     };
-    let method = "get_proposal";
-    let arguments = GetProposalArguments {
-        proposal_id: Some(ProposalId { id: proposal_id }),
+
+    for ballot in ballots {
+        stats.total_voting_power = stats.total_voting_power.saturating_add(ballot.voting_power);
+
+        match ballot.vote {
+            BallotVote::Yes => {
+                stats.yes_neurons += 1;
+                stats.yes_voting_power = stats.yes_voting_power.saturating_add(ballot.voting_power);
+            }
+            BallotVote::No => {
+                stats.no_neurons += 1;
+                stats.no_voting_power = stats.no_voting_power.saturating_add(ballot.voting_power);
+            }
+            BallotVote::Unspecified => {}
+        }
+    }
+
+    stats.voted_neurons = stats.yes_neurons + stats.no_neurons;
+    let cast_voting_power = stats.yes_voting_power as u128 + stats.no_voting_power as u128;
+    stats.participation = (cast_voting_power * MAX_BASIS_POINTS as u128)
+        .checked_div(stats.total_voting_power as u128)
+        .unwrap_or(0) as u64;
+
+    stats
+}
+
+fn parse_sns_neuron_id(neuron_id: &str) -> Result<SnsNeuronIdKey, APIError> {
+    let bytes = hex_to_bytes(neuron_id).map_err(|err| {
+        APIError::BadRequest(format!(
+            "Failed to decode neuron id from hex string: {}. Error: {}",
+            neuron_id, err
+        ))
+    })?;
+
+    bytes.try_into().map_err(|_| {
+        APIError::BadRequest(format!("Neuron id {} should be 32 bytes long.", neuron_id))
+    })
+}
+
+/// Builds the vote result of a phase from the latest tally of its SNS proposal, applying the
+/// vote rules of the phase.
+pub fn evaluate_vote_result(
+    proposal: &ProposalData,
+    vote_rules: &VoteRules,
+) -> StepPhaseVoteResultCreate {
+    let (yes, no, total) = match &proposal.latest_tally {
+        Some(tally) => (tally.yes, tally.no, tally.total),
+        None => (0, 0, 0),
     };
 
-    ic_cdk::println!("Calling get_proposal with arguments: {:?}", arguments);
+    let approved = if vote_rules.use_sns_decision {
+        matches!(
+            get_sns_proposal_status(proposal),
+            SnsProposalStatus::Adopted | SnsProposalStatus::Executed
+        )
+    } else {
+        // Ratios are compared by cross-multiplying, nothing is divided by a zero tally.
+        let yes_votes = yes as u128;
+        let exercised = yes as u128 + no as u128;
+        let max_basis_points = MAX_BASIS_POINTS as u128;
 
-    let result: Result<(Option<ProposalData>,), (ic_cdk::api::call::RejectionCode, String)> =
-        ic_cdk::call(gov_canister_id, method, (arguments,)).await;
+        exercised > 0
+            && yes_votes * max_basis_points > exercised * vote_rules.min_yes_ratio as u128
+            && exercised * max_basis_points >= total as u128 * vote_rules.min_participation as u128
+            && yes >= vote_rules.min_yes_votes
+    };
 
-    match result {
-        Ok((Some(proposal_data),)) => Ok(proposal_data),
-        Ok((None,)) => Err(APIError::NotFound(format!(
-            "Proposal with id {} not found",
-            proposal_id
-        ))),
-        Err((code, msg)) => Err(APIError::InternalServerError(format!(
-            "Error: {}: {}",
-            code as i32, msg
-        ))),
+    StepPhaseVoteResultCreate {
+        yes,
+        no,
+        total,
+        approved,
+    }
+}
+
+/// Derives the status of an SNS proposal the same way SNS governance does.
+pub fn get_sns_proposal_status(proposal: &ProposalData) -> SnsProposalStatus {
+    if proposal.decided_timestamp_seconds == 0 {
+        SnsProposalStatus::Open
+    } else if proposal.executed_timestamp_seconds > 0 {
+        SnsProposalStatus::Executed
+    } else if proposal.failed_timestamp_seconds > 0 {
+        SnsProposalStatus::Failed
+    } else if is_sns_proposal_accepted(proposal) {
+        SnsProposalStatus::Adopted
+    } else {
+        SnsProposalStatus::Rejected
+    }
+}
+
+fn is_sns_proposal_accepted(proposal: &ProposalData) -> bool {
+    let tally = match &proposal.latest_tally {
+        Some(tally) => tally,
+        None => return false,
+    };
+
+    // SNS defaults: 3% of the total voting power and a simple majority of the votes cast.
+    let min_yes_of_total = proposal
+        .minimum_yes_proportion_of_total
+        .as_ref()
+        .and_then(|percentage| percentage.basis_points)
+        .unwrap_or(300) as u128;
+    let min_yes_of_exercised = proposal
+        .minimum_yes_proportion_of_exercised
+        .as_ref()
+        .and_then(|percentage| percentage.basis_points)
+        .unwrap_or(5_000) as u128;
+
+    let yes = tally.yes as u128;
+    let exercised = tally.yes as u128 + tally.no as u128;
+
+    yes * 10_000 >= tally.total as u128 * min_yes_of_total
+        && yes * 10_000 > exercised * min_yes_of_exercised
+}
+
+fn schedule_vote_phase_check(project_id: ProjectId, step_phase_id: StepPhaseId) {
+    let interval = icvc_configuration::service::get_icvc_config().projects_update_timer_interval;
+    let next_check = ic_cdk::api::time() + interval * 1_000_000_000;
+
+    repository::insert_step_phase_deadline(next_check, project_id, step_phase_id);
+}
+
+/// Queues a proposal submission, or the sync of its voting deadline when `proposal_id` is
+/// set, to be retried by the timer with an exponential backoff.
+pub fn queue_pending_proposal(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    proposal_id: Option<u64>,
+    error: String,
+) -> PendingProposal {
+    let current_time = ic_cdk::api::time();
+    let (attempts, created_at) = match repository::get_pending_proposal(project_id, step_phase_id) {
+        Some(pending) => (pending.attempts + 1, pending.created_at),
+        None => (1, current_time),
+    };
+
+    let backoff = get_pending_proposal_backoff(attempts);
+
+    ic_cdk::println!(
+        "Queued proposal for project id {} on phase id {}, attempt {} failed: {}",
+        project_id,
+        step_phase_id,
+        attempts,
+        error
+    );
+
+    repository::put_pending_proposal(
+        project_id,
+        step_phase_id,
+        PendingProposalModel {
+            proposal_id,
+            attempts,
+            last_error: error,
+            next_attempt_at: current_time + backoff * 1_000_000_000,
+            created_at,
+        },
+    )
+}
+
+// Seconds to wait before the given attempt, doubled after every failed attempt up to a day.
+fn get_pending_proposal_backoff(attempts: u32) -> u64 {
+    PENDING_PROPOSAL_BASE_BACKOFF
+        .saturating_mul(1u64 << attempts.saturating_sub(1).min(32))
+        .min(PENDING_PROPOSAL_MAX_BACKOFF)
+}
+
+/// Retries the pending proposals that are due. Returns the number of retries started.
+pub fn process_pending_proposals(current_time: u64) -> u64 {
+    let due_proposals =
+        repository::get_due_pending_proposals(current_time, MAX_PENDING_PROPOSALS_PER_BATCH);
+
+    for pending in due_proposals.iter() {
+        // Push the next attempt back first, so the next tick doesn't retry it concurrently.
+        if let Some(mut model) =
+            repository::get_pending_proposal(pending.project_id, pending.step_phase_id)
+        {
+            model.next_attempt_at = current_time + PENDING_PROPOSAL_MAX_BACKOFF * 1_000_000_000;
+            repository::put_pending_proposal(pending.project_id, pending.step_phase_id, model);
+        }
+
+        ic_cdk::spawn(retry_pending_proposal(pending.clone()));
     }
+
+    due_proposals.len() as u64
 }
 
+async fn retry_pending_proposal(pending: PendingProposal) {
+    let project_id = pending.project_id;
+    let step_phase_id = pending.step_phase_id;
+
+    if let Some(proposal_id) = pending.proposal_id {
+        sync_voting_end_time(project_id, step_phase_id, proposal_id).await;
+        return;
+    }
+
+    let project = repository::retrieve_project_by_id(project_id);
+    let step_phase = repository::get_step_phase_by_id(project_id, step_phase_id);
+
+    let is_still_pending = match (&project, &step_phase) {
+        (Some(project), Some(step_phase)) => {
+            project.status == ProjectStatus::Open
+                && project.current_phase == step_phase_id
+                && step_phase.status == StepPhaseStatus::PendingProposal
+        }
+        _ => false,
+    };
+
+    if !is_still_pending {
+        repository::remove_pending_proposal(project_id, step_phase_id);
+        return;
+    }
+
+    // The validator only accepts a proposal for a submitted phase, and the assessment period
+    // starts over with the proposal.
+    let current_time = ic_cdk::api::time();
+    let assessment_duration = icvc_configuration::service::get_assessment_duration();
+    repository::update_step_phase(
+        project_id,
+        step_phase_id,
+        StepPhaseUpdate {
+            status: Some(StepPhaseStatus::Submitted),
+            start_assessment_date: Some(current_time),
+            end_assessment_date: Some(current_time + assessment_duration * 1_000_000_000),
+            ..Default::default()
+        },
+    );
+
+    match submit_project_vote_proposal(project_id, step_phase_id).await {
+        Ok(proposal_id) => {
+            repository::remove_pending_proposal(project_id, step_phase_id);
+            sync_voting_end_time(project_id, step_phase_id, proposal_id).await;
+        }
+        Err(e) => {
+            repository::update_step_phase_status(
+                project_id,
+                step_phase_id,
+                StepPhaseStatus::PendingProposal,
+            );
+            queue_pending_proposal(project_id, step_phase_id, None, e.to_string());
+        }
+    }
+}
+
+/// Sets the end of the assessment period of a phase to the voting deadline of its SNS
+/// proposal. If the proposal can't be fetched, the sync is queued to be retried.
+pub async fn sync_voting_end_time(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    proposal_id: u64,
+) -> Option<StepPhase> {
+    match get_sns_proposal_by_id(proposal_id).await {
+        Ok(proposal) => {
+            repository::remove_pending_proposal(project_id, step_phase_id);
+
+            let step_phase = repository::get_step_phase_by_id(project_id, step_phase_id)?;
+            if step_phase.status != StepPhaseStatus::Submitted {
+                return Some(step_phase);
+            }
+
+            repository::update_step_phase(
+                project_id,
+                step_phase_id,
+                StepPhaseUpdate {
+                    end_assessment_date: Some(step::service::calculate_voting_end_time(&proposal)),
+                    ..Default::default()
+                },
+            )
+        }
+        Err(e) => {
+            queue_pending_proposal(
+                project_id,
+                step_phase_id,
+                Some(proposal_id),
+                format!("Unable to fetch the voting deadline: {}", e),
+            );
+            repository::get_step_phase_by_id(project_id, step_phase_id)
+        }
+    }
+}
+
+pub fn get_pending_proposals() -> Vec<PendingProposal> {
+    repository::get_all_pending_proposals()
+}
+
+/// Retrieves a single SNS proposal with `get_proposal`, falling back to paging through
+/// `list_proposals` if the single proposal call fails.
 pub async fn get_sns_proposal_by_id(proposal_id: u64) -> Result<ProposalData, APIError> {
     let canister_config = canister_management::service::get_canister_config();
     let gov_canister_id = match canister_config.sns_governance_id {
@@ -160,43 +773,102 @@ pub async fn get_sns_proposal_by_id(proposal_id: u64) -> Result<ProposalData, AP
             ))
         }
     };
-    //Note: This approach was taken due to some errors encountered when calling the SNS governance for a single proposal.
-    let limit = 100;
-    let method = "list_proposals";
-    let arguments = ListProposals {
-        include_reward_status: vec![],
-        before_proposal: None,
-        limit: limit,
-        exclude_type: vec![],
-        include_status: vec![],
+
+    match call_sns_get_proposal(gov_canister_id, proposal_id).await {
+        Ok(proposal_data) => Ok(proposal_data),
+        // Governance answered, the proposal doesn't exist.
+        Err(APIError::NotFound(msg)) => Err(APIError::NotFound(msg)),
+        Err(e) => {
+            ic_cdk::println!(
+                "get_proposal failed for proposal id {}, listing proposals instead: {}",
+                proposal_id,
+                e
+            );
+            find_sns_proposal_in_list(gov_canister_id, proposal_id).await
+        }
+    }
+}
+
+async fn call_sns_get_proposal(
+    gov_canister_id: Principal,
+    proposal_id: u64,
+) -> Result<ProposalData, APIError> {
+    let method = "get_proposal";
+    let arguments = GetProposal {
+        proposal_id: Some(ProposalId { id: proposal_id }),
     };
 
-    let result: Result<(ListProposalsResponse,), (ic_cdk::api::call::RejectionCode, String)> =
+    let result: Result<(GetProposalResponse,), (RejectionCode, String)> =
         ic_cdk::call(gov_canister_id, method, (arguments,)).await;
 
-    ic_cdk::println!("get proposal: {:?}", result);
     match result {
-        Ok((response,)) => {
-            ic_cdk::println!("{:?}", response);
-            for proposal in response.proposals {
-                ic_cdk::println!("proposal: {:?}", proposal.id);
-                if let Some(proposal_id_struct) = &proposal.id {
-                    if proposal_id_struct.id == proposal_id {
-                        return Ok(proposal);
-                    }
+        Ok((response,)) => match response.result {
+            Some(Result1::Proposal(proposal_data)) => Ok(proposal_data),
+            Some(Result1::Error(error)) => Err(APIError::NotFound(format!(
+                "Proposal with id {} not found: {} (error type {})",
+                proposal_id, error.error_message, error.error_type
+            ))),
+            None => Err(APIError::InternalServerError(format!(
+                "Empty get_proposal response for proposal id {}",
+                proposal_id
+            ))),
+        },
+        Err((code, msg)) => Err(APIError::InternalServerError(format!(
+            "Error: {}: {}",
+            code as i32, msg
+        ))),
+    }
+}
+
+// Proposals are listed from the newest to the oldest, so start right above the wanted id.
+async fn find_sns_proposal_in_list(
+    gov_canister_id: Principal,
+    proposal_id: u64,
+) -> Result<ProposalData, APIError> {
+    let mut before_proposal = proposal_id.checked_add(1);
+
+    loop {
+        let arguments = ListProposals {
+            include_reward_status: vec![],
+            before_proposal: before_proposal.map(|id| ProposalId { id }),
+            limit: LIST_PROPOSALS_PAGE_SIZE,
+            exclude_type: vec![],
+            include_status: vec![],
+        };
+
+        let result: Result<(ListProposalsResponse,), (RejectionCode, String)> =
+            ic_cdk::call(gov_canister_id, "list_proposals", (arguments,)).await;
+
+        let proposals = match result {
+            Ok((response,)) => response.proposals,
+            Err((code, msg)) => {
+                ic_cdk::println!("Error code: {:?}, message: {:?}", code, msg);
+                return Err(APIError::InternalServerError(format!(
+                    "Error: {}: {}",
+                    code as i32, msg
+                )));
+            }
+        };
+
+        let mut oldest_id: Option<u64> = None;
+        for proposal in proposals {
+            if let Some(id) = proposal.id.as_ref().map(|id| id.id) {
+                if id == proposal_id {
+                    return Ok(proposal);
                 }
+                oldest_id = Some(oldest_id.map_or(id, |oldest_id| oldest_id.min(id)));
             }
-            Err(APIError::NotFound(format!(
-                "Proposal with id {} not found",
-                proposal_id
-            )))
         }
-        Err((code, msg)) => {
-            ic_cdk::println!("Error code: {:?}, message: {:?}", code, msg);
-            Err(APIError::InternalServerError(format!(
-                "Error: {}: {}",
-                code as i32, msg
-            )))
+
+        // Keep paging only while there are older proposals left to look at.
+        match oldest_id {
+            Some(oldest_id) if oldest_id > proposal_id => before_proposal = Some(oldest_id),
+            _ => {
+                return Err(APIError::NotFound(format!(
+                    "Proposal with id {} not found",
+                    proposal_id
+                )))
+            }
         }
     }
 }
@@ -259,39 +931,227 @@ pub async fn submit_project_vote_proposal(
         }
     };
 
-    let proposal = Proposal {
-        url: "https://icvc.com/".to_string(),
-        title: "ICVC project votting".to_string(),
-        action: Some(Action::ExecuteGenericNervousSystemFunction(
-            ExecuteGenericNervousSystemFunction {
-                function_id: 4001,
-                payload: serialized_payload,
-            },
-        )),
-        summary: format!("Votting for {}", project.title),
-    };
-
-    match make_sns_proposal(proposal).await {
+    match make_sns_proposal(|| {
+        build_project_vote_proposal(&project, phase_id, serialized_payload.clone())
+    })
+    .await
+    {
         Ok(proposal) => {
             repository::put_step_phase_proposal(project_id, phase_id, proposal.id);
+            audit::service::record(
+                ic_cdk::caller(),
+                AuditAction::VoteProposalSubmitted,
+                AuditTarget::step_phase(project_id, phase_id).with_entity(proposal.id),
+                None,
+                None,
+            );
             Ok(proposal.id)
         }
         Err(e) => return Err(e),
     }
 }
 
-async fn make_sns_proposal(proposal: Proposal) -> Result<ProposalId, APIError> {
-    let canister_config: CanisterConfig = canister_management::service::get_canister_config();
+/// Renders the SNS proposal of a project vote from the configured proposal template.
+fn build_project_vote_proposal(
+    project: &Project,
+    step_phase_id: StepPhaseId,
+    payload: Vec<u8>,
+) -> Result<Proposal, APIError> {
+    let template = icvc_configuration::service::get_project_vote_proposal_template();
+    let url = render_proposal_template(&template.url, project, step_phase_id, "");
 
-    let subaccount = match canister_config.subaccount {
-        Some(ref subaccount) => subaccount.clone(),
-        None => {
-            return Err(APIError::BadRequest(
-                "Subaccount is not set in the canister configuration.".to_string(),
-            ))
+    if url.len() > MAX_PROPOSAL_URL_BYTES {
+        return Err(APIError::BadRequest(format!(
+            "Proposal url is {} bytes long, max allowed: {}",
+            url.len(),
+            MAX_PROPOSAL_URL_BYTES
+        )));
+    }
+
+    let title = render_proposal_template(&template.title, project, step_phase_id, &url);
+    let summary = render_proposal_template(&template.summary, project, step_phase_id, &url);
+
+    Ok(Proposal {
+        title: truncate_to_bytes(title, MAX_PROPOSAL_TITLE_BYTES, ""),
+        summary: truncate_to_bytes(
+            summary,
+            MAX_PROPOSAL_SUMMARY_BYTES,
+            TRUNCATED_SUMMARY_NOTICE,
+        ),
+        url,
+        action: Some(Action::ExecuteGenericNervousSystemFunction(
+            ExecuteGenericNervousSystemFunction {
+                function_id: template.function_id,
+                payload,
+            },
+        )),
+    })
+}
+
+/// Renders the summary of the project under vote so voters can review it from the NNS dapp.
+fn render_project_vote_summary(project: &Project, step_phase_id: StepPhaseId) -> String {
+    let template = icvc_configuration::service::get_project_vote_proposal_template();
+    let url = render_proposal_template(&template.url, project, step_phase_id, "");
+    let summary = render_proposal_template(&template.summary, project, step_phase_id, &url);
+
+    truncate_to_bytes(
+        summary,
+        MAX_PROPOSAL_SUMMARY_BYTES,
+        TRUNCATED_SUMMARY_NOTICE,
+    )
+}
+
+// The template is scanned once, so the placeholders written by the founder in the project
+// fields are kept as they are instead of being expanded.
+fn render_proposal_template(
+    template: &str,
+    project: &Project,
+    step_phase_id: StepPhaseId,
+    submission_url: &str,
+) -> String {
+    let mut rendering = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        rendering.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        match render_placeholder(&rest[1..end], project, step_phase_id, submission_url) {
+            Some(value) => {
+                rendering.push_str(&value);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                rendering.push('{');
+                rest = &rest[1..];
+            }
         }
+    }
+    rendering.push_str(rest);
+
+    rendering
+}
+
+// Value of a placeholder of the template, the lists are only built when the template uses them.
+fn render_placeholder(
+    placeholder: &str,
+    project: &Project,
+    step_phase_id: StepPhaseId,
+    submission_url: &str,
+) -> Option<String> {
+    let value = match placeholder {
+        "project_id" => project.id.to_string(),
+        "project_title" => project.title.clone(),
+        "project_moto" => project.moto.clone(),
+        "project_description" => project.description.clone(),
+        "phase_id" => step_phase_id.to_string(),
+        "phase_name" => icvc_configuration::service::get_step_phase_name(step_phase_id),
+        "submission_url" => submission_url.to_string(),
+        "key_metrics" => render_key_metrics(project, step_phase_id),
+        "categories" => render_list(
+            project
+                .categories
+                .iter()
+                .filter_map(|category_id| repository::get_category_by_id(*category_id))
+                .map(|category| format!("- {}", category.name))
+                .collect(),
+        ),
+        "team" => render_list(
+            project
+                .team_members
+                .iter()
+                .map(|team_member| {
+                    format!(
+                        "- {} {}, {}",
+                        team_member.first_name, team_member.last_name, team_member.position
+                    )
+                })
+                .collect(),
+        ),
+        "links" => render_list(
+            project
+                .links
+                .iter()
+                .map(|link| format!("- {}: {}", link.kind, link.url))
+                .collect(),
+        ),
+        _ => return None,
     };
 
+    Some(value)
+}
+
+// Results of the previous phases and the numeric values submitted on the phase under vote.
+fn render_key_metrics(project: &Project, step_phase_id: StepPhaseId) -> String {
+    let mut metrics = vec![];
+
+    for previous_phase_id in 0..step_phase_id {
+        let phase_name = icvc_configuration::service::get_step_phase_name(previous_phase_id);
+
+        if let Some(grade_result) =
+            repository::get_grade_result_by_step_phase_id(project.id, previous_phase_id)
+        {
+            metrics.push(format!(
+                "- {} grade: {:.2} ({} grades)",
+                phase_name, grade_result.avg_result, grade_result.total_steps_grades_count
+            ));
+        }
+        if let Some(vote_result) =
+            repository::get_vote_result_by_step_phase_id(project.id, previous_phase_id)
+        {
+            metrics.push(format!(
+                "- {} vote: {} yes, {} no",
+                phase_name, vote_result.yes, vote_result.no
+            ));
+        }
+    }
+
+    for step in repository::get_all_steps_by_phase(project.id, step_phase_id) {
+        for decimal_submission in step.decimal_submission {
+            metrics.push(format!(
+                "- {}: {}",
+                decimal_submission.id, decimal_submission.value
+            ));
+        }
+    }
+
+    render_list(metrics)
+}
+
+fn render_list(items: Vec<String>) -> String {
+    if items.is_empty() {
+        "None".to_string()
+    } else {
+        items.join("\n")
+    }
+}
+
+// Cuts the text on a char boundary so it fits the SNS limits, ending with the given notice.
+fn truncate_to_bytes(text: String, max_bytes: usize, notice: &str) -> String {
+    if text.len() <= max_bytes {
+        return text;
+    }
+
+    let mut end = max_bytes.saturating_sub(notice.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    format!("{}{}", &text[..end], notice)
+}
+
+/// Makes an SNS proposal with the first eligible neuron of the canister. A neuron that is
+/// refused by governance is marked as ineligible and the next one is tried. The proposal is
+/// built again for every neuron that is tried.
+async fn make_sns_proposal<F>(build_proposal: F) -> Result<ProposalId, APIError>
+where
+    F: Fn() -> Result<Proposal, APIError>,
+{
+    let canister_config: CanisterConfig = canister_management::service::get_canister_config();
+
     let sns_governance_id = match canister_config.sns_governance_id {
         Some(sns_gov_canister_id) => sns_gov_canister_id,
         None => {
@@ -301,11 +1161,45 @@ async fn make_sns_proposal(proposal: Proposal) -> Result<ProposalId, APIError> {
         }
     };
 
-    let subaccount_bytes = hex_to_bytes(&subaccount).map_err(|err| {
-        APIError::BadRequest(format!(
-            "Failed to decode subaccount from hex string: {}. Error: {}", 
-            subaccount, 
-            err
+    let subaccounts = get_proposal_neuron_subaccounts(&canister_config)?;
+    let mut neuron_errors = vec![];
+
+    for subaccount in subaccounts {
+        match make_sns_proposal_with_neuron(sns_governance_id, &subaccount, build_proposal()?).await
+        {
+            Ok(proposal_id) => {
+                record_sns_neuron_proposal(&subaccount);
+                return Ok(proposal_id);
+            }
+            Err(MakeProposalError::Neuron(msg)) => {
+                record_sns_neuron_error(&subaccount, msg.clone());
+                neuron_errors.push(format!("{}: {}", subaccount, msg));
+            }
+            Err(MakeProposalError::Other(e)) => return Err(e),
+        }
+    }
+
+    Err(APIError::BadRequest(format!(
+        "No neuron was able to make the proposal. {}",
+        neuron_errors.join(" ")
+    )))
+}
+
+enum MakeProposalError {
+    /// Governance refused the neuron, another neuron may succeed.
+    Neuron(String),
+    Other(APIError),
+}
+
+async fn make_sns_proposal_with_neuron(
+    sns_governance_id: Principal,
+    subaccount: &str,
+    proposal: Proposal,
+) -> Result<ProposalId, MakeProposalError> {
+    let subaccount_bytes = hex_to_bytes(subaccount).map_err(|err| {
+        MakeProposalError::Neuron(format!(
+            "Failed to decode subaccount from hex string: {}. Error: {}",
+            subaccount, err
         ))
     })?;
 
@@ -326,30 +1220,545 @@ async fn make_sns_proposal(proposal: Proposal) -> Result<ProposalId, APIError> {
                         if let Some(proposal_id) = get_proposal.proposal_id {
                             Ok(proposal_id)
                         } else {
-                            Err(APIError::InternalServerError(format!(
-                                "Error making proposa, no proposal id found."
+                            Err(MakeProposalError::Other(APIError::InternalServerError(
+                                "Error making proposal, no proposal id found.".to_string(),
                             )))
                         }
                     }
-                    _ => Err(APIError::InternalServerError(format!(
-                        "Error making proposal command make proposal not found."
+                    Command1::Error(error) => {
+                        let msg = format!(
+                            "Error making proposal: {} (error type {})",
+                            error.error_message, error.error_type
+                        );
+                        if NEURON_GOVERNANCE_ERROR_TYPES.contains(&error.error_type) {
+                            Err(MakeProposalError::Neuron(msg))
+                        } else {
+                            Err(MakeProposalError::Other(APIError::BadRequest(msg)))
+                        }
+                    }
+                    _ => Err(MakeProposalError::Other(APIError::InternalServerError(
+                        "Error making proposal command make proposal not found.".to_string(),
                     ))),
                 },
-                None => Err(APIError::InternalServerError(format!(
-                    "Error making proposal, no command found"
+                None => Err(MakeProposalError::Other(APIError::InternalServerError(
+                    "Error making proposal, no command found".to_string(),
                 ))),
             }
         }
         Err((code, msg)) => {
             ic_cdk::println!("Error code: {:?}, message: {:?}", code, msg);
-            Err(APIError::InternalServerError(format!(
-                "Error making proposal: code: {:?}, message: {}",
+            Err(MakeProposalError::Other(APIError::InternalServerError(
+                format!("Error making proposal: code: {:?}, message: {}", code, msg),
+            )))
+        }
+    }
+}
+
+/// Returns the subaccounts of the neurons to try, the configured subaccount first and then
+/// the neurons with the most stake left. Until the neurons were listed once, only the
+/// configured subaccount is used.
+fn get_proposal_neuron_subaccounts(
+    canister_config: &CanisterConfig,
+) -> Result<Vec<String>, APIError> {
+    let neurons = repository::get_all_sns_neurons();
+
+    if neurons.is_empty() {
+        return match canister_config.subaccount {
+            Some(ref subaccount) => Ok(vec![subaccount.clone()]),
+            None => Err(APIError::BadRequest(
+                "Subaccount is not set in the canister configuration.".to_string(),
+            )),
+        };
+    }
+
+    let mut eligible_neurons: Vec<SnsNeuron> = neurons
+        .iter()
+        .filter(|neuron| neuron.ineligible_reason.is_none())
+        .cloned()
+        .collect();
+
+    if eligible_neurons.is_empty() {
+        let reasons: Vec<String> = neurons
+            .into_iter()
+            .map(|neuron| {
+                format!(
+                    "{}: {}",
+                    neuron.subaccount,
+                    neuron.ineligible_reason.unwrap_or_default()
+                )
+            })
+            .collect();
+
+        return Err(APIError::BadRequest(format!(
+            "No neuron is eligible to make proposals. {}",
+            reasons.join(" ")
+        )));
+    }
+
+    eligible_neurons.sort_by_key(|neuron| {
+        (
+            Some(&neuron.subaccount) != canister_config.subaccount.as_ref(),
+            std::cmp::Reverse(get_available_stake(
+                neuron.stake_e8s,
+                neuron.neuron_fees_e8s,
+            )),
+        )
+    });
+
+    Ok(eligible_neurons
+        .into_iter()
+        .map(|neuron| neuron.subaccount)
+        .collect())
+}
+
+// Governance charges the rejection fee upfront and refunds it if the proposal is adopted.
+fn record_sns_neuron_proposal(subaccount: &str) {
+    if let Some(mut model) = repository::get_sns_neuron(subaccount) {
+        model.neuron_fees_e8s = model.neuron_fees_e8s.saturating_add(model.proposal_fee_e8s);
+        model.last_used_at = Some(ic_cdk::api::time());
+        model.last_error = None;
+        model.ineligible_reason = get_sns_neuron_ineligible_reason(&model);
+        repository::put_sns_neuron(subaccount.to_string(), model);
+    }
+}
+
+// The neuron is skipped until the neurons are listed again.
+fn record_sns_neuron_error(subaccount: &str, error: String) {
+    if let Some(mut model) = repository::get_sns_neuron(subaccount) {
+        model.ineligible_reason = Some(error.clone());
+        model.last_error = Some(error);
+        repository::put_sns_neuron(subaccount.to_string(), model);
+    }
+}
+
+/// Lists the neurons of the canister on SNS governance and checks whether they can still
+/// make proposals.
+pub async fn refresh_sns_neurons() -> Result<SnsNeuronsHealth, APIError> {
+    let canister_config = canister_management::service::get_canister_config();
+    let sns_governance_id = canister_config.sns_governance_id.ok_or_else(|| {
+        APIError::BadRequest(
+            "SNS governance canister ID is not set in the canister configuration.".to_string(),
+        )
+    })?;
+
+    let parameters: Result<(NervousSystemParameters,), (RejectionCode, String)> =
+        ic_cdk::call(sns_governance_id, "get_nervous_system_parameters", ((),)).await;
+    let parameters = match parameters {
+        Ok((parameters,)) => parameters,
+        Err((code, msg)) => {
+            return Err(APIError::InternalServerError(format!(
+                "Unable to get the nervous system parameters: code: {:?}, message: {}",
                 code, msg
             )))
         }
+    };
+
+    let neurons = list_canister_sns_neurons(sns_governance_id).await?;
+
+    let canister_id = ic_cdk::id();
+    let current_time = ic_cdk::api::time();
+    let current_time_seconds = current_time / 1_000_000_000;
+
+    let models = neurons
+        .into_iter()
+        .filter_map(|neuron| {
+            let subaccount = hex::encode(&neuron.id.as_ref()?.id);
+            let previous_model = repository::get_sns_neuron(&subaccount);
+
+            let dissolve_delay_seconds = match neuron.dissolve_state {
+                Some(DissolveState::DissolveDelaySeconds(delay)) => delay,
+                Some(DissolveState::WhenDissolvedTimestampSeconds(timestamp)) => {
+                    timestamp.saturating_sub(current_time_seconds)
+                }
+                None => 0,
+            };
+
+            let can_submit_proposals = neuron.permissions.iter().any(|permission| {
+                permission.principal == Some(canister_id)
+                    && permission
+                        .permission_type
+                        .contains(&NEURON_PERMISSION_SUBMIT_PROPOSAL)
+            });
+
+            let mut model = SnsNeuronModel {
+                stake_e8s: neuron.cached_neuron_stake_e8s,
+                neuron_fees_e8s: neuron.neuron_fees_e8s,
+                dissolve_delay_seconds,
+                can_submit_proposals,
+                proposal_fee_e8s: parameters.reject_cost_e8s.unwrap_or(0),
+                min_dissolve_delay_seconds: parameters
+                    .neuron_minimum_dissolve_delay_to_vote_seconds
+                    .unwrap_or(0),
+                ineligible_reason: None,
+                last_error: previous_model
+                    .as_ref()
+                    .and_then(|model| model.last_error.clone()),
+                last_used_at: previous_model.and_then(|model| model.last_used_at),
+                checked_at: current_time,
+            };
+            model.ineligible_reason = get_sns_neuron_ineligible_reason(&model);
+
+            Some((subaccount, model))
+        })
+        .collect();
+
+    repository::replace_sns_neurons(models);
+
+    Ok(get_sns_neurons_health())
+}
+
+async fn list_canister_sns_neurons(sns_governance_id: Principal) -> Result<Vec<Neuron>, APIError> {
+    let mut neurons: Vec<Neuron> = vec![];
+
+    loop {
+        let arguments = ListNeurons {
+            of_principal: Some(ic_cdk::id()),
+            limit: LIST_NEURONS_PAGE_SIZE,
+            start_page_at: neurons
+                .last()
+                .and_then(|neuron| neuron.id.as_ref())
+                .map(|neuron_id| NeuronId {
+                    id: neuron_id.id.clone(),
+                }),
+        };
+
+        let result: Result<(ListNeuronsResponse,), (RejectionCode, String)> =
+            ic_cdk::call(sns_governance_id, "list_neurons", (arguments,)).await;
+
+        let page = match result {
+            Ok((response,)) => response.neurons,
+            Err((code, msg)) => {
+                return Err(APIError::InternalServerError(format!(
+                    "Unable to list neurons: code: {:?}, message: {}",
+                    code, msg
+                )))
+            }
+        };
+
+        let is_last_page = page.len() < LIST_NEURONS_PAGE_SIZE as usize;
+        neurons.extend(page);
+
+        if is_last_page {
+            return Ok(neurons);
+        }
+    }
+}
+
+fn get_sns_neuron_ineligible_reason(model: &SnsNeuronModel) -> Option<String> {
+    let available_stake_e8s = get_available_stake(model.stake_e8s, model.neuron_fees_e8s);
+
+    if !model.can_submit_proposals {
+        Some("The canister is not allowed to submit proposals with this neuron.".to_string())
+    } else if model.dissolve_delay_seconds < model.min_dissolve_delay_seconds {
+        Some(format!(
+            "Dissolve delay of {} seconds is below the minimum of {} seconds.",
+            model.dissolve_delay_seconds, model.min_dissolve_delay_seconds
+        ))
+    } else if available_stake_e8s < model.proposal_fee_e8s {
+        Some(format!(
+            "Stake of {} e8s left after fees doesn't cover the proposal fee of {} e8s.",
+            available_stake_e8s, model.proposal_fee_e8s
+        ))
+    } else {
+        None
+    }
+}
+
+fn get_available_stake(stake_e8s: u64, neuron_fees_e8s: u64) -> u64 {
+    stake_e8s.saturating_sub(neuron_fees_e8s)
+}
+
+pub fn get_sns_neurons_health() -> SnsNeuronsHealth {
+    let neurons = repository::get_all_sns_neurons();
+    let eligible_neurons: Vec<&SnsNeuron> = neurons
+        .iter()
+        .filter(|neuron| neuron.ineligible_reason.is_none())
+        .collect();
+
+    let remaining_proposals = eligible_neurons.iter().fold(0u64, |total, neuron| {
+        let available_stake_e8s = get_available_stake(neuron.stake_e8s, neuron.neuron_fees_e8s);
+        let proposals = available_stake_e8s
+            .checked_div(neuron.proposal_fee_e8s)
+            .unwrap_or(u64::MAX);
+        total.saturating_add(proposals)
+    });
+
+    SnsNeuronsHealth {
+        eligible_neurons: eligible_neurons.len() as u64,
+        remaining_proposals,
+        neurons,
     }
 }
 
 pub fn hex_to_bytes(hex_string: &str) -> Result<Vec<u8>, hex::FromHexError> {
     hex::decode(hex_string)
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use candid::Principal;
+
+    use super::*;
+    use crate::{
+        domains::{
+            project::types::{Link, TeamMember},
+            sns_integration::types_sns_governance::Tally,
+        },
+        ProposalData,
+    };
+
+    fn proposal_with_tally(yes: u64, no: u64, total: u64) -> ProposalData {
+        ProposalData {
+            id: None,
+            payload_text_rendering: None,
+            action: 0,
+            failure_reason: None,
+            action_auxiliary: None,
+            ballots: HashMap::new(),
+            minimum_yes_proportion_of_total: None,
+            reward_event_round: 0,
+            failed_timestamp_seconds: 0,
+            reward_event_end_timestamp_seconds: None,
+            proposal_creation_timestamp_seconds: 0,
+            initial_voting_period_seconds: 0,
+            reject_cost_e8s: 0,
+            latest_tally: Some(Tally {
+                no,
+                yes,
+                total,
+                timestamp_seconds: 0,
+            }),
+            wait_for_quiet_deadline_increase_seconds: 0,
+            decided_timestamp_seconds: 0,
+            proposal: None,
+            proposer: None,
+            wait_for_quiet_state: None,
+            minimum_yes_proportion_of_exercised: None,
+            is_eligible_for_rewards: false,
+            executed_timestamp_seconds: 0,
+        }
+    }
+
+    fn vote_rules(min_yes_ratio: u64, min_participation: u64, min_yes_votes: u64) -> VoteRules {
+        VoteRules {
+            use_sns_decision: false,
+            min_yes_ratio,
+            min_participation,
+            min_yes_votes,
+        }
+    }
+
+    fn project() -> Project {
+        Project {
+            id: 7,
+            user_id: Principal::anonymous(),
+            title: "Solar farm".to_string(),
+            moto: "Power to the people".to_string(),
+            description: "A community solar farm.".to_string(),
+            team_members: vec![],
+            links: vec![],
+            categories: vec![],
+            current_phase: 1,
+            status: ProjectStatus::Open,
+            created_at: 0,
+            update_by: None,
+            update_at: None,
+            suspended_at: None,
+        }
+    }
+
+    #[test]
+    fn vote_is_approved_strictly_above_the_yes_ratio() {
+        let rules = vote_rules(5_000, 0, 0);
+
+        assert!(evaluate_vote_result(&proposal_with_tally(51, 49, 100), &rules).approved);
+        assert!(!evaluate_vote_result(&proposal_with_tally(50, 50, 100), &rules).approved);
+    }
+
+    #[test]
+    fn vote_needs_the_minimum_participation() {
+        let rules = vote_rules(5_000, 2_000, 0);
+
+        assert!(evaluate_vote_result(&proposal_with_tally(20, 0, 100), &rules).approved);
+        assert!(!evaluate_vote_result(&proposal_with_tally(19, 0, 100), &rules).approved);
+    }
+
+    #[test]
+    fn vote_needs_the_minimum_yes_votes() {
+        let rules = vote_rules(0, 0, 10);
+
+        assert!(evaluate_vote_result(&proposal_with_tally(10, 0, 100), &rules).approved);
+        assert!(!evaluate_vote_result(&proposal_with_tally(9, 0, 100), &rules).approved);
+    }
+
+    #[test]
+    fn vote_without_ballots_is_rejected() {
+        let rules = vote_rules(0, 0, 0);
+
+        let result = evaluate_vote_result(&proposal_with_tally(0, 0, 0), &rules);
+        assert!(!result.approved);
+
+        let mut proposal = proposal_with_tally(0, 0, 0);
+        proposal.latest_tally = None;
+        let result = evaluate_vote_result(&proposal, &rules);
+        assert!(!result.approved);
+        assert_eq!((result.yes, result.no, result.total), (0, 0, 0));
+    }
+
+    #[test]
+    fn vote_follows_the_sns_decision() {
+        let mut rules = vote_rules(9_000, 9_000, 1_000);
+        rules.use_sns_decision = true;
+
+        // Still open on the SNS.
+        let mut proposal = proposal_with_tally(60, 40, 100);
+        assert!(!evaluate_vote_result(&proposal, &rules).approved);
+
+        proposal.decided_timestamp_seconds = 1;
+        let result = evaluate_vote_result(&proposal, &rules);
+        assert!(result.approved);
+        assert_eq!((result.yes, result.no, result.total), (60, 40, 100));
+
+        let proposal = ProposalData {
+            decided_timestamp_seconds: 1,
+            ..proposal_with_tally(40, 60, 100)
+        };
+        assert!(!evaluate_vote_result(&proposal, &rules).approved);
+    }
+
+    #[test]
+    fn sns_decision_does_not_overflow_on_large_tallies() {
+        let proposal = ProposalData {
+            decided_timestamp_seconds: 1,
+            ..proposal_with_tally(u64::MAX, 1, u64::MAX)
+        };
+
+        assert_eq!(
+            get_sns_proposal_status(&proposal),
+            SnsProposalStatus::Adopted
+        );
+    }
+
+    #[test]
+    fn template_placeholders_are_rendered() {
+        let rendering = render_proposal_template(
+            "{project_id} {project_title}: {project_moto} ({project_description}) {phase_id} {phase_name} {submission_url}",
+            &project(),
+            1,
+            "https://example.com",
+        );
+
+        assert_eq!(
+            rendering,
+            "7 Solar farm: Power to the people (A community solar farm.) 1 Phase 1 https://example.com"
+        );
+    }
+
+    #[test]
+    fn template_lists_are_rendered() {
+        let mut project = project();
+        project.team_members = vec![TeamMember {
+            first_name: "Ada".to_string(),
+            last_name: "Lovelace".to_string(),
+            position: "CTO".to_string(),
+            previous_experience: String::new(),
+            links: vec![],
+            profile_picture: String::new(),
+        }];
+        project.links = vec![
+            Link {
+                kind: "Website".to_string(),
+                url: "https://example.com".to_string(),
+            },
+            Link {
+                kind: "Github".to_string(),
+                url: "https://github.com/example".to_string(),
+            },
+        ];
+
+        let rendering = render_proposal_template("{team}\n{links}", &project, 1, "");
+
+        assert_eq!(
+            rendering,
+            "- Ada Lovelace, CTO\n- Website: https://example.com\n- Github: https://github.com/example"
+        );
+    }
+
+    #[test]
+    fn placeholders_in_the_project_fields_are_not_expanded() {
+        let mut project = project();
+        project.title = "{links}{team} {project_title}".to_string();
+        project.links = vec![Link {
+            kind: "Website".to_string(),
+            url: "https://example.com".to_string(),
+        }];
+
+        let rendering = render_proposal_template("{project_title}: {links}", &project, 1, "");
+
+        assert_eq!(
+            rendering,
+            "{links}{team} {project_title}: - Website: https://example.com"
+        );
+    }
+
+    #[test]
+    fn unknown_placeholders_and_braces_are_kept() {
+        let rendering =
+            render_proposal_template("{unknown} {{project_id}} {project_id", &project(), 1, "");
+
+        assert_eq!(rendering, "{unknown} {7} {project_id");
+    }
+
+    #[test]
+    fn empty_template_lists_are_rendered_as_none() {
+        let rendering = render_proposal_template("{team} {links} {categories}", &project(), 1, "");
+
+        assert_eq!(rendering, "None None None");
+    }
+
+    #[test]
+    fn text_within_the_limit_is_kept() {
+        assert_eq!(truncate_to_bytes("short".to_string(), 5, "..."), "short");
+    }
+
+    #[test]
+    fn text_is_truncated_with_the_notice() {
+        let truncated = truncate_to_bytes("abcdefghij".to_string(), 8, "...");
+
+        assert_eq!(truncated, "abcde...");
+    }
+
+    #[test]
+    fn text_is_truncated_on_a_char_boundary() {
+        // "é" takes two bytes, the cut would fall in the middle of the third one.
+        let truncated = truncate_to_bytes("ééééé".to_string(), 8, "...");
+
+        assert_eq!(truncated, "éé...");
+        assert!(truncated.len() <= 8);
+    }
+
+    #[test]
+    fn pending_proposal_backoff_doubles_up_to_a_day() {
+        assert_eq!(
+            get_pending_proposal_backoff(1),
+            PENDING_PROPOSAL_BASE_BACKOFF
+        );
+        assert_eq!(
+            get_pending_proposal_backoff(2),
+            2 * PENDING_PROPOSAL_BASE_BACKOFF
+        );
+        assert_eq!(
+            get_pending_proposal_backoff(3),
+            4 * PENDING_PROPOSAL_BASE_BACKOFF
+        );
+        assert_eq!(
+            get_pending_proposal_backoff(12),
+            PENDING_PROPOSAL_MAX_BACKOFF
+        );
+        assert_eq!(
+            get_pending_proposal_backoff(u32::MAX),
+            PENDING_PROPOSAL_MAX_BACKOFF
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use candid::{CandidType, Deserialize};
 use serde::Serialize;
 
@@ -8,3 +10,121 @@ pub struct ProjectProposalPayload {
     pub project_id: ProjectId,
     pub phase_id: StepPhaseId,
 }
+
+#[derive(CandidType, Deserialize, PartialEq, Clone, Debug)]
+pub enum SnsProposalStatus {
+    Open,
+    Adopted,
+    Rejected,
+    Executed,
+    Failed,
+}
+
+impl fmt::Display for SnsProposalStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            SnsProposalStatus::Open => write!(f, "Open"),
+            SnsProposalStatus::Adopted => write!(f, "Adopted"),
+            SnsProposalStatus::Rejected => write!(f, "Rejected"),
+            SnsProposalStatus::Executed => write!(f, "Executed"),
+            SnsProposalStatus::Failed => write!(f, "Failed"),
+        }
+    }
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ProposalExecutionReceipt {
+    pub proposal_id: u64,
+    pub project_id: ProjectId,
+    pub step_phase_id: StepPhaseId,
+    pub approved: bool,
+    pub executed_at: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct PendingProposal {
+    pub project_id: ProjectId,
+    pub step_phase_id: StepPhaseId,
+    /// Set once the proposal was made and only its voting deadline is left to sync.
+    pub proposal_id: Option<u64>,
+    pub attempts: u32,
+    pub last_error: String,
+    pub next_attempt_at: u64,
+    pub created_at: u64,
+}
+
+/// A neuron of the canister that can be used to make SNS proposals, as last checked.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SnsNeuron {
+    pub subaccount: String,
+    pub stake_e8s: u64,
+    pub neuron_fees_e8s: u64,
+    pub dissolve_delay_seconds: u64,
+    pub can_submit_proposals: bool,
+    pub proposal_fee_e8s: u64,
+    pub min_dissolve_delay_seconds: u64,
+    /// Why the neuron is skipped when making proposals, `None` if it is eligible.
+    pub ineligible_reason: Option<String>,
+    pub last_error: Option<String>,
+    pub last_used_at: Option<u64>,
+    pub checked_at: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SnsNeuronsHealth {
+    pub neurons: Vec<SnsNeuron>,
+    pub eligible_neurons: u64,
+    /// How many more proposals the eligible neurons can pay the rejection fee of.
+    pub remaining_proposals: u64,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Clone, Debug)]
+pub enum BallotVote {
+    Unspecified,
+    Yes,
+    No,
+}
+
+impl fmt::Display for BallotVote {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            BallotVote::Unspecified => write!(f, "Unspecified"),
+            BallotVote::Yes => write!(f, "Yes"),
+            BallotVote::No => write!(f, "No"),
+        }
+    }
+}
+
+/// The final ballot of an SNS neuron on the proposal of a project vote phase.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ProjectBallot {
+    pub project_id: ProjectId,
+    pub step_phase_id: StepPhaseId,
+    pub proposal_id: u64,
+    /// The neuron id as a hex string, as in the ballots of SNS governance.
+    pub neuron_id: String,
+    pub vote: BallotVote,
+    pub voting_power: u64,
+    pub cast_timestamp_seconds: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, Default)]
+pub struct VotingStats {
+    pub eligible_neurons: u64,
+    pub voted_neurons: u64,
+    pub yes_neurons: u64,
+    pub no_neurons: u64,
+    pub total_voting_power: u64,
+    pub yes_voting_power: u64,
+    pub no_voting_power: u64,
+    /// Share of the voting power that was cast, in basis points.
+    pub participation: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StepPhaseVotingBreakdown {
+    pub project_id: ProjectId,
+    pub step_phase_id: StepPhaseId,
+    pub ballots: Vec<ProjectBallot>,
+    pub stats: VotingStats,
+}
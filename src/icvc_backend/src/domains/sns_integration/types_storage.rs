@@ -0,0 +1,98 @@
+use candid::{CandidType, Deserialize};
+use candid::{Decode, Encode};
+use ic_stable_structures::{storable::Bound, Storable};
+use std::borrow::Cow;
+
+use crate::{ProjectId, StepPhaseId};
+
+use super::types::BallotVote;
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ProposalExecutionReceiptModel {
+    pub project_id: ProjectId,
+    pub step_phase_id: StepPhaseId,
+    pub approved: bool,
+    pub executed_at: u64,
+}
+
+impl Storable for ProposalExecutionReceiptModel {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct PendingProposalModel {
+    pub proposal_id: Option<u64>,
+    pub attempts: u32,
+    pub last_error: String,
+    pub next_attempt_at: u64,
+    pub created_at: u64,
+}
+
+impl Storable for PendingProposalModel {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SnsNeuronModel {
+    pub stake_e8s: u64,
+    pub neuron_fees_e8s: u64,
+    pub dissolve_delay_seconds: u64,
+    pub can_submit_proposals: bool,
+    pub proposal_fee_e8s: u64,
+    pub min_dissolve_delay_seconds: u64,
+    pub ineligible_reason: Option<String>,
+    pub last_error: Option<String>,
+    pub last_used_at: Option<u64>,
+    pub checked_at: u64,
+}
+
+impl Storable for SnsNeuronModel {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+/// SNS neuron ids are 32 bytes long, they are stored as is to be usable in tuple keys.
+pub type SnsNeuronIdKey = [u8; 32];
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct BallotModel {
+    pub proposal_id: u64,
+    pub vote: BallotVote,
+    pub voting_power: u64,
+    pub cast_timestamp_seconds: u64,
+}
+
+impl Storable for BallotModel {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
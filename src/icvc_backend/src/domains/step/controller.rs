@@ -1,8 +1,13 @@
 //! This module defines the controller functions for project steps queries and updates.
 use crate::{
-    utils::authenticator::check_is_project_owner_or_admin, APIError, ProjectId, Step, StepGrade,
-    StepId, StepPhase, StepPhaseGradeResult, StepPhaseId, StepPhaseProposal, StepPhaseVoteResult,
-    StepUpdate, UploadUrlRequest, UploadUrlResponse,
+    utils::authenticator::{
+        check_is_owner_or_admin, check_is_owner_or_governance_id, check_is_project_owner_or_admin,
+        check_is_project_owner_reviewer_or_admin,
+    },
+    APIError, DocumentId, DownloadUrlResponse, ProjectId, Step, StepGrade, StepId, StepPhase,
+    StepPhaseAdminAction, StepPhaseDeadlineExtension, StepPhaseDeadlineExtensionPayload,
+    StepPhaseGradeResult, StepPhaseId, StepPhaseProposal, StepPhaseReopen, StepPhaseVoteResult,
+    StepUpdate, UploadConfirmation, UploadFile, UploadUrlRequest, UploadUrlResponse,
 };
 
 use super::service;
@@ -64,6 +69,98 @@ pub async fn submit_step_phase(
     service::submit_step_phase(project_id, step_phase_id).await
 }
 
+/// Extends the open or assessment deadline of the current step phase of a project.
+///
+/// # Arguments
+///
+/// * `project_id` - The ID of the project.
+/// * `step_phase_id` - The ID of the step phase.
+/// * `extension` - The deadline to extend, its new end date and the reason for the extension.
+///
+/// # Returns
+///
+/// * `Result<StepPhase, APIError>` - The updated step phase or an error.
+#[ic_cdk::update(name = "extendStepPhaseDeadline")]
+pub fn extend_step_phase_deadline(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    extension: StepPhaseDeadlineExtension,
+) -> Result<StepPhase, APIError> {
+    let caller_id = ic_cdk::caller();
+    check_is_owner_or_admin(caller_id)?;
+
+    service::extend_step_phase_deadline(caller_id, project_id, step_phase_id, extension)
+}
+
+/// Reopens a step phase that was closed as not submitted, starting a new open period.
+///
+/// # Arguments
+///
+/// * `project_id` - The ID of the project.
+/// * `step_phase_id` - The ID of the step phase.
+/// * `reopen` - The optional new open end date and the reason for reopening.
+///
+/// # Returns
+///
+/// * `Result<StepPhase, APIError>` - The reopened step phase or an error.
+#[ic_cdk::update(name = "reopenStepPhase")]
+pub fn reopen_step_phase(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    reopen: StepPhaseReopen,
+) -> Result<StepPhase, APIError> {
+    let caller_id = ic_cdk::caller();
+    check_is_owner_or_admin(caller_id)?;
+
+    service::reopen_step_phase(caller_id, project_id, step_phase_id, reopen)
+}
+
+/// Resets a submitted step phase back to open so the project team can make corrections. The
+/// grades already given on the phase are discarded.
+///
+/// # Arguments
+///
+/// * `project_id` - The ID of the project.
+/// * `step_phase_id` - The ID of the step phase.
+/// * `reopen` - The optional new open end date and the reason for the reset.
+///
+/// # Returns
+///
+/// * `Result<StepPhase, APIError>` - The reset step phase or an error.
+#[ic_cdk::update(name = "resetStepPhaseToOpen")]
+pub fn reset_step_phase_to_open(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    reopen: StepPhaseReopen,
+) -> Result<StepPhase, APIError> {
+    let caller_id = ic_cdk::caller();
+    check_is_owner_or_admin(caller_id)?;
+
+    service::reset_step_phase_to_open(caller_id, project_id, step_phase_id, reopen)
+}
+
+/// Retrieves the admin actions (deadline extensions, reopenings, resets and shifts after a
+/// suspension) recorded on a step phase.
+///
+/// # Arguments
+///
+/// * `project_id` - The ID of the project.
+/// * `step_phase_id` - The ID of the step phase.
+///
+/// # Returns
+///
+/// * `Result<Vec<StepPhaseAdminAction>, APIError>` - The recorded admin actions, oldest first.
+#[ic_cdk::query(name = "getStepPhaseAdminActions")]
+pub fn get_step_phase_admin_actions(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+) -> Result<Vec<StepPhaseAdminAction>, APIError> {
+    Ok(service::get_step_phase_admin_actions(
+        project_id,
+        step_phase_id,
+    ))
+}
+
 /// Updates a specific step in a step phase for a given project.
 ///
 /// # Arguments
@@ -257,3 +354,115 @@ pub async fn generate_upload_url(
     )
     .await
 }
+
+/// Generates short-lived URLs to download the uploaded documents of a step. Only the project
+/// team, its assigned reviewers and the admins can download the documents, every URL issued is
+/// recorded in the access log of the project.
+///
+/// # Arguments
+/// * `project_id` - The ID of the project.
+/// * `step_phase_id` - The ID of the step phase.
+/// * `step_id` - The ID of the step.
+///
+/// # Returns
+/// * `Result<Vec<DownloadUrlResponse>, APIError>` - The download URLs or an error.
+#[ic_cdk::update(name = "generateDownloadUrls")]
+pub async fn generate_download_urls(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    step_id: StepId,
+) -> Result<Vec<DownloadUrlResponse>, APIError> {
+    let caller_id = ic_cdk::caller();
+    check_is_project_owner_reviewer_or_admin(caller_id, project_id)?;
+
+    service::generate_download_urls(caller_id, project_id, step_phase_id, step_id).await
+}
+
+/// Confirms the upload of a document with a generated URL. The canister reads the object back
+/// from the storage with a HEAD request: the file is recorded as uploaded, or as rejected when
+/// the stored object or the limits of its document type don't match. A phase can only be
+/// submitted once all its documents are uploaded. Only pending or rejected uploads can be
+/// confirmed.
+///
+/// The stored size is always checked. The SHA-256 is checked when the storage reports it: S3
+/// returns it for objects uploaded with a SHA-256 checksum. Uploads to the `MockHttp`
+/// development server are not checked.
+///
+/// # Arguments
+/// * `project_id` - The ID of the project.
+/// * `step_phase_id` - The ID of the step phase.
+/// * `step_id` - The ID of the step.
+/// * `upload_confirmation` - The document type, size, SHA-256 and MIME type of the file.
+///
+/// # Returns
+/// * `Result<UploadFile, APIError>` - The document with its upload status or an error.
+#[ic_cdk::update(name = "confirmUpload")]
+pub async fn confirm_upload(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    step_id: StepId,
+    upload_confirmation: UploadConfirmation,
+) -> Result<UploadFile, APIError> {
+    let caller_id = ic_cdk::caller();
+    check_is_project_owner_or_admin(caller_id, project_id)?;
+
+    service::confirm_upload(
+        caller_id,
+        project_id,
+        step_phase_id,
+        step_id,
+        upload_confirmation,
+    )
+    .await
+}
+
+/// Deletes a document of a step with all its versions, while the step phase is open.
+///
+/// # Arguments
+/// * `project_id` - The ID of the project.
+/// * `step_phase_id` - The ID of the step phase.
+/// * `step_id` - The ID of the step.
+/// * `document_id` - The ID of the document.
+///
+/// # Returns
+/// * `Result<UploadFile, APIError>` - The deleted document or an error.
+#[ic_cdk::update(name = "deleteUploadFile")]
+pub fn delete_upload_file(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    step_id: StepId,
+    document_id: DocumentId,
+) -> Result<UploadFile, APIError> {
+    let caller_id = ic_cdk::caller();
+    check_is_project_owner_or_admin(caller_id, project_id)?;
+
+    service::delete_upload_file(caller_id, project_id, step_phase_id, step_id, document_id)
+}
+
+#[ic_cdk::update]
+fn validate_step_phase_deadline_extension(
+    payload: StepPhaseDeadlineExtensionPayload,
+) -> Result<String, String> {
+    let caller_id = ic_cdk::caller();
+
+    match check_is_owner_or_governance_id(caller_id) {
+        Ok(_) => service::validate_step_phase_deadline_extension(payload),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+#[ic_cdk::update]
+fn execute_step_phase_deadline_extension(payload: StepPhaseDeadlineExtensionPayload) {
+    let caller_id = ic_cdk::caller();
+
+    match check_is_owner_or_governance_id(caller_id) {
+        Ok(_) => {
+            if let Err(err) = service::execute_step_phase_deadline_extension(caller_id, payload) {
+                ic_cdk::println!("Unable to execute deadline extension: {}", err.to_string());
+            }
+        }
+        Err(err) => {
+            ic_cdk::println!("Unable to execute deadline extension: {}", err.to_string());
+        }
+    }
+}
@@ -1,27 +1,33 @@
-use ic_cdk::api::{
-    self,
-    management_canister::http_request::{
-        http_request, CanisterHttpRequestArgument, HttpHeader, HttpMethod, HttpResponse,
-        TransformArgs, TransformContext,
-    },
-};
-use serde_json::json;
+use ic_cdk::api;
 use std::collections::{HashSet, VecDeque};
 
 use crate::{
     domains::{
+        audit::{
+            self,
+            types::{AuditAction, AuditTarget},
+        },
         icvc_configuration::{
             self,
+            constants::MAX_ADMIN_REASON_BYTES,
             types::{CheckBoxConfig, DecimalValueConfig, QuestionConfig, StepConfig},
         },
         sns_integration,
+        storage::{
+            self,
+            constants::DOWNLOAD_URL_DURATION,
+            types::{S3Method, StorageProviderKind},
+        },
     },
-    repository, APIError, AssessmentMethod, CheckBoxSubmission, Context, DecimalSubmission,
-    DocumentType, MultipleUploadUrlResponse, ProjectId, ProposalData, QuestionSubmission, S3Method,
-    Step, StepCreate, StepGrade, StepGradeResult, StepId, StepPhase, StepPhaseCreate,
+    repository, APIError, AssessmentMethod, CheckBoxSubmission, DecimalSubmission, DocumentId,
+    DocumentTypeId, DownloadUrlResponse, Project, ProjectId, ProjectStatus, ProposalData,
+    QuestionSubmission, Step, StepCreate, StepGrade, StepGradeResult, StepId, StepPhase,
+    StepPhaseAdminAction, StepPhaseAdminActionCreate, StepPhaseAdminActionKind, StepPhaseCreate,
+    StepPhaseDeadline, StepPhaseDeadlineExtension, StepPhaseDeadlineExtensionPayload,
     StepPhaseGradeResult, StepPhaseGradeResultCreate, StepPhaseId, StepPhaseProposal,
-    StepPhaseStatus, StepPhaseUpdate, StepPhaseVoteResult, StepPhaseVoteResultCreate, StepUpdate,
-    UploadFile, UploadPreSignedUrlRequest, UploadUrlRequest, UploadUrlResponse, UserId,
+    StepPhaseReopen, StepPhaseStatus, StepPhaseUpdate, StepPhaseVoteResult,
+    StepPhaseVoteResultCreate, StepUpdate, UploadConfirmation, UploadFile, UploadFileVersion,
+    UploadStatus, UploadUrlRequest, UploadUrlResponse, UserId,
 };
 
 pub fn create_step_phase(
@@ -66,6 +72,8 @@ pub fn update_step(
     step_id: StepId,
     step_update: StepUpdate,
 ) -> Result<Step, APIError> {
+    check_project_is_not_suspended(project_id)?;
+
     let step_phase =
         repository::get_step_phase_by_id(project_id, step_phase_id).ok_or_else(|| {
             APIError::NotFound(format!(
@@ -103,6 +111,13 @@ pub fn update_step(
         )));
     }
 
+    if step_update.upload_files.is_some() {
+        return Err(APIError::BadRequest(
+            "Uploaded files can't be edited, use generateUploadUrl and confirmUpload instead."
+                .to_string(),
+        ));
+    }
+
     if let Some(question_submissions) = &step_update.questions_submission {
         check_submission_lengths(
             step_config.questions.len(),
@@ -135,14 +150,31 @@ pub fn update_step(
         check_valid_numeric_ids(decimal_submissions, &step_config.decimal_values)?;
     }
 
-    repository::update_step(caller_id, project_id, step_phase_id, step_id, step_update).ok_or_else(
-        || {
+    let updated_parts = [
+        ("questions", step_update.questions_submission.is_some()),
+        ("checkboxes", step_update.checkbox_submission.is_some()),
+        ("numeric values", step_update.numeric_submission.is_some()),
+    ]
+    .into_iter()
+    .filter_map(|(part, updated)| updated.then_some(part))
+    .collect::<Vec<_>>();
+
+    let step = repository::update_step(caller_id, project_id, step_phase_id, step_id, step_update)
+        .ok_or_else(|| {
             APIError::NotFound(format!(
                 "Unable to update step with id: {} for project id: {}.",
                 step_id, project_id
             ))
-        },
-    )
+        })?;
+    audit::service::record(
+        caller_id,
+        AuditAction::StepUpdated,
+        AuditTarget::step(project_id, step_phase_id, step_id),
+        None,
+        Some(format!("updated: {}", updated_parts.join(", "))),
+    );
+
+    Ok(step)
 }
 
 pub async fn submit_step_phase(
@@ -150,6 +182,7 @@ pub async fn submit_step_phase(
     step_phase_id: StepPhaseId,
 ) -> Result<StepPhase, APIError> {
     let current_time = ic_cdk::api::time();
+    check_project_is_not_suspended(project_id)?;
 
     let step_phase = repository::get_step_phase_by_id(project_id, step_phase_id).ok_or(
         APIError::NotFound(format!(
@@ -172,54 +205,16 @@ pub async fn submit_step_phase(
         )));
     }
 
-    if let Some(_) = repository::get_step_phase_config_by_id(step_phase_id) {
-        let get_assessment_duration = icvc_configuration::service::get_assessment_duration();
-        let end_assessment_date_ns = current_time + get_assessment_duration * 1_000_000_000;
+    check_step_phase_uploads(project_id, step_phase_id)?;
 
-        let mut step_phase_update = StepPhaseUpdate {
-            status: Some(StepPhaseStatus::Submitted),
+    if let Some(_) = repository::get_step_phase_config_by_id(step_phase_id) {
+        let step_phase_update = StepPhaseUpdate {
             end_open_date: Some(current_time),
             submit_date: Some(current_time),
-            start_assessment_date: Some(current_time),
-            end_assessment_date: Some(end_assessment_date_ns),
             ..Default::default()
         };
 
-        if step_phase.assessment_method == AssessmentMethod::Vote {
-            match sns_integration::service::submit_project_vote_proposal(project_id, step_phase_id)
-                .await
-            {
-                Ok(proposal_id) => {
-                    match sns_integration::service::get_sns_proposal_by_id(proposal_id).await {
-                        Ok(proposal) => {
-                            let voting_end_time = calculate_voting_end_time(&proposal);
-                            step_phase_update.end_assessment_date = Some(voting_end_time);
-                        }
-                        Err(e) => {
-                            ic_cdk::println!(
-                                "Proposal was submitted but couldn't update the end time: {}",
-                                e
-                            );
-                        }
-                    }
-                }
-                Err(e) => {
-                    return Err(APIError::InternalServerError(format!(
-                        "Failed to submit project vote proposal: {}",
-                        e
-                    )));
-                }
-            }
-        }
-
-        repository::update_step_phase(project_id, step_phase_id, step_phase_update).ok_or_else(
-            || {
-                APIError::NotFound(format!(
-                    "Unable to submit step phase with id: {} for project id: {}, step phase not found!",
-                    step_phase_id, project_id
-                ))
-            },
-        )
+        start_step_phase_assessment(&step_phase, step_phase_update).await
     } else {
         Err(APIError::NotFound(format!(
             "Unable to submit Step phase with id: {} for project id: {}, step phase config not found!",
@@ -228,6 +223,96 @@ pub async fn submit_step_phase(
     }
 }
 
+/// Moves a step phase to `Submitted` and opens a new assessment period, submitting an SNS
+/// proposal when the phase is assessed by vote.
+pub async fn start_step_phase_assessment(
+    step_phase: &StepPhase,
+    mut step_phase_update: StepPhaseUpdate,
+) -> Result<StepPhase, APIError> {
+    let project_id = step_phase.project_id;
+    let step_phase_id = step_phase.id;
+    let current_time = ic_cdk::api::time();
+
+    let get_assessment_duration = icvc_configuration::service::get_assessment_duration();
+    let end_assessment_date_ns = current_time + get_assessment_duration * 1_000_000_000;
+
+    step_phase_update.status = Some(StepPhaseStatus::Submitted);
+    step_phase_update.start_assessment_date = Some(current_time);
+    step_phase_update.end_assessment_date = Some(end_assessment_date_ns);
+
+    // The phase is submitted before making the proposal, so the SNS validator sees it in the
+    // state it is voted on.
+    let Some(submitted_step_phase) =
+        repository::update_step_phase(project_id, step_phase_id, step_phase_update)
+    else {
+        return Err(APIError::NotFound(format!(
+            "Unable to submit step phase with id: {} for project id: {}, step phase not found!",
+            step_phase_id, project_id
+        )));
+    };
+    audit::service::record(
+        ic_cdk::caller(),
+        AuditAction::StepPhaseSubmitted,
+        AuditTarget::step_phase(project_id, step_phase_id),
+        Some(format!("status: {:?}", step_phase.status)),
+        Some(format!(
+            "status: {:?}, assessment method: {:?}, end of assessment: {}",
+            submitted_step_phase.status,
+            submitted_step_phase.assessment_method,
+            submitted_step_phase.end_assessment_date
+        )),
+    );
+
+    if step_phase.assessment_method != AssessmentMethod::Vote {
+        return Ok(submitted_step_phase);
+    }
+
+    match sns_integration::service::submit_project_vote_proposal(project_id, step_phase_id).await {
+        Ok(proposal_id) => Ok(sns_integration::service::sync_voting_end_time(
+            project_id,
+            step_phase_id,
+            proposal_id,
+        )
+        .await
+        .unwrap_or(submitted_step_phase)),
+        Err(e) => {
+            // The submission is kept, the proposal is retried by the timer.
+            sns_integration::service::queue_pending_proposal(
+                project_id,
+                step_phase_id,
+                None,
+                e.to_string(),
+            );
+            let pending_step_phase = repository::update_step_phase(
+                project_id,
+                step_phase_id,
+                StepPhaseUpdate {
+                    status: Some(StepPhaseStatus::PendingProposal),
+                    ..Default::default()
+                },
+            )
+            .ok_or_else(|| {
+                APIError::NotFound(format!(
+                    "Step phase with id: {} for project id: {}, not found.",
+                    step_phase_id, project_id
+                ))
+            })?;
+            audit::service::record(
+                ic_cdk::caller(),
+                AuditAction::StepPhaseStatusChanged,
+                AuditTarget::step_phase(project_id, step_phase_id),
+                Some(format!("status: {:?}", submitted_step_phase.status)),
+                Some(format!(
+                    "status: {:?}, proposal error: {}",
+                    pending_step_phase.status, e
+                )),
+            );
+
+            Ok(pending_step_phase)
+        }
+    }
+}
+
 pub fn get_project_step_by_id(
     project_id: ProjectId,
     step_phase_id: StepPhaseId,
@@ -258,11 +343,13 @@ pub fn submit_step_grade(
     step_id: StepId,
     grade: u32,
 ) -> Result<u32, APIError> {
-    repository::retrieve_project_by_user_id_and_project_id(caller_id, project_id).ok_or(
-        APIError::BadRequest(
+    check_project_is_not_suspended(project_id)?;
+
+    if repository::retrieve_project_by_user_id_and_project_id(caller_id, project_id).is_some() {
+        return Err(APIError::BadRequest(
             "As the owner of the project, you are not allowed to vote on it.".to_string(),
-        ),
-    )?;
+        ));
+    }
 
     let step_phase = repository::get_step_phase_by_id(project_id, step_phase_id).ok_or(
         APIError::NotFound(format!(
@@ -302,12 +389,23 @@ pub fn submit_step_grade(
         )));
     };
 
-    repository::put_step_grade(caller_id, project_id, step_phase_id, step_id, grade).ok_or(
-        APIError::InternalServerError(format!(
+    let previous_grade =
+        repository::get_step_grade_by_id(caller_id, project_id, step_phase_id, step_id);
+
+    let grade = repository::put_step_grade(caller_id, project_id, step_phase_id, step_id, grade)
+        .ok_or(APIError::InternalServerError(format!(
             "Unable to update step grade for step_id: {} in project_id: {}, it doesn't exist.",
             step_id, project_id
-        )),
-    )
+        )))?;
+    audit::service::record(
+        caller_id,
+        AuditAction::StepGraded,
+        AuditTarget::step(project_id, step_phase_id, step_id),
+        previous_grade.map(|step_grade| format!("grade: {}", step_grade.grade)),
+        Some(format!("grade: {}", grade)),
+    );
+
+    Ok(grade)
 }
 
 pub fn get_step_grade_by_id(
@@ -487,6 +585,432 @@ pub fn get_vote_result_by_step_phase_id(
     }
 }
 
+//Admin actions
+pub fn extend_step_phase_deadline(
+    caller_id: UserId,
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    extension: StepPhaseDeadlineExtension,
+) -> Result<StepPhase, APIError> {
+    let (step_phase, action, previous_end_date, step_phase_update) =
+        check_step_phase_deadline_extension(project_id, step_phase_id, &extension)?;
+
+    let updated_step_phase =
+        repository::update_step_phase(project_id, step_phase_id, step_phase_update).ok_or_else(
+            || {
+                APIError::NotFound(format!(
+                    "Unable to update step phase with id: {} for project id: {}.",
+                    step_phase_id, project_id
+                ))
+            },
+        )?;
+
+    audit::service::record(
+        caller_id,
+        AuditAction::StepPhaseDeadlineExtended,
+        AuditTarget::step_phase(project_id, step_phase_id),
+        Some(format!("{}: {}", action, previous_end_date)),
+        Some(format!(
+            "{}: {}, reason: {}",
+            action, extension.new_end_date, extension.reason
+        )),
+    );
+    repository::insert_step_phase_admin_action(
+        project_id,
+        step_phase_id,
+        StepPhaseAdminActionCreate {
+            action,
+            reason: extension.reason,
+            previous_status: step_phase.status.clone(),
+            new_status: updated_step_phase.status.clone(),
+            previous_end_date,
+            new_end_date: extension.new_end_date,
+            performed_by: caller_id,
+        },
+    );
+
+    Ok(updated_step_phase)
+}
+
+/// Delays the deadlines of a phase by the time its project was suspended, so the suspension
+/// doesn't shorten the open or assessment window. Deadlines that passed before the suspension
+/// and the voting period of an SNS proposal are kept.
+pub fn shift_step_phase_deadlines(
+    caller_id: UserId,
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    suspended_at: u64,
+    reason: String,
+) -> Option<StepPhase> {
+    let step_phase = repository::get_step_phase_by_id(project_id, step_phase_id)?;
+    let shift = api::time().saturating_sub(suspended_at);
+
+    let (previous_end_date, step_phase_update) = match step_phase.status {
+        StepPhaseStatus::Open if step_phase.end_open_date > suspended_at => (
+            step_phase.end_open_date,
+            StepPhaseUpdate {
+                end_open_date: Some(step_phase.end_open_date + shift),
+                start_assessment_date: Some(step_phase.start_assessment_date + shift),
+                end_assessment_date: Some(step_phase.end_assessment_date + shift),
+                ..Default::default()
+            },
+        ),
+        StepPhaseStatus::Submitted
+            if step_phase.assessment_method != AssessmentMethod::Vote
+                && step_phase.end_assessment_date > suspended_at =>
+        {
+            (
+                step_phase.end_assessment_date,
+                StepPhaseUpdate {
+                    end_assessment_date: Some(step_phase.end_assessment_date + shift),
+                    ..Default::default()
+                },
+            )
+        }
+        _ => return None,
+    };
+
+    let updated_step_phase =
+        repository::update_step_phase(project_id, step_phase_id, step_phase_update)?;
+    let action = StepPhaseAdminActionKind::ShiftForSuspension;
+    let new_end_date = previous_end_date + shift;
+
+    audit::service::record(
+        caller_id,
+        AuditAction::StepPhaseDeadlineExtended,
+        AuditTarget::step_phase(project_id, step_phase_id),
+        Some(format!("{}: {}", action, previous_end_date)),
+        Some(format!("{}: {}, reason: {}", action, new_end_date, reason)),
+    );
+    repository::insert_step_phase_admin_action(
+        project_id,
+        step_phase_id,
+        StepPhaseAdminActionCreate {
+            action,
+            reason,
+            previous_status: step_phase.status,
+            new_status: updated_step_phase.status.clone(),
+            previous_end_date,
+            new_end_date,
+            performed_by: caller_id,
+        },
+    );
+
+    Some(updated_step_phase)
+}
+
+pub fn validate_step_phase_deadline_extension(
+    payload: StepPhaseDeadlineExtensionPayload,
+) -> Result<String, String> {
+    let extension = &payload.extension;
+
+    match check_step_phase_deadline_extension(payload.project_id, payload.phase_id, extension) {
+        Ok((_, action, previous_end_date, _)) => Ok(format!(
+            "{} of phase {} of project id {} from {} to {} (nanoseconds since epoch).\n\nReason: {}",
+            action,
+            payload.phase_id,
+            payload.project_id,
+            previous_end_date,
+            extension.new_end_date,
+            extension.reason
+        )),
+        Err(e) => Err(format!("Unable to validate deadline extension: {}", e)),
+    }
+}
+
+pub fn execute_step_phase_deadline_extension(
+    caller_id: UserId,
+    payload: StepPhaseDeadlineExtensionPayload,
+) -> Result<StepPhase, APIError> {
+    extend_step_phase_deadline(
+        caller_id,
+        payload.project_id,
+        payload.phase_id,
+        payload.extension,
+    )
+}
+
+/// Checks that a deadline of the current phase of a project can be extended. Returns the phase,
+/// the recorded action, the current end date and the update to apply.
+fn check_step_phase_deadline_extension(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    extension: &StepPhaseDeadlineExtension,
+) -> Result<(StepPhase, StepPhaseAdminActionKind, u64, StepPhaseUpdate), APIError> {
+    check_admin_reason(&extension.reason)?;
+    let (project, step_phase) = get_current_project_step_phase(project_id, step_phase_id)?;
+
+    if project.status != ProjectStatus::Open {
+        return Err(APIError::BadRequest(format!(
+            "Deadline for project id: {} can't be extended since the project status is: {} and should be Open.",
+            project_id, project.status
+        )));
+    }
+
+    let current_time = api::time();
+    let (action, previous_end_date, step_phase_update) = match extension.deadline {
+        StepPhaseDeadline::Open => {
+            if step_phase.status != StepPhaseStatus::Open {
+                return Err(APIError::BadRequest(format!(
+                    "Open deadline of step phase with id: {} for project id: {} can't be extended since the step phase status is: {} and should be Open.",
+                    step_phase_id, project_id, step_phase.status
+                )));
+            }
+
+            // Keep the assessment window length by shifting it along with the open deadline.
+            let delta = extension
+                .new_end_date
+                .saturating_sub(step_phase.end_open_date);
+
+            (
+                StepPhaseAdminActionKind::ExtendOpenDeadline,
+                step_phase.end_open_date,
+                StepPhaseUpdate {
+                    end_open_date: Some(extension.new_end_date),
+                    start_assessment_date: Some(step_phase.start_assessment_date + delta),
+                    end_assessment_date: Some(step_phase.end_assessment_date + delta),
+                    ..Default::default()
+                },
+            )
+        }
+        StepPhaseDeadline::Assessment => {
+            if step_phase.status != StepPhaseStatus::Submitted {
+                return Err(APIError::BadRequest(format!(
+                    "Assessment deadline of step phase with id: {} for project id: {} can't be extended since the step phase status is: {} and should be Submitted.",
+                    step_phase_id, project_id, step_phase.status
+                )));
+            }
+
+            if step_phase.assessment_method == AssessmentMethod::Vote {
+                return Err(APIError::BadRequest(format!(
+                    "Assessment deadline of step phase with id: {} for project id: {} is defined by the SNS proposal and can't be extended.",
+                    step_phase_id, project_id
+                )));
+            }
+
+            (
+                StepPhaseAdminActionKind::ExtendAssessmentDeadline,
+                step_phase.end_assessment_date,
+                StepPhaseUpdate {
+                    end_assessment_date: Some(extension.new_end_date),
+                    ..Default::default()
+                },
+            )
+        }
+    };
+
+    if extension.new_end_date <= previous_end_date || extension.new_end_date <= current_time {
+        return Err(APIError::BadRequest(format!(
+            "The new end date: {} must be later than the current end date: {} and the current time: {}.",
+            extension.new_end_date, previous_end_date, current_time
+        )));
+    }
+
+    Ok((step_phase, action, previous_end_date, step_phase_update))
+}
+
+pub fn reopen_step_phase(
+    caller_id: UserId,
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    reopen: StepPhaseReopen,
+) -> Result<StepPhase, APIError> {
+    check_admin_reason(&reopen.reason)?;
+    let (project, step_phase) = get_current_project_step_phase(project_id, step_phase_id)?;
+
+    if step_phase.status != StepPhaseStatus::NotSubmitted
+        || project.status != ProjectStatus::NotSubmitted
+    {
+        return Err(APIError::BadRequest(format!(
+            "Step phase with id: {} for project id: {} can't be reopened since the step phase status is: {} and the project status is: {}, both should be Not Submitted.",
+            step_phase_id, project_id, step_phase.status, project.status
+        )));
+    }
+
+    let updated_step_phase = reset_step_phase_open_period(
+        caller_id,
+        &step_phase,
+        StepPhaseAdminActionKind::Reopen,
+        reopen,
+    )?;
+
+    repository::update_project_status(project.user_id, project_id, ProjectStatus::Open);
+
+    Ok(updated_step_phase)
+}
+
+pub fn reset_step_phase_to_open(
+    caller_id: UserId,
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    reopen: StepPhaseReopen,
+) -> Result<StepPhase, APIError> {
+    check_admin_reason(&reopen.reason)?;
+    let (project, step_phase) = get_current_project_step_phase(project_id, step_phase_id)?;
+
+    if step_phase.status != StepPhaseStatus::Submitted || project.status != ProjectStatus::Open {
+        return Err(APIError::BadRequest(format!(
+            "Step phase with id: {} for project id: {} can't be reset since the step phase status is: {} and should be Submitted on an Open project.",
+            step_phase_id, project_id, step_phase.status
+        )));
+    }
+
+    if step_phase.assessment_method == AssessmentMethod::Vote
+        && repository::get_proposal_by_step_phase_id(project_id, step_phase_id).is_some()
+    {
+        return Err(APIError::BadRequest(format!(
+            "Step phase with id: {} for project id: {} can't be reset since it already has an SNS proposal.",
+            step_phase_id, project_id
+        )));
+    }
+
+    let updated_step_phase = reset_step_phase_open_period(
+        caller_id,
+        &step_phase,
+        StepPhaseAdminActionKind::ResetToOpen,
+        reopen,
+    )?;
+
+    // The grades were given to the content being corrected, the phase is graded again once
+    // it is submitted.
+    repository::remove_step_phase_grades(project_id, step_phase_id);
+
+    Ok(updated_step_phase)
+}
+
+pub fn get_step_phase_admin_actions(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+) -> Vec<StepPhaseAdminAction> {
+    repository::get_step_phase_admin_actions(project_id, step_phase_id)
+}
+
+fn reset_step_phase_open_period(
+    caller_id: UserId,
+    step_phase: &StepPhase,
+    action: StepPhaseAdminActionKind,
+    reopen: StepPhaseReopen,
+) -> Result<StepPhase, APIError> {
+    let project_id = step_phase.project_id;
+    let step_phase_id = step_phase.id;
+    let current_time = api::time();
+
+    let end_open_date = reopen
+        .end_open_date
+        .unwrap_or(current_time + icvc_configuration::service::get_open_duration() * 1_000_000_000);
+
+    if end_open_date <= current_time {
+        return Err(APIError::BadRequest(format!(
+            "The new open end date: {} must be later than the current time: {}.",
+            end_open_date, current_time
+        )));
+    }
+
+    // Keep the original assessment window length after the new open period.
+    let assessment_window = step_phase
+        .end_assessment_date
+        .saturating_sub(step_phase.start_assessment_date);
+
+    let updated_step_phase = repository::reopen_step_phase(
+        project_id,
+        step_phase_id,
+        end_open_date,
+        end_open_date,
+        end_open_date + assessment_window,
+    )
+    .ok_or_else(|| {
+        APIError::NotFound(format!(
+            "Unable to reopen step phase with id: {} for project id: {}.",
+            step_phase_id, project_id
+        ))
+    })?;
+
+    audit::service::record(
+        caller_id,
+        if action == StepPhaseAdminActionKind::Reopen {
+            AuditAction::StepPhaseReopened
+        } else {
+            AuditAction::StepPhaseResetToOpen
+        },
+        AuditTarget::step_phase(project_id, step_phase_id),
+        Some(format!(
+            "status: {:?}, end of open period: {}",
+            step_phase.status, step_phase.end_open_date
+        )),
+        Some(format!(
+            "status: {:?}, end of open period: {}, reason: {}",
+            updated_step_phase.status, end_open_date, reopen.reason
+        )),
+    );
+    repository::insert_step_phase_admin_action(
+        project_id,
+        step_phase_id,
+        StepPhaseAdminActionCreate {
+            action,
+            reason: reopen.reason,
+            previous_status: step_phase.status.clone(),
+            new_status: updated_step_phase.status.clone(),
+            previous_end_date: step_phase.end_open_date,
+            new_end_date: end_open_date,
+            performed_by: caller_id,
+        },
+    );
+
+    Ok(updated_step_phase)
+}
+
+fn get_current_project_step_phase(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+) -> Result<(Project, StepPhase), APIError> {
+    let project = repository::retrieve_project_by_id(project_id)
+        .ok_or_else(|| APIError::NotFound(format!("Project with id {} not found.", project_id)))?;
+
+    let step_phase =
+        repository::get_step_phase_by_id(project_id, step_phase_id).ok_or_else(|| {
+            APIError::NotFound(format!(
+                "Step phase with id: {} for project id: {}, not found.",
+                step_phase_id, project_id
+            ))
+        })?;
+
+    if project.current_phase != step_phase_id {
+        return Err(APIError::BadRequest(format!(
+            "Step phase with id: {} is not the current phase of project id: {}.",
+            step_phase_id, project_id
+        )));
+    }
+
+    Ok((project, step_phase))
+}
+
+fn check_project_is_not_suspended(project_id: ProjectId) -> Result<(), APIError> {
+    match repository::retrieve_project_by_id(project_id) {
+        Some(project) if project.status == ProjectStatus::Suspended => Err(APIError::BadRequest(
+            format!("Project with id {} is suspended.", project_id),
+        )),
+        _ => Ok(()),
+    }
+}
+
+pub fn check_admin_reason(reason: &str) -> Result<(), APIError> {
+    if reason.trim().is_empty() {
+        return Err(APIError::BadRequest(
+            "A reason is required for this action.".to_string(),
+        ));
+    }
+
+    if reason.len() > MAX_ADMIN_REASON_BYTES {
+        return Err(APIError::BadRequest(format!(
+            "Reason length: {}. Max allowed length: {}.",
+            reason.len(),
+            MAX_ADMIN_REASON_BYTES
+        )));
+    }
+
+    Ok(())
+}
+
 //Private methods
 fn initialize_step_phase(
     project_id: ProjectId,
@@ -576,11 +1100,7 @@ fn initialize_step(step_config: &StepConfig) -> StepCreate {
     upload_files = step_config
         .required_upload_files
         .iter()
-        .map(|document_type| UploadFile {
-            filename: None,
-            document_type: document_type.clone(),
-            s3_key: None,
-        })
+        .map(|document_type| empty_upload_file(*document_type))
         .collect();
 
     StepCreate {
@@ -747,13 +1267,14 @@ fn check_numeric_values(submissions: &[DecimalSubmission]) -> Result<(), APIErro
     Ok(())
 }
 
-pub async fn generate_upload_urls(
-    caller_id: UserId,
+/// Checks that the documents can be uploaded to the step: its phase must be open and the
+/// document types required by the step config.
+pub fn check_step_accepts_uploads(
     project_id: ProjectId,
-    step_phase_id: u64,
+    step_phase_id: StepPhaseId,
     step_id: StepId,
-    upload_req_list: Vec<UploadUrlRequest>,
-) -> Result<Vec<UploadUrlResponse>, APIError> {
+    document_types: &[DocumentTypeId],
+) -> Result<(), APIError> {
     let step_phase =
         repository::get_step_phase_by_id(project_id, step_phase_id).ok_or_else(|| {
             APIError::NotFound(format!(
@@ -791,205 +1312,741 @@ pub async fn generate_upload_urls(
         )));
     }
 
-    let required_document_types: Vec<DocumentType> = step_config.required_upload_files.clone();
-    let requested_document_types: Vec<DocumentType> = upload_req_list
-        .iter()
-        .map(|req| req.document_type.clone())
+    for document_type in document_types {
+        if !step_config.required_upload_files.contains(document_type) {
+            return Err(APIError::BadRequest(format!(
+                "The requested document type: {:?} is not required for step id: {} in step phase id: {}",
+                document_type, step_id, step_phase_id
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn generate_upload_urls(
+    caller_id: UserId,
+    project_id: ProjectId,
+    step_phase_id: u64,
+    step_id: StepId,
+    upload_req_list: Vec<UploadUrlRequest>,
+) -> Result<Vec<UploadUrlResponse>, APIError> {
+    let requested_document_types: Vec<DocumentTypeId> = upload_req_list
+        .iter()
+        .map(|req| req.document_type)
         .collect();
 
-    for req_doc_type in requested_document_types {
-        if !required_document_types.contains(&req_doc_type) {
+    check_step_accepts_uploads(
+        project_id,
+        step_phase_id,
+        step_id,
+        &requested_document_types,
+    )?;
+
+    let mut requested_document_ids = HashSet::new();
+    for document_id in upload_req_list.iter().filter_map(|req| req.document_id) {
+        if !requested_document_ids.insert(document_id) {
             return Err(APIError::BadRequest(format!(
-                "The requested document type: {:?} is not required for step id: {} in step phase id: {}",
-                req_doc_type, step_id, step_phase_id
+                "The document with id: {} can only be requested once.",
+                document_id
             )));
         }
     }
 
-    let bucket = "icvc-s3-uploads".to_string();
-    let mut keys = Vec::new();
-    let mut upload_files = Vec::new();
-    let mut responses = Vec::new();
+    let mut upload_files = get_project_step_by_id(project_id, step_phase_id, step_id)?.upload_files;
+    let mut pending_files = Vec::new();
+    let mut replaced_files = Vec::new();
 
     for upload_req in upload_req_list {
-        let key: String = format!(
-            "projects/{}/{}/{}/{}",
+        let (pending_file, mut replaced) = add_pending_upload(
+            &mut upload_files,
             project_id,
             step_phase_id,
             step_id,
-            upload_req.document_type.to_string()
-        );
-        keys.push(key.clone());
-
-        let upload_file = UploadFile {
-            filename: Some(upload_req.filename),
-            document_type: upload_req.document_type,
-            s3_key: Some(key.clone()),
-        };
-        upload_files.push(upload_file);
+            upload_req.document_type,
+            upload_req.document_id,
+            upload_req.filename,
+        )?;
+        pending_files.push(pending_file);
+        replaced_files.append(&mut replaced);
     }
 
-    let upload_pre_signed_request = UploadPreSignedUrlRequest {
-        bucket,
-        keys,
-        method: S3Method::PUT,
+    // The pending uploads are saved before the outcall, so that calls made meanwhile see them.
+    save_upload_files(
+        caller_id,
+        project_id,
+        step_phase_id,
+        step_id,
+        upload_files,
+        &replaced_files,
+    );
+
+    let keys: Vec<String> = pending_files
+        .iter()
+        .filter_map(|file| file.s3_key.clone())
+        .collect();
+    let presigned_urls = match storage::service::presign_urls(keys.clone(), S3Method::PUT).await {
+        Ok(presigned_urls) => presigned_urls,
+        Err(e) => {
+            discard_pending_uploads(caller_id, project_id, step_phase_id, step_id, &keys);
+            return Err(e);
+        }
     };
-    ic_cdk::println!("upload_pre_signed_request: {:?}", upload_pre_signed_request);
-    let multiple_response: MultipleUploadUrlResponse =
-        generate_presigned_urls(upload_pre_signed_request).await?;
 
-    for url_response in multiple_response.urls {
+    for pending_file in &pending_files {
+        audit_upload_request(caller_id, project_id, step_phase_id, step_id, pending_file);
+    }
+
+    let mut responses = Vec::new();
+    for url_response in presigned_urls {
+        if let Some(pending_file) = pending_files
+            .iter()
+            .find(|file| file.s3_key.as_ref() == Some(&url_response.key))
+        {
+            responses.push(UploadUrlResponse {
+                project_id,
+                step_phase_id,
+                step_id,
+                url: url_response.url,
+                document_type: pending_file.document_type,
+                document_id: pending_file.document_id.unwrap_or_default(),
+                version: pending_file.version.unwrap_or_default(),
+            });
+        }
+    }
+
+    Ok(responses)
+}
+
+/// Adds the pending upload of a new document, or of a new version of an existing document, to
+/// a step. Used by the on-chain storage, whose files are uploaded to the canister.
+pub fn create_pending_upload(
+    caller_id: UserId,
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    step_id: StepId,
+    document_type: DocumentTypeId,
+    document_id: Option<DocumentId>,
+    filename: String,
+) -> Result<UploadFile, APIError> {
+    let mut upload_files = get_project_step_by_id(project_id, step_phase_id, step_id)?.upload_files;
+
+    let (pending_file, replaced_files) = add_pending_upload(
+        &mut upload_files,
+        project_id,
+        step_phase_id,
+        step_id,
+        document_type,
+        document_id,
+        filename,
+    )?;
+
+    save_upload_files(
+        caller_id,
+        project_id,
+        step_phase_id,
+        step_id,
+        upload_files,
+        &replaced_files,
+    );
+    audit_upload_request(caller_id, project_id, step_phase_id, step_id, &pending_file);
+
+    Ok(pending_file)
+}
+
+fn audit_upload_request(
+    caller_id: UserId,
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    step_id: StepId,
+    pending_file: &UploadFile,
+) {
+    audit::service::record(
+        caller_id,
+        AuditAction::DocumentUploadRequested,
+        AuditTarget::step(project_id, step_phase_id, step_id)
+            .with_entity(pending_file.document_id.unwrap_or_default()),
+        None,
+        Some(format!(
+            "document type: {}, version: {}, filename: {}",
+            pending_file.document_type,
+            pending_file.version.unwrap_or(1),
+            pending_file.filename.as_deref().unwrap_or_default()
+        )),
+    );
+}
+
+/// Creates short-lived URLs to download the uploaded documents of a step. Every URL issued is
+/// recorded in the access log of the project.
+pub async fn generate_download_urls(
+    caller_id: UserId,
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    step_id: StepId,
+) -> Result<Vec<DownloadUrlResponse>, APIError> {
+    let step = repository::get_step_by_id(project_id, step_phase_id, step_id).ok_or_else(|| {
+        APIError::NotFound(format!(
+            "Step with id: {} for project id: {} and step phase id: {}, not found.",
+            step_id, project_id, step_phase_id
+        ))
+    })?;
+
+    let upload_files: Vec<UploadFile> = step
+        .upload_files
+        .into_iter()
+        .filter(|file| file.status == Some(UploadStatus::Uploaded) && file.s3_key.is_some())
+        .collect();
+
+    if upload_files.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let keys = upload_files
+        .iter()
+        .filter_map(|file| file.s3_key.clone())
+        .collect();
+    let presigned_urls = storage::service::presign_urls(keys, S3Method::GET).await?;
+    let expires_at = api::time() + DOWNLOAD_URL_DURATION * 1_000_000_000;
+
+    let mut responses = Vec::new();
+    for url_response in presigned_urls {
         if let Some(upload_file) = upload_files
-            .iter_mut()
+            .iter()
             .find(|file| file.s3_key.as_ref() == Some(&url_response.key))
         {
-            let response = UploadUrlResponse {
-                project_id: project_id.clone(),
-                step_phase_id: step_phase_id,
-                step_id: step_id.clone(),
+            storage::service::log_document_access(
+                caller_id,
+                project_id,
+                step_phase_id,
+                step_id,
+                upload_file.document_type,
+                url_response.key,
+            );
+
+            responses.push(DownloadUrlResponse {
+                project_id,
+                step_phase_id,
+                step_id,
+                document_type: upload_file.document_type,
+                document_id: upload_file.document_id,
+                version: upload_file.version,
+                filename: upload_file.filename.clone(),
                 url: url_response.url,
-                document_type: upload_file.document_type.clone(),
-            };
+                expires_at,
+            });
+        }
+    }
+
+    Ok(responses)
+}
 
-            responses.push(response);
+/// Records the metadata of a file uploaded with a generated URL, once the storage reports the
+/// same size and SHA-256 for the object. A file that doesn't match the storage or the limits of
+/// its document type is recorded as rejected, with the reason.
+pub async fn confirm_upload(
+    caller_id: UserId,
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    step_id: StepId,
+    upload_confirmation: UploadConfirmation,
+) -> Result<UploadFile, APIError> {
+    let upload_file =
+        get_upload_to_confirm(project_id, step_phase_id, step_id, &upload_confirmation)?;
+    let s3_key = upload_file.s3_key.unwrap_or_default();
+    let stored_object = storage::service::head_object(&s3_key).await?;
+
+    // The step may have changed while the storage answered.
+    let mut upload_file =
+        get_upload_to_confirm(project_id, step_phase_id, step_id, &upload_confirmation)?;
+    if upload_file.s3_key.as_ref() != Some(&s3_key) {
+        return Err(APIError::BadRequest(format!(
+            "The upload of the document type: {:?} of step id: {} was replaced while it was checked, confirm it again.",
+            upload_confirmation.document_type, step_id
+        )));
+    }
+
+    match storage::service::check_file_metadata(
+        upload_confirmation.document_type,
+        upload_confirmation.size,
+        &upload_confirmation.mime_type,
+        &upload_confirmation.sha256,
+    )
+    .and_then(|sha256| {
+        storage::service::check_stored_object(&stored_object, upload_confirmation.size, &sha256)
+            .map(|_| sha256)
+    }) {
+        Ok(sha256) => {
+            upload_file.status = Some(UploadStatus::Uploaded);
+            upload_file.sha256 = Some(sha256);
+            upload_file.rejection_reason = None;
+        }
+        Err(e) => {
+            upload_file.status = Some(UploadStatus::Rejected);
+            upload_file.sha256 = Some(upload_confirmation.sha256);
+            upload_file.rejection_reason = Some(e.to_string());
         }
     }
+    upload_file.size = Some(upload_confirmation.size);
+    upload_file.mime_type = Some(upload_confirmation.mime_type.to_lowercase());
+    upload_file.confirmed_at = Some(api::time());
+
+    let upload_file =
+        record_upload_file(caller_id, project_id, step_phase_id, step_id, upload_file)?;
+    audit::service::record(
+        caller_id,
+        AuditAction::DocumentUploadConfirmed,
+        AuditTarget::step(project_id, step_phase_id, step_id)
+            .with_entity(upload_file.document_id.unwrap_or_default()),
+        None,
+        Some(format!(
+            "version: {}, status: {:?}, size: {}",
+            upload_file.version.unwrap_or(1),
+            upload_file.status,
+            upload_file.size.unwrap_or_default()
+        )),
+    );
 
-    let step_update = StepUpdate {
-        questions_submission: None,
-        checkbox_submission: None,
-        numeric_submission: None,
-        upload_files: Some(upload_files.clone()),
-    };
+    Ok(upload_file)
+}
 
-    repository::update_step(caller_id, project_id, step_phase_id, step_id, step_update);
+/// Latest upload of the document waiting for its confirmation.
+fn get_upload_to_confirm(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    step_id: StepId,
+    upload_confirmation: &UploadConfirmation,
+) -> Result<UploadFile, APIError> {
+    check_step_accepts_uploads(
+        project_id,
+        step_phase_id,
+        step_id,
+        std::slice::from_ref(&upload_confirmation.document_type),
+    )?;
 
-    Ok(responses)
+    if storage::service::get_storage_config().provider == StorageProviderKind::OnChain {
+        return Err(APIError::BadRequest(
+            "Files of the on-chain storage are confirmed by commitFileUpload.".to_string(),
+        ));
+    }
+
+    let step = get_project_step_by_id(project_id, step_phase_id, step_id)?;
+
+    // An uploaded file is never confirmed again, a rejected one can be confirmed once fixed.
+    let candidates: Vec<UploadFile> = step
+        .upload_files
+        .into_iter()
+        .filter(|file| {
+            file.document_type == upload_confirmation.document_type
+                && file.s3_key.is_some()
+                && matches!(
+                    file.status,
+                    Some(UploadStatus::Pending) | Some(UploadStatus::Rejected)
+                )
+                && (upload_confirmation.document_id.is_none()
+                    || file.document_id == upload_confirmation.document_id)
+        })
+        .collect();
+
+    let document_ids: HashSet<Option<DocumentId>> =
+        candidates.iter().map(|file| file.document_id).collect();
+    if document_ids.len() > 1 {
+        return Err(APIError::BadRequest(format!(
+            "Step id: {} has several documents of type: {:?}, the document_id of the upload must be set.",
+            step_id, upload_confirmation.document_type
+        )));
+    }
+
+    // The latest version is the one waiting for its confirmation.
+    candidates
+        .into_iter()
+        .max_by_key(|file| file.version)
+        .ok_or_else(|| {
+            APIError::BadRequest(format!(
+                "No upload of the document type: {:?} of step id: {} is waiting for its confirmation.",
+                upload_confirmation.document_type, step_id
+            ))
+        })
 }
 
-pub async fn generate_presigned_urls(
-    upload_pre_signed_req: UploadPreSignedUrlRequest,
-) -> Result<MultipleUploadUrlResponse, APIError> {
-    let url =
-        "https://ys2m3gt3ngexum4o4w66xcd5z40csxds.lambda-url.eu-central-1.on.aws/batch_generate"
-            .to_string();
+/// Saves the confirmed upload of a document. Once a version is uploaded, the versions it
+/// replaces move to its history and its unconfirmed uploads are removed.
+pub fn record_upload_file(
+    caller_id: UserId,
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    step_id: StepId,
+    mut upload_file: UploadFile,
+) -> Result<UploadFile, APIError> {
+    let step = get_project_step_by_id(project_id, step_phase_id, step_id)?;
+    let uploaded = upload_file.status == Some(UploadStatus::Uploaded);
 
-    let request_headers = vec![
-        HttpHeader {
-            name: "User-Agent".to_string(),
-            value: "ic-canister".to_string(),
-        },
-        HttpHeader {
-            name: "Content-Type".to_string(),
-            value: "application/json".to_string(),
-        },
-    ];
+    let mut upload_files = Vec::new();
+    let mut removed_files = Vec::new();
+    let mut previous_versions = upload_file.previous_versions.take().unwrap_or_default();
 
-    let request_body = json!(upload_pre_signed_req).to_string().into_bytes();
+    for file in step.upload_files {
+        if file.s3_key == upload_file.s3_key {
+            continue;
+        }
 
-    let context = Context {
-        bucket_start_time_index: 0,
-        closing_price_index: 4,
-    };
+        let replaced =
+            uploaded && file.document_id.is_some() && file.document_id == upload_file.document_id;
+        if !replaced {
+            upload_files.push(file);
+        } else if file.status == Some(UploadStatus::Uploaded) {
+            previous_versions.extend(file.previous_versions.unwrap_or_default());
+            previous_versions.push(UploadFileVersion {
+                version: file.version.unwrap_or(1),
+                filename: file.filename,
+                s3_key: file.s3_key,
+                size: file.size,
+                sha256: file.sha256,
+                mime_type: file.mime_type,
+                confirmed_at: file.confirmed_at,
+                replaced_at: api::time(),
+            });
+        } else {
+            removed_files.push(file);
+        }
+    }
+
+    previous_versions.sort_by_key(|version| version.version);
+    upload_file.previous_versions = Some(previous_versions).filter(|versions| !versions.is_empty());
+    upload_files.push(upload_file.clone());
+
+    save_upload_files(
+        caller_id,
+        project_id,
+        step_phase_id,
+        step_id,
+        upload_files,
+        &removed_files,
+    );
+
+    Ok(upload_file)
+}
+
+/// Deletes a document of a step with all its versions.
+pub fn delete_upload_file(
+    caller_id: UserId,
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    step_id: StepId,
+    document_id: DocumentId,
+) -> Result<UploadFile, APIError> {
+    let step = get_project_step_by_id(project_id, step_phase_id, step_id)?;
+
+    let document_type = step
+        .upload_files
+        .iter()
+        .find(|file| file.document_id == Some(document_id))
+        .map(|file| file.document_type)
+        .ok_or_else(|| {
+            APIError::NotFound(format!(
+                "Document with id: {} of step id: {}, not found.",
+                document_id, step_id
+            ))
+        })?;
 
-    let request = CanisterHttpRequestArgument {
-        url,
-        max_response_bytes: None,
-        method: HttpMethod::POST,
-        headers: request_headers,
-        body: Some(request_body),
-        transform: Some(TransformContext::from_name(
-            "transform".to_string(),
-            serde_json::to_vec(&context).unwrap(),
+    check_step_accepts_uploads(
+        project_id,
+        step_phase_id,
+        step_id,
+        std::slice::from_ref(&document_type),
+    )?;
+
+    let (removed_files, mut upload_files): (Vec<UploadFile>, Vec<UploadFile>) = step
+        .upload_files
+        .into_iter()
+        .partition(|file| file.document_id == Some(document_id));
+    restore_placeholders(&mut upload_files, &removed_files);
+
+    let deleted_file = removed_files
+        .iter()
+        .max_by_key(|file| file.version)
+        .cloned()
+        .ok_or_else(|| {
+            APIError::NotFound(format!(
+                "Document with id: {} of step id: {}, not found.",
+                document_id, step_id
+            ))
+        })?;
+
+    save_upload_files(
+        caller_id,
+        project_id,
+        step_phase_id,
+        step_id,
+        upload_files,
+        &removed_files,
+    );
+    audit::service::record(
+        caller_id,
+        AuditAction::DocumentDeleted,
+        AuditTarget::step(project_id, step_phase_id, step_id).with_entity(document_id),
+        Some(format!(
+            "document type: {}, versions: {}",
+            document_type,
+            removed_files.len()
         )),
-    };
+        None,
+    );
 
-    match http_request(request, 1_703_154_400).await {
-        Ok((response,)) => {
-            let str_body = String::from_utf8(response.body).map_err(|e| {
-                APIError::InternalServerError(format!("Response was not valid UTF-8: {}", e))
-            })?;
+    Ok(deleted_file)
+}
 
-            ic_cdk::println!("Reponse: {:?}", str_body.clone().to_string());
+/// Gives an ID to the documents uploaded before a step could hold several files of a type.
+pub fn init_document_ids() {
+    let count = repository::assign_missing_document_ids();
+    if count > 0 {
+        ic_cdk::println!("Assigned an ID to {} documents.", count);
+    }
+}
 
-            if response.status == 403 as u64 {
-                return Err(APIError::Forbidden(
-                    "AccessDeniedException: Ensure your Lambda and API Gateway permissions are correctly configured".to_string(),
-                ));
+/// Adds the pending upload of a new document, or of a new version of an existing one, to the
+/// files of a step. Returns the pending file and the unconfirmed uploads it replaces.
+fn add_pending_upload(
+    upload_files: &mut Vec<UploadFile>,
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    step_id: StepId,
+    document_type: DocumentTypeId,
+    document_id: Option<DocumentId>,
+    filename: String,
+) -> Result<(UploadFile, Vec<UploadFile>), APIError> {
+    let (document_id, version, replaced_files) = match document_id {
+        Some(document_id) => {
+            let versions: Vec<&UploadFile> = upload_files
+                .iter()
+                .filter(|file| file.document_id == Some(document_id))
+                .collect();
+
+            let current_type =
+                versions
+                    .first()
+                    .map(|file| file.document_type)
+                    .ok_or_else(|| {
+                        APIError::NotFound(format!(
+                            "Document with id: {} of step id: {}, not found.",
+                            document_id, step_id
+                        ))
+                    })?;
+            if current_type != document_type {
+                return Err(APIError::BadRequest(format!(
+                    "Document with id: {} has the document type id: {} and not {}.",
+                    document_id, current_type, document_type
+                )));
             }
-
-            if response.status == 502 as u64 {
-                return Err(APIError::InternalServerError(
-                    "Bad Gateway: The server was acting as a gateway or proxy and received an invalid response from the upstream server.".to_string(),
-                ));
+            let version = versions
+                .iter()
+                .filter_map(|file| file.version)
+                .max()
+                .unwrap_or(1)
+                + 1;
+
+            // The uploaded version stays until the new one is confirmed, unconfirmed uploads
+            // of the document are replaced.
+            let (replaced_files, kept_files): (Vec<UploadFile>, Vec<UploadFile>) =
+                std::mem::take(upload_files).into_iter().partition(|file| {
+                    file.document_id == Some(document_id)
+                        && file.status != Some(UploadStatus::Uploaded)
+                });
+            *upload_files = kept_files;
+
+            (document_id, version, replaced_files)
+        }
+        None => {
+            let limits = storage::service::get_document_type(document_type)?;
+            let document_count = upload_files
+                .iter()
+                .filter(|file| file.document_type == document_type)
+                .filter_map(|file| file.document_id)
+                .collect::<HashSet<DocumentId>>()
+                .len();
+
+            if document_count >= limits.max_files as usize {
+                return Err(APIError::BadRequest(format!(
+                    "Step id: {} already has {} documents of type: {}, the maximum allowed.",
+                    step_id, document_count, limits.name
+                )));
             }
 
-            let multiple_upload_url_response: MultipleUploadUrlResponse =
-                serde_json::from_str(&str_body).map_err(|e| {
-                    APIError::InternalServerError(format!("Failed to parse response: {}", e))
-                })?;
-
-            ic_cdk::println!(
-                "multiple_upload_url_response: {:?}",
-                multiple_upload_url_response.clone()
-            );
+            // The placeholder of a required document is replaced by its first file.
+            upload_files.retain(|file| {
+                file.document_type != document_type
+                    || file.document_id.is_some()
+                    || file.status.is_some()
+            });
 
-            Ok(multiple_upload_url_response.clone())
+            (repository::generate_document_id(), 1, Vec::new())
         }
-        Err((r, m)) => Err(APIError::InternalServerError(format!(
-            "HTTP request failed: RejectionCode: {r:?}, Error: {m}"
-        ))),
-    }
+    };
+
+    let key = storage::service::build_object_key(
+        project_id,
+        step_phase_id,
+        step_id,
+        &format!("{}/{}/v{}", document_type, document_id, version),
+    );
+    let pending_file = UploadFile {
+        filename: Some(filename),
+        s3_key: Some(key),
+        status: Some(UploadStatus::Pending),
+        document_id: Some(document_id),
+        version: Some(version),
+        ..empty_upload_file(document_type)
+    };
+    upload_files.push(pending_file.clone());
+
+    Ok((pending_file, replaced_files))
 }
 
-#[ic_cdk::query()]
-fn transform(raw: TransformArgs) -> HttpResponse {
-    let headers = vec![
-        HttpHeader {
-            name: "Content-Security-Policy".to_string(),
-            value: "default-src 'self'".to_string(),
-        },
-        HttpHeader {
-            name: "Referrer-Policy".to_string(),
-            value: "strict-origin".to_string(),
-        },
-        HttpHeader {
-            name: "Permissions-Policy".to_string(),
-            value: "geolocation=(self)".to_string(),
-        },
-        HttpHeader {
-            name: "Strict-Transport-Security".to_string(),
-            value: "max-age=63072000".to_string(),
-        },
-        HttpHeader {
-            name: "X-Frame-Options".to_string(),
-            value: "DENY".to_string(),
-        },
-        HttpHeader {
-            name: "X-Content-Type-Options".to_string(),
-            value: "nosniff".to_string(),
-        },
-    ];
+// Removes the pending uploads whose URLs couldn't be generated.
+fn discard_pending_uploads(
+    caller_id: UserId,
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    step_id: StepId,
+    keys: &[String],
+) {
+    if let Ok(step) = get_project_step_by_id(project_id, step_phase_id, step_id) {
+        let (discarded_files, mut upload_files): (Vec<UploadFile>, Vec<UploadFile>) =
+            step.upload_files.into_iter().partition(|file| {
+                file.status == Some(UploadStatus::Pending)
+                    && file.s3_key.as_ref().is_some_and(|key| keys.contains(key))
+            });
+        restore_placeholders(&mut upload_files, &discarded_files);
+
+        save_upload_files(
+            caller_id,
+            project_id,
+            step_phase_id,
+            step_id,
+            upload_files,
+            &discarded_files,
+        );
+    }
+}
 
-    let mut res = HttpResponse {
-        status: raw.response.status.clone(),
-        body: raw.response.body.clone(),
-        headers,
+/// Saves the files of a step and removes the files that are not referenced anymore from the
+/// storage.
+fn save_upload_files(
+    caller_id: UserId,
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    step_id: StepId,
+    upload_files: Vec<UploadFile>,
+    removed_files: &[UploadFile],
+) {
+    let step_update = StepUpdate {
+        upload_files: Some(upload_files),
         ..Default::default()
     };
+    repository::update_step(caller_id, project_id, step_phase_id, step_id, step_update);
 
-    if res.status == 200 as u64 {
-        res.body = raw.response.body;
-    } else {
-        ic_cdk::api::print(format!("Received an error: {:?}", raw));
+    let removed_keys: Vec<String> = removed_files
+        .iter()
+        .flat_map(|file| {
+            file.s3_key.clone().into_iter().chain(
+                file.previous_versions
+                    .iter()
+                    .flatten()
+                    .filter_map(|version| version.s3_key.clone()),
+            )
+        })
+        .collect();
+    storage::service::remove_stored_files(&removed_keys);
+}
+
+// A document type without any file keeps a placeholder, the document is still expected.
+fn restore_placeholders(upload_files: &mut Vec<UploadFile>, removed_files: &[UploadFile]) {
+    for removed_file in removed_files {
+        if !upload_files
+            .iter()
+            .any(|file| file.document_type == removed_file.document_type)
+        {
+            upload_files.push(empty_upload_file(removed_file.document_type));
+        }
     }
-    res
 }
 
-fn calculate_voting_end_time(proposal: &ProposalData) -> u64 {
-    if let Some(wait_for_quiet_state) = &proposal.wait_for_quiet_state {
+fn empty_upload_file(document_type: DocumentTypeId) -> UploadFile {
+    UploadFile {
+        filename: None,
+        document_type,
+        s3_key: None,
+        status: None,
+        size: None,
+        sha256: None,
+        mime_type: None,
+        confirmed_at: None,
+        rejection_reason: None,
+        document_id: None,
+        version: None,
+        previous_versions: None,
+    }
+}
+
+/// Checks that every document required by the steps of a phase was uploaded and confirmed.
+fn check_step_phase_uploads(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+) -> Result<(), APIError> {
+    let mut missing_documents = Vec::new();
+
+    for step in repository::get_all_steps_by_phase(project_id, step_phase_id) {
+        let required_document_types = repository::get_step_config_by_id(step_phase_id, step.id)
+            .map(|step_config| step_config.required_upload_files)
+            .unwrap_or_default();
+
+        for document_type in required_document_types {
+            let files: Vec<&UploadFile> = step
+                .upload_files
+                .iter()
+                .filter(|file| file.document_type == document_type)
+                .collect();
+
+            if files
+                .iter()
+                .any(|file| file.status == Some(UploadStatus::Uploaded))
+            {
+                continue;
+            }
+
+            let status = files.iter().filter_map(|file| file.status.clone()).next();
+            let name = repository::get_document_type_by_id(document_type).map_or_else(
+                || document_type.to_string(),
+                |document_type| document_type.name,
+            );
+            missing_documents.push(format!(
+                "{} of step {} ({})",
+                name,
+                step.id,
+                status.map_or("Missing".to_string(), |status| status.to_string())
+            ));
+        }
+    }
+
+    if !missing_documents.is_empty() {
+        return Err(APIError::BadRequest(format!(
+            "Step phase with id: {} for project id: {} can't be submited until its documents are uploaded and confirmed: {}.",
+            step_phase_id,
+            project_id,
+            missing_documents.join(", ")
+        )));
+    }
+
+    Ok(())
+}
+
+// Phase dates are stored in nanoseconds, SNS timestamps are in seconds.
+pub fn calculate_voting_end_time(proposal: &ProposalData) -> u64 {
+    let voting_end_time_seconds = if let Some(wait_for_quiet_state) = &proposal.wait_for_quiet_state
+    {
         wait_for_quiet_state.current_deadline_timestamp_seconds
     } else {
         proposal.proposal_creation_timestamp_seconds + proposal.initial_voting_period_seconds
-    }
+    };
+
+    voting_end_time_seconds * 1_000_000_000
 }
@@ -1,9 +1,8 @@
 use std::fmt;
 
 use candid::{CandidType, Deserialize};
-use serde::Serialize;
 
-use crate::{DocumentType, ProjectId, UploadFile, UserId};
+use crate::{DocumentId, DocumentTypeId, ProjectId, UploadFile, UserId};
 
 pub type StepId = u64;
 pub type StepPhaseId = u64;
@@ -80,6 +79,8 @@ pub enum StepPhaseStatus {
     Submitted,
     Approved,
     NotApproved,
+    UnderAppeal,
+    PendingProposal,
 }
 
 impl fmt::Display for StepPhaseStatus {
@@ -90,6 +91,8 @@ impl fmt::Display for StepPhaseStatus {
             StepPhaseStatus::Submitted => write!(f, "Submitted"),
             StepPhaseStatus::Approved => write!(f, "Approved"),
             StepPhaseStatus::NotApproved => write!(f, "Not Approved"),
+            StepPhaseStatus::UnderAppeal => write!(f, "Under Appeal"),
+            StepPhaseStatus::PendingProposal => write!(f, "Pending Proposal"),
         }
     }
 }
@@ -102,10 +105,86 @@ impl fmt::Debug for StepPhaseStatus {
             Self::Submitted => write!(f, "Submitted"),
             Self::Approved => write!(f, "Approved"),
             Self::NotApproved => write!(f, "Not Approved"),
+            Self::UnderAppeal => write!(f, "Under Appeal"),
+            Self::PendingProposal => write!(f, "Pending Proposal"),
         }
     }
 }
 
+#[derive(CandidType, Deserialize, PartialEq, Clone, Debug)]
+pub enum StepPhaseDeadline {
+    Open,
+    Assessment,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StepPhaseDeadlineExtension {
+    pub deadline: StepPhaseDeadline,
+    pub new_end_date: u64,
+    pub reason: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StepPhaseDeadlineExtensionPayload {
+    pub project_id: ProjectId,
+    pub phase_id: StepPhaseId,
+    pub extension: StepPhaseDeadlineExtension,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StepPhaseReopen {
+    pub end_open_date: Option<u64>,
+    pub reason: String,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Clone, Debug)]
+pub enum StepPhaseAdminActionKind {
+    ExtendOpenDeadline,
+    ExtendAssessmentDeadline,
+    Reopen,
+    ResetToOpen,
+    ShiftForSuspension,
+}
+
+impl fmt::Display for StepPhaseAdminActionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            StepPhaseAdminActionKind::ExtendOpenDeadline => write!(f, "Extend Open Deadline"),
+            StepPhaseAdminActionKind::ExtendAssessmentDeadline => {
+                write!(f, "Extend Assessment Deadline")
+            }
+            StepPhaseAdminActionKind::Reopen => write!(f, "Reopen"),
+            StepPhaseAdminActionKind::ResetToOpen => write!(f, "Reset To Open"),
+            StepPhaseAdminActionKind::ShiftForSuspension => write!(f, "Shift For Suspension"),
+        }
+    }
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StepPhaseAdminActionCreate {
+    pub action: StepPhaseAdminActionKind,
+    pub reason: String,
+    pub previous_status: StepPhaseStatus,
+    pub new_status: StepPhaseStatus,
+    pub previous_end_date: u64,
+    pub new_end_date: u64,
+    pub performed_by: UserId,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StepPhaseAdminAction {
+    pub project_id: ProjectId,
+    pub step_phase_id: StepPhaseId,
+    pub action: StepPhaseAdminActionKind,
+    pub reason: String,
+    pub previous_status: StepPhaseStatus,
+    pub new_status: StepPhaseStatus,
+    pub previous_end_date: u64,
+    pub new_end_date: u64,
+    pub performed_by: UserId,
+    pub performed_at: u64,
+}
+
 #[derive(CandidType, Deserialize, Debug)]
 pub struct StepPhaseVoteResultCreate {
     pub yes: u64,
@@ -217,10 +296,24 @@ pub struct StepPhaseProposal {
     pub proposal_id: u64,
 }
 
+/// Upload of a new document, or of a new version of the document `document_id`.
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct UploadUrlRequest {
     pub filename: String,
-    pub document_type: DocumentType,
+    pub document_type: DocumentTypeId,
+    pub document_id: Option<DocumentId>,
+}
+
+/// Metadata of a file uploaded with a generated URL, checked against the limits of its
+/// document type. `document_id` can be left out when only one file of the document type is
+/// waiting for its confirmation.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct UploadConfirmation {
+    pub document_type: DocumentTypeId,
+    pub size: u64,
+    pub sha256: String,
+    pub mime_type: String,
+    pub document_id: Option<DocumentId>,
 }
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
@@ -229,34 +322,20 @@ pub struct UploadUrlResponse {
     pub step_phase_id: StepPhaseId,
     pub step_id: StepId,
     pub url: String,
-    pub document_type: DocumentType,
-}
-
-#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
-pub struct UploadPreSignedUrlRequest {
-    pub bucket: String,
-    pub keys: Vec<String>,
-    pub method: S3Method, // "PUT" or "GET"
-}
-
-#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
-pub enum S3Method {
-    PUT,
-    GET,
+    pub document_type: DocumentTypeId,
+    pub document_id: DocumentId,
+    pub version: u32,
 }
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
-pub struct MultipleUploadUrlResponse {
-    pub urls: Vec<PresignedUrlResponse>,
-}
-
-#[derive(CandidType, Deserialize, Clone, Debug)]
-pub struct PresignedUrlResponse {
-    pub key: String,
+pub struct DownloadUrlResponse {
+    pub project_id: ProjectId,
+    pub step_phase_id: StepPhaseId,
+    pub step_id: StepId,
+    pub document_type: DocumentTypeId,
+    pub document_id: Option<DocumentId>,
+    pub version: Option<u32>,
+    pub filename: Option<String>,
     pub url: String,
-}
-#[derive(Serialize, Deserialize)]
-pub struct Context {
-    pub bucket_start_time_index: usize,
-    pub closing_price_index: usize,
+    pub expires_at: u64,
 }
@@ -3,10 +3,15 @@ use candid::{Decode, Encode};
 use ic_stable_structures::{storable::Bound, Storable};
 use std::borrow::Cow;
 
-use crate::{StepGradeResult, UploadFile, UserId};
+use crate::domains::storage::types_storage::LegacyDocumentType;
+use crate::{
+    DocumentId, DocumentTypeId, StepGradeResult, UploadFile, UploadFileVersion, UploadStatus,
+    UserId,
+};
 
 use super::types::{
-    AssessmentMethod, CheckBoxSubmission, DecimalSubmission, QuestionSubmission, StepPhaseStatus,
+    AssessmentMethod, CheckBoxSubmission, DecimalSubmission, QuestionSubmission,
+    StepPhaseAdminActionKind, StepPhaseStatus,
 };
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
@@ -14,7 +19,7 @@ pub struct StepModel {
     pub questions_submission: Vec<QuestionSubmission>,
     pub checkbox_submission: Vec<CheckBoxSubmission>,
     pub numeric_submission: Vec<DecimalSubmission>,
-    pub upload_files: Vec<UploadFile>,
+    pub upload_files: Vec<UploadFileModel>,
     pub grade_end_date: Option<u64>,
     pub update_by: Option<UserId>,
     pub update_at: Option<u64>,
@@ -32,6 +37,67 @@ impl Storable for StepModel {
     const BOUND: Bound = Bound::Unbounded;
 }
 
+/// Uploaded file of a step. The files uploaded before the document types became configurable
+/// only hold their former built-in `document_type`.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct UploadFileModel {
+    pub filename: Option<String>,
+    pub document_type: Option<LegacyDocumentType>,
+    pub s3_key: Option<String>,
+    pub document_type_id: Option<DocumentTypeId>,
+    pub status: Option<UploadStatus>,
+    pub size: Option<u64>,
+    pub sha256: Option<String>,
+    pub mime_type: Option<String>,
+    pub confirmed_at: Option<u64>,
+    pub rejection_reason: Option<String>,
+    pub document_id: Option<DocumentId>,
+    pub version: Option<u32>,
+    pub previous_versions: Option<Vec<UploadFileVersion>>,
+}
+
+impl From<UploadFile> for UploadFileModel {
+    fn from(file: UploadFile) -> Self {
+        UploadFileModel {
+            filename: file.filename,
+            document_type: None,
+            s3_key: file.s3_key,
+            document_type_id: Some(file.document_type),
+            status: file.status,
+            size: file.size,
+            sha256: file.sha256,
+            mime_type: file.mime_type,
+            confirmed_at: file.confirmed_at,
+            rejection_reason: file.rejection_reason,
+            document_id: file.document_id,
+            version: file.version,
+            previous_versions: file.previous_versions,
+        }
+    }
+}
+
+impl From<UploadFileModel> for UploadFile {
+    fn from(model: UploadFileModel) -> Self {
+        UploadFile {
+            filename: model.filename,
+            document_type: model
+                .document_type_id
+                .or_else(|| model.document_type.map(|legacy| legacy.document_type_id()))
+                .expect("Upload file without a document type."),
+            s3_key: model.s3_key,
+            status: model.status,
+            size: model.size,
+            sha256: model.sha256,
+            mime_type: model.mime_type,
+            confirmed_at: model.confirmed_at,
+            rejection_reason: model.rejection_reason,
+            document_id: model.document_id,
+            version: model.version,
+            previous_versions: model.previous_versions,
+        }
+    }
+}
+
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct StepPhaseModel {
     pub status: StepPhaseStatus,
@@ -93,3 +159,108 @@ impl Storable for StepPhaseVoteResultModel {
 
     const BOUND: Bound = Bound::Unbounded;
 }
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StepPhaseAdminActionModel {
+    pub action: StepPhaseAdminActionKind,
+    pub reason: String,
+    pub previous_status: StepPhaseStatus,
+    pub new_status: StepPhaseStatus,
+    pub previous_end_date: u64,
+    pub new_end_date: u64,
+    pub performed_by: UserId,
+    pub performed_at: u64,
+}
+
+impl Storable for StepPhaseAdminActionModel {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::storage::constants::{LOGO_DOCUMENT_TYPE_ID, PITCH_DECK_DOCUMENT_TYPE_ID};
+
+    // Step as stored before the document types became configurable.
+    #[derive(CandidType)]
+    struct BaselineStepModel {
+        questions_submission: Vec<QuestionSubmission>,
+        checkbox_submission: Vec<CheckBoxSubmission>,
+        numeric_submission: Vec<DecimalSubmission>,
+        upload_files: Vec<BaselineUploadFile>,
+        grade_end_date: Option<u64>,
+        update_by: Option<UserId>,
+        update_at: Option<u64>,
+    }
+
+    #[derive(CandidType)]
+    struct BaselineUploadFile {
+        filename: Option<String>,
+        document_type: LegacyDocumentType,
+        s3_key: Option<String>,
+    }
+
+    #[test]
+    fn baseline_steps_read_their_former_document_types() {
+        let baseline = BaselineStepModel {
+            questions_submission: vec![],
+            checkbox_submission: vec![],
+            numeric_submission: vec![],
+            upload_files: vec![BaselineUploadFile {
+                filename: Some("deck.pdf".to_string()),
+                document_type: LegacyDocumentType::PitchDeck,
+                s3_key: Some("projects/1/deck.pdf".to_string()),
+            }],
+            grade_end_date: None,
+            update_by: None,
+            update_at: Some(1),
+        };
+
+        let model = StepModel::from_bytes(Cow::Owned(Encode!(&baseline).unwrap()));
+        let file = UploadFile::from(model.upload_files[0].clone());
+
+        assert_eq!(file.document_type, PITCH_DECK_DOCUMENT_TYPE_ID);
+        assert_eq!(file.s3_key.as_deref(), Some("projects/1/deck.pdf"));
+        assert!(file.status.is_none());
+        assert_eq!(model.update_at, Some(1));
+    }
+
+    #[test]
+    fn upload_files_keep_their_document_type_id() {
+        let model = StepModel {
+            questions_submission: vec![],
+            checkbox_submission: vec![],
+            numeric_submission: vec![],
+            upload_files: vec![UploadFileModel::from(UploadFile {
+                filename: None,
+                document_type: LOGO_DOCUMENT_TYPE_ID,
+                s3_key: None,
+                status: None,
+                size: None,
+                sha256: None,
+                mime_type: None,
+                confirmed_at: None,
+                rejection_reason: None,
+                document_id: None,
+                version: None,
+                previous_versions: None,
+            })],
+            grade_end_date: None,
+            update_by: None,
+            update_at: None,
+        };
+
+        let model = StepModel::from_bytes(model.to_bytes());
+        let file = UploadFile::from(model.upload_files[0].clone());
+
+        assert_eq!(file.document_type, LOGO_DOCUMENT_TYPE_ID);
+    }
+}
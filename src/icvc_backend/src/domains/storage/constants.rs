@@ -0,0 +1,32 @@
+use super::types::DocumentTypeId;
+
+// Default upload provider
+pub const DEFAULT_STORAGE_ENDPOINT: &str = "https://s3.eu-central-1.amazonaws.com";
+pub const DEFAULT_STORAGE_REGION: &str = "eu-central-1";
+pub const DEFAULT_STORAGE_BUCKET: &str = "icvc-s3-uploads";
+pub const DEFAULT_STORAGE_PATH_PREFIX: &str = "projects";
+pub const MAX_STORAGE_FIELD_BYTES: usize = 2048;
+pub const MAX_FILES_PER_DOCUMENT_TYPE: u32 = 20;
+pub const MAX_DOCUMENT_TYPE_NAME_BYTES: usize = 100;
+pub const MAX_DOCUMENT_TYPE_DESCRIPTION_BYTES: usize = 1000;
+pub const MAX_DOCUMENT_TYPE_MIME_TYPES: usize = 20;
+// Document types created in place of the former built-in ones, with the same IDs everywhere
+pub const PITCH_DECK_DOCUMENT_TYPE_ID: DocumentTypeId = 1;
+pub const LOGO_DOCUMENT_TYPE_ID: DocumentTypeId = 2;
+pub const COVER_PHOTO_DOCUMENT_TYPE_ID: DocumentTypeId = 3;
+pub const FINANCIAL_MODELS_DOCUMENT_TYPE_ID: DocumentTypeId = 4;
+pub const PRODUCT_DEMO_DOCUMENT_TYPE_ID: DocumentTypeId = 5;
+pub const EXPENDITURE_PLAN_DOCUMENT_TYPE_ID: DocumentTypeId = 6;
+// Lifetime in seconds of the download URLs and of the on-chain access tokens
+pub const DOWNLOAD_URL_DURATION: u64 = 15 * 60;
+// Lifetime in seconds of the upload URLs
+pub const UPLOAD_URL_DURATION: u64 = 60 * 60;
+// HEAD requests checking the uploaded objects, the response only holds headers
+pub const MAX_HEAD_RESPONSE_BYTES: u64 = 8 * 1024;
+pub const HEAD_REQUEST_CYCLES: u128 = 200_000_000;
+// On-chain storage
+pub const MAX_FILE_CHUNK_BYTES: u64 = 1024 * 1024; // Below the 2 MiB ingress message limit
+pub const MAX_FILENAME_BYTES: usize = 255;
+pub const MAX_PENDING_UPLOAD_BYTES_PER_PROJECT: u64 = 512 * 1024 * 1024; // Uncommitted uploads
+pub const MAX_MIME_TYPE_BYTES: usize = 127;
+pub const FILE_HTTP_PATH_PREFIX: &str = "/files/";
@@ -0,0 +1,299 @@
+//! This module defines the controller functions for the storage of the project documents.
+
+use ic_cdk::api::management_canister::http_request::{HttpResponse, TransformArgs};
+use serde_bytes::ByteBuf;
+
+use crate::{
+    utils::authenticator::{
+        check_is_owner_or_admin, check_is_owner_or_governance_id, check_is_project_owner_or_admin,
+        check_is_sns_governance,
+    },
+    APIError, ProjectId, StepId, StepPhaseId,
+};
+
+use super::{
+    service,
+    types::{
+        DocumentAccess, DocumentType, DocumentTypeCreate, DocumentTypeId,
+        DocumentTypeProposalPayload, DocumentTypeUpdate, FileAccessUrl, FileId, FileUploadCreate,
+        HttpGatewayRequest, HttpGatewayResponse, StorageConfig, StorageConfigUpdate, StoredFile,
+        StreamingCallbackHttpResponse, StreamingCallbackToken,
+    },
+};
+
+/// Retrieves the storage configuration. The secret access key is never returned, but the node
+/// operators of the subnet can read it in the canister state.
+///
+/// # Returns
+/// * `Result<StorageConfig, APIError>` - The storage configuration or an error.
+#[ic_cdk::query(name = "getStorageConfig")]
+pub fn get_storage_config() -> Result<StorageConfig, APIError> {
+    check_is_owner_or_admin(ic_cdk::caller())?;
+
+    Ok(service::get_storage_config())
+}
+
+/// Updates the storage configuration, only the fields set in the update are changed. The
+/// secret access key can only be set by the owner, never by the SNS governance. It is kept in
+/// plain text, readable by the node operators of the subnet, so it must belong to a key that
+/// can only put and get the objects under the configured bucket and path prefix.
+///
+/// # Arguments
+/// * `storage_config_update` - The new values of the storage configuration.
+///
+/// # Returns
+/// * `Result<StorageConfig, APIError>` - The updated storage configuration or an error.
+#[ic_cdk::update(name = "updateStorageConfig")]
+pub fn update_storage_config(
+    storage_config_update: StorageConfigUpdate,
+) -> Result<StorageConfig, APIError> {
+    let caller_id = ic_cdk::caller();
+    check_is_owner_or_governance_id(caller_id)?;
+
+    if check_is_sns_governance(caller_id).is_ok() {
+        service::check_storage_config_proposal(&storage_config_update)?;
+    }
+
+    service::update_storage_config(storage_config_update)
+}
+
+#[ic_cdk::update]
+fn validate_storage_config_proposal(payload: StorageConfigUpdate) -> Result<String, String> {
+    let caller_id = ic_cdk::caller();
+
+    match check_is_owner_or_governance_id(caller_id) {
+        Ok(_) => service::validate_update_storage_config(payload),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+#[ic_cdk::update]
+fn execute_storage_config_proposal(payload: StorageConfigUpdate) {
+    let caller_id = ic_cdk::caller();
+
+    match check_is_owner_or_governance_id(caller_id) {
+        Ok(_) => {
+            if let Err(err) = service::execute_storage_config_proposal(payload) {
+                ic_cdk::println!(
+                    "Unable to execute storage config proposal: {}",
+                    err.to_string()
+                );
+            }
+        }
+        Err(err) => {
+            ic_cdk::println!(
+                "Unable to execute storage config proposal: {}",
+                err.to_string()
+            );
+        }
+    }
+}
+
+/// Retrieves the document types, with the size, MIME and count limits of their files.
+///
+/// # Returns
+/// * `Vec<DocumentType>` - Every document type, active or not.
+#[ic_cdk::query(name = "getDocumentTypes")]
+pub fn get_document_types() -> Vec<DocumentType> {
+    service::get_all_document_types()
+}
+
+/// Retrieves a document type.
+///
+/// # Arguments
+/// * `document_type_id` - The ID of the document type.
+///
+/// # Returns
+/// * `Result<DocumentType, APIError>` - The document type or an error.
+#[ic_cdk::query(name = "getDocumentTypeById")]
+pub fn get_document_type_by_id(document_type_id: DocumentTypeId) -> Result<DocumentType, APIError> {
+    service::get_document_type(document_type_id)
+}
+
+/// Creates a document type, which can then be required by the steps.
+///
+/// # Arguments
+/// * `document_type_create` - The name, description and file limits of the document type.
+///
+/// # Returns
+/// * `Result<DocumentType, APIError>` - The created document type or an error.
+#[ic_cdk::update(name = "createDocumentType")]
+pub fn create_document_type(
+    document_type_create: DocumentTypeCreate,
+) -> Result<DocumentType, APIError> {
+    check_is_owner_or_governance_id(ic_cdk::caller())?;
+
+    service::create_document_type(document_type_create)
+}
+
+/// Updates a document type, only the fields set in the update are changed. The new limits
+/// apply to the files uploaded afterwards.
+///
+/// # Arguments
+/// * `document_type_id` - The ID of the document type.
+/// * `document_type_update` - The new values of the document type.
+///
+/// # Returns
+/// * `Result<DocumentType, APIError>` - The updated document type or an error.
+#[ic_cdk::update(name = "updateDocumentType")]
+pub fn update_document_type(
+    document_type_id: DocumentTypeId,
+    document_type_update: DocumentTypeUpdate,
+) -> Result<DocumentType, APIError> {
+    check_is_owner_or_governance_id(ic_cdk::caller())?;
+
+    service::update_document_type(document_type_id, document_type_update)
+}
+
+#[ic_cdk::update]
+fn validate_document_type_proposal(payload: DocumentTypeProposalPayload) -> Result<String, String> {
+    let caller_id = ic_cdk::caller();
+
+    match check_is_owner_or_governance_id(caller_id) {
+        Ok(_) => service::validate_document_type_proposal(payload),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+#[ic_cdk::update]
+fn execute_document_type_proposal(payload: DocumentTypeProposalPayload) {
+    let caller_id = ic_cdk::caller();
+
+    match check_is_owner_or_governance_id(caller_id) {
+        Ok(_) => {
+            if let Err(err) = service::execute_document_type_proposal(payload) {
+                ic_cdk::println!(
+                    "Unable to execute document type proposal: {}",
+                    err.to_string()
+                );
+            }
+        }
+        Err(err) => {
+            ic_cdk::println!(
+                "Unable to execute document type proposal: {}",
+                err.to_string()
+            );
+        }
+    }
+}
+
+/// Starts the upload of a document to the on-chain storage. The file is then uploaded with
+/// `uploadFileChunk` in chunks of 1 MiB, and attached to the step by `commitFileUpload`.
+/// The unfinished uploads of a project can't hold more than 512 MiB.
+///
+/// # Arguments
+/// * `project_id` - The ID of the project.
+/// * `step_phase_id` - The ID of the step phase.
+/// * `step_id` - The ID of the step.
+/// * `file_upload_create` - The document type, name, MIME type, size and SHA-256 of the file.
+///
+/// # Returns
+/// * `Result<StoredFile, APIError>` - The file waiting for its chunks or an error.
+#[ic_cdk::update(name = "createFileUpload")]
+pub fn create_file_upload(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    step_id: StepId,
+    file_upload_create: FileUploadCreate,
+) -> Result<StoredFile, APIError> {
+    let caller_id = ic_cdk::caller();
+    check_is_project_owner_or_admin(caller_id, project_id)?;
+
+    service::create_file_upload(
+        caller_id,
+        project_id,
+        step_phase_id,
+        step_id,
+        file_upload_create,
+    )
+}
+
+/// Uploads a chunk of a file while its step still accepts uploads. Every chunk is 1 MiB,
+/// except the last one.
+///
+/// # Arguments
+/// * `file_id` - The ID of the file returned by `createFileUpload`.
+/// * `chunk_index` - The position of the chunk in the file, starting at 0.
+/// * `content` - The bytes of the chunk.
+///
+/// # Returns
+/// * `Result<StoredFile, APIError>` - The file with its upload progress or an error.
+#[ic_cdk::update(name = "uploadFileChunk")]
+pub fn upload_file_chunk(
+    file_id: FileId,
+    chunk_index: u32,
+    content: ByteBuf,
+) -> Result<StoredFile, APIError> {
+    service::upload_file_chunk(ic_cdk::caller(), file_id, chunk_index, content.into_vec())
+}
+
+/// Checks the SHA-256 of the uploaded chunks and attaches the file to its step, replacing the
+/// previous file of the same document type.
+///
+/// # Arguments
+/// * `file_id` - The ID of the file returned by `createFileUpload`.
+///
+/// # Returns
+/// * `Result<StoredFile, APIError>` - The committed file or an error.
+#[ic_cdk::update(name = "commitFileUpload")]
+pub fn commit_file_upload(file_id: FileId) -> Result<StoredFile, APIError> {
+    service::commit_file_upload(ic_cdk::caller(), file_id)
+}
+
+/// Retrieves the metadata of a file of the on-chain storage.
+///
+/// # Arguments
+/// * `file_id` - The ID of the file.
+///
+/// # Returns
+/// * `Result<StoredFile, APIError>` - The file or an error.
+#[ic_cdk::query(name = "getFileById")]
+pub fn get_file_by_id(file_id: FileId) -> Result<StoredFile, APIError> {
+    service::get_file_by_id(ic_cdk::caller(), file_id)
+}
+
+/// Creates a short-lived URL to download a file of the on-chain storage.
+///
+/// # Arguments
+/// * `file_id` - The ID of the file.
+///
+/// # Returns
+/// * `Result<FileAccessUrl, APIError>` - The URL with its access token or an error.
+#[ic_cdk::update(name = "getFileAccessUrl")]
+pub async fn get_file_access_url(file_id: FileId) -> Result<FileAccessUrl, APIError> {
+    service::get_file_access_url(ic_cdk::caller(), file_id).await
+}
+
+/// Retrieves the download URLs issued for the documents of a project, oldest first.
+///
+/// # Arguments
+/// * `project_id` - The ID of the project.
+///
+/// # Returns
+/// * `Result<Vec<DocumentAccess>, APIError>` - The access log of the project or an error.
+#[ic_cdk::query(name = "getDocumentAccessLog")]
+pub fn get_document_access_log(project_id: ProjectId) -> Result<Vec<DocumentAccess>, APIError> {
+    check_is_project_owner_or_admin(ic_cdk::caller(), project_id)?;
+
+    Ok(service::get_document_access_log(project_id))
+}
+
+#[ic_cdk::query]
+fn http_request(request: HttpGatewayRequest) -> HttpGatewayResponse {
+    service::serve_file(request)
+}
+
+#[ic_cdk::query]
+fn http_request_streaming_callback(token: StreamingCallbackToken) -> StreamingCallbackHttpResponse {
+    match service::serve_file_chunk(token) {
+        Ok(response) => response,
+        Err(err) => ic_cdk::trap(&err.to_string()),
+    }
+}
+
+/// Reduces the responses to the HEAD requests checking the uploaded files to the headers that
+/// are the same on every replica.
+#[ic_cdk::query]
+fn transform_object_head(args: TransformArgs) -> HttpResponse {
+    service::transform_object_head(args)
+}
@@ -0,0 +1,7 @@
+pub mod constants;
+pub mod controller;
+mod provider;
+#[doc(hidden)]
+pub mod service;
+pub mod types;
+pub mod types_storage;
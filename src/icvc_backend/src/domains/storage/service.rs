@@ -0,0 +1,1339 @@
+use ic_cdk::api::{
+    self,
+    management_canister::{
+        http_request::{HttpResponse, TransformArgs},
+        main::raw_rand,
+    },
+    stable::WASM_PAGE_SIZE_IN_BYTES,
+};
+use serde_bytes::ByteBuf;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    domains::{
+        audit::{
+            self,
+            types::{AuditAction, AuditTarget},
+        },
+        step,
+    },
+    repository,
+    utils::authenticator::check_is_project_owner_reviewer_or_admin,
+    APIError, ProjectId, StepId, StepPhaseId, UploadFile, UploadStatus, UserId,
+};
+
+use super::{
+    constants::{
+        COVER_PHOTO_DOCUMENT_TYPE_ID, DOWNLOAD_URL_DURATION, EXPENDITURE_PLAN_DOCUMENT_TYPE_ID,
+        FILE_HTTP_PATH_PREFIX, FINANCIAL_MODELS_DOCUMENT_TYPE_ID, LOGO_DOCUMENT_TYPE_ID,
+        MAX_DOCUMENT_TYPE_DESCRIPTION_BYTES, MAX_DOCUMENT_TYPE_MIME_TYPES,
+        MAX_DOCUMENT_TYPE_NAME_BYTES, MAX_FILENAME_BYTES, MAX_FILES_PER_DOCUMENT_TYPE,
+        MAX_FILE_CHUNK_BYTES, MAX_MIME_TYPE_BYTES, MAX_PENDING_UPLOAD_BYTES_PER_PROJECT,
+        MAX_STORAGE_FIELD_BYTES, PITCH_DECK_DOCUMENT_TYPE_ID, PRODUCT_DEMO_DOCUMENT_TYPE_ID,
+    },
+    provider::{
+        self, AssetCanisterProvider, MockHttpProvider, ObjectHead, OnChainProvider,
+        S3CompatibleProvider, StorageProvider,
+    },
+    types::{
+        DocumentAccess, DocumentType, DocumentTypeCreate, DocumentTypeId,
+        DocumentTypeProposalPayload, DocumentTypeUpdate, FileAccessUrl, FileId, FileUploadCreate,
+        HttpGatewayRequest, HttpGatewayResponse, PresignedUrlResponse, S3Method, StorageConfig,
+        StorageConfigUpdate, StorageProviderKind, StoredFile, StreamingCallback,
+        StreamingCallbackHttpResponse, StreamingCallbackToken, StreamingStrategy,
+    },
+    types_storage::{
+        DocumentAccessModel, DocumentTypeModel, FileAccessTokenModel, FileKeyEntryModel,
+        StorageConfigModel, StoredFileModel,
+    },
+};
+
+const MB: u64 = 1024 * 1024;
+const PDF: &str = "application/pdf";
+const PPTX: &str = "application/vnd.openxmlformats-officedocument.presentationml.presentation";
+const XLSX: &str = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+const CSV: &str = "text/csv";
+const PNG: &str = "image/png";
+const JPEG: &str = "image/jpeg";
+const WEBP: &str = "image/webp";
+const MP4: &str = "video/mp4";
+const WEBM: &str = "video/webm";
+
+pub fn get_storage_config() -> StorageConfig {
+    repository::get_storage_config()
+}
+
+pub fn update_storage_config(
+    storage_config_update: StorageConfigUpdate,
+) -> Result<StorageConfig, APIError> {
+    check_storage_config_update(&storage_config_update)?;
+
+    let previous_config = repository::get_storage_config();
+    match repository::update_storage_config(storage_config_update) {
+        Ok(config) => {
+            audit::service::record(
+                ic_cdk::caller(),
+                AuditAction::StorageConfigUpdated,
+                AuditTarget::default(),
+                Some(format!("{:?}", previous_config)),
+                Some(format!("{:?}", config)),
+            );
+            Ok(config)
+        }
+        Err(_) => Err(APIError::InternalServerError(
+            "Unable to update storage config".to_string(),
+        )),
+    }
+}
+
+pub fn execute_storage_config_proposal(
+    storage_config_update: StorageConfigUpdate,
+) -> Result<StorageConfig, APIError> {
+    check_storage_config_proposal(&storage_config_update)?;
+
+    update_storage_config(storage_config_update)
+}
+
+pub fn validate_update_storage_config(
+    storage_config_update: StorageConfigUpdate,
+) -> Result<String, String> {
+    match check_storage_config_proposal(&storage_config_update)
+        .and_then(|_| check_storage_config_update(&storage_config_update))
+    {
+        Ok(()) => {
+            let mut changes = Vec::new();
+
+            if let Some(provider) = &storage_config_update.provider {
+                changes.push(format!("provider: {}", provider));
+            }
+            if let Some(endpoint) = &storage_config_update.endpoint {
+                changes.push(format!("endpoint: \"{}\"", endpoint));
+            }
+            if let Some(bucket) = &storage_config_update.bucket {
+                changes.push(format!("bucket: \"{}\"", bucket));
+            }
+            if let Some(path_prefix) = &storage_config_update.path_prefix {
+                changes.push(format!("path prefix: \"{}\"", path_prefix));
+            }
+            if let Some(region) = &storage_config_update.region {
+                changes.push(format!("region: \"{}\"", region));
+            }
+            match storage_config_update.access_key_id.as_deref() {
+                Some("") => changes.push("access key id: removed".to_string()),
+                Some(access_key_id) => {
+                    changes.push(format!("access key id: \"{}\"", access_key_id))
+                }
+                None => {}
+            }
+
+            Ok(format!(
+                "Update the storage config with values: {}.",
+                changes.join(", ")
+            ))
+        }
+        Err(e) => Err(format!("Unable to validate storage config proposal: {}", e)),
+    }
+}
+
+/// Key of a project document in the storage, below the configured path prefix.
+pub fn build_object_key(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    step_id: StepId,
+    name: &str,
+) -> String {
+    let path_prefix = repository::get_storage_config_model().path_prefix;
+    let path_prefix = path_prefix.trim_matches('/');
+    let key = format!("{}/{}/{}/{}", project_id, step_phase_id, step_id, name);
+
+    if path_prefix.is_empty() {
+        key
+    } else {
+        format!("{}/{}", path_prefix, key)
+    }
+}
+
+pub async fn presign_urls(
+    keys: Vec<String>,
+    method: S3Method,
+) -> Result<Vec<PresignedUrlResponse>, APIError> {
+    let config = repository::get_storage_config_model();
+
+    match config.provider {
+        StorageProviderKind::S3Compatible => {
+            S3CompatibleProvider { config: &config }
+                .presign_urls(keys, method)
+                .await
+        }
+        StorageProviderKind::MockHttp => {
+            MockHttpProvider { config: &config }
+                .presign_urls(keys, method)
+                .await
+        }
+        StorageProviderKind::AssetCanister => {
+            AssetCanisterProvider { config: &config }
+                .presign_urls(keys, method)
+                .await
+        }
+        StorageProviderKind::OnChain => {
+            OnChainProvider { config: &config }
+                .presign_urls(keys, method)
+                .await
+        }
+    }
+}
+
+/// Reads the size and SHA-256 of the object stored under `key` back from the storage.
+pub(crate) async fn head_object(key: &str) -> Result<ObjectHead, APIError> {
+    let config = repository::get_storage_config_model();
+
+    match config.provider {
+        StorageProviderKind::S3Compatible => {
+            S3CompatibleProvider { config: &config }
+                .head_object(key)
+                .await
+        }
+        StorageProviderKind::MockHttp => {
+            MockHttpProvider { config: &config }.head_object(key).await
+        }
+        StorageProviderKind::AssetCanister => {
+            AssetCanisterProvider { config: &config }
+                .head_object(key)
+                .await
+        }
+        StorageProviderKind::OnChain => OnChainProvider { config: &config }.head_object(key).await,
+    }
+}
+
+pub fn transform_object_head(args: TransformArgs) -> HttpResponse {
+    provider::transform_object_head(args.response)
+}
+
+/// Proposals are public, the secret access key is only set by the owner with
+/// `updateStorageConfig`.
+pub fn check_storage_config_proposal(update: &StorageConfigUpdate) -> Result<(), APIError> {
+    if update.secret_access_key.is_some() {
+        return Err(APIError::BadRequest(
+            "The secret access key can't be set by a proposal, the owner sets it with updateStorageConfig.".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn check_storage_config_update(update: &StorageConfigUpdate) -> Result<(), APIError> {
+    let current = repository::get_storage_config_model();
+    let provider = update.provider.as_ref().unwrap_or(&current.provider);
+    let endpoint = update.endpoint.as_ref().unwrap_or(&current.endpoint);
+    let bucket = update.bucket.as_ref().unwrap_or(&current.bucket);
+
+    for value in [
+        &update.endpoint,
+        &update.bucket,
+        &update.path_prefix,
+        &update.region,
+        &update.access_key_id,
+        &update.secret_access_key,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        if value.len() > MAX_STORAGE_FIELD_BYTES {
+            return Err(APIError::BadRequest(format!(
+                "Storage config values can't exceed {} bytes.",
+                MAX_STORAGE_FIELD_BYTES
+            )));
+        }
+    }
+
+    if endpoint.trim().is_empty() {
+        return Err(APIError::BadRequest(
+            "The storage endpoint can't be empty.".to_string(),
+        ));
+    }
+
+    if *provider == StorageProviderKind::S3Compatible {
+        if !endpoint.starts_with("https://") {
+            return Err(APIError::BadRequest(
+                "The endpoint of an S3 compatible storage must use https.".to_string(),
+            ));
+        }
+        if bucket.trim().is_empty() {
+            return Err(APIError::BadRequest(
+                "The bucket of an S3 compatible storage can't be empty.".to_string(),
+            ));
+        }
+    }
+
+    if let Some(region) = &update.region {
+        let valid_region = !region.is_empty()
+            && region
+                .bytes()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == b'-');
+        if !valid_region {
+            return Err(APIError::BadRequest(format!(
+                "Invalid storage region: \"{}\".",
+                region
+            )));
+        }
+    }
+
+    if let Some(access_key_id) = &update.access_key_id {
+        if !access_key_id.bytes().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(APIError::BadRequest(format!(
+                "Invalid access key id: \"{}\".",
+                access_key_id
+            )));
+        }
+    }
+
+    if let Some(secret_access_key) = &update.secret_access_key {
+        if !secret_access_key.bytes().all(|c| c.is_ascii_graphic()) {
+            return Err(APIError::BadRequest(
+                "The secret access key can only hold visible ASCII characters.".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn get_document_type(document_type_id: DocumentTypeId) -> Result<DocumentType, APIError> {
+    repository::get_document_type_by_id(document_type_id).ok_or_else(|| {
+        APIError::NotFound(format!(
+            "Document type with id: {} not found.",
+            document_type_id
+        ))
+    })
+}
+
+pub fn get_all_document_types() -> Vec<DocumentType> {
+    repository::get_all_document_types()
+}
+
+pub fn create_document_type(
+    document_type_create: DocumentTypeCreate,
+) -> Result<DocumentType, APIError> {
+    check_document_type_create(&document_type_create)?;
+
+    let document_type = repository::insert_document_type(
+        repository::generate_document_type_id(),
+        DocumentTypeModel {
+            name: document_type_create.name.trim().to_string(),
+            description: document_type_create.description,
+            mime_types: normalize_mime_types(document_type_create.mime_types),
+            max_size: document_type_create.max_size,
+            max_files: document_type_create.max_files,
+            public: document_type_create.public,
+            active: true,
+        },
+    );
+    audit::service::record(
+        ic_cdk::caller(),
+        AuditAction::DocumentTypeCreated,
+        AuditTarget::entity(document_type.id),
+        None,
+        Some(format!("{:?}", document_type)),
+    );
+
+    Ok(document_type)
+}
+
+pub fn update_document_type(
+    document_type_id: DocumentTypeId,
+    mut document_type_update: DocumentTypeUpdate,
+) -> Result<DocumentType, APIError> {
+    check_document_type_update(document_type_id, &document_type_update)?;
+
+    document_type_update.name = document_type_update
+        .name
+        .map(|name| name.trim().to_string());
+    document_type_update.mime_types = document_type_update.mime_types.map(normalize_mime_types);
+
+    let previous_document_type = repository::get_document_type_by_id(document_type_id);
+    let document_type = repository::update_document_type(document_type_id, document_type_update)
+        .ok_or_else(|| {
+            APIError::NotFound(format!(
+                "Document type with id: {} not found.",
+                document_type_id
+            ))
+        })?;
+    audit::service::record(
+        ic_cdk::caller(),
+        AuditAction::DocumentTypeUpdated,
+        AuditTarget::entity(document_type_id),
+        previous_document_type.map(|document_type| format!("{:?}", document_type)),
+        Some(format!("{:?}", document_type)),
+    );
+
+    Ok(document_type)
+}
+
+pub fn validate_document_type_proposal(
+    payload: DocumentTypeProposalPayload,
+) -> Result<String, String> {
+    match check_document_type_proposal(&payload) {
+        Ok(()) => Ok(match payload {
+            DocumentTypeProposalPayload::Create(create) => format!(
+                "Create the document type \"{}\": {}, MIME types: {}, max size: {} bytes, max files: {}, public: {}.",
+                create.name,
+                create.description,
+                create.mime_types.join(", "),
+                create.max_size,
+                create.max_files,
+                create.public
+            ),
+            DocumentTypeProposalPayload::Update(document_type_id, update) => {
+                let name = repository::get_document_type_by_id(document_type_id)
+                    .map(|document_type| document_type.name)
+                    .unwrap_or_default();
+                let mut changes = Vec::new();
+
+                if let Some(new_name) = &update.name {
+                    changes.push(format!("name: \"{}\"", new_name));
+                }
+                if let Some(description) = &update.description {
+                    changes.push(format!("description: \"{}\"", description));
+                }
+                if let Some(mime_types) = &update.mime_types {
+                    changes.push(format!("MIME types: {}", mime_types.join(", ")));
+                }
+                if let Some(max_size) = update.max_size {
+                    changes.push(format!("max size: {} bytes", max_size));
+                }
+                if let Some(max_files) = update.max_files {
+                    changes.push(format!("max files: {}", max_files));
+                }
+                if let Some(public) = update.public {
+                    changes.push(format!("public: {}", public));
+                }
+                if let Some(active) = update.active {
+                    changes.push(format!("active: {}", active));
+                }
+
+                format!(
+                    "Update the document type \"{}\" (id {}) with values: {}.",
+                    name,
+                    document_type_id,
+                    changes.join(", ")
+                )
+            }
+        }),
+        Err(e) => Err(format!("Unable to validate document type proposal: {}", e)),
+    }
+}
+
+pub fn execute_document_type_proposal(
+    payload: DocumentTypeProposalPayload,
+) -> Result<DocumentType, APIError> {
+    match payload {
+        DocumentTypeProposalPayload::Create(create) => create_document_type(create),
+        DocumentTypeProposalPayload::Update(document_type_id, update) => {
+            update_document_type(document_type_id, update)
+        }
+    }
+}
+
+fn check_document_type_proposal(payload: &DocumentTypeProposalPayload) -> Result<(), APIError> {
+    match payload {
+        DocumentTypeProposalPayload::Create(create) => check_document_type_create(create),
+        DocumentTypeProposalPayload::Update(document_type_id, update) => {
+            check_document_type_update(*document_type_id, update)
+        }
+    }
+}
+
+fn check_document_type_create(create: &DocumentTypeCreate) -> Result<(), APIError> {
+    check_document_type_name(None, &create.name)?;
+    check_document_type_limits(
+        &create.description,
+        &create.mime_types,
+        create.max_size,
+        create.max_files,
+    )
+}
+
+fn check_document_type_update(
+    document_type_id: DocumentTypeId,
+    update: &DocumentTypeUpdate,
+) -> Result<(), APIError> {
+    let current = get_document_type(document_type_id)?;
+
+    if let Some(name) = &update.name {
+        check_document_type_name(Some(document_type_id), name)?;
+    }
+
+    check_document_type_limits(
+        update.description.as_ref().unwrap_or(&current.description),
+        update.mime_types.as_ref().unwrap_or(&current.mime_types),
+        update.max_size.unwrap_or(current.max_size),
+        update.max_files.unwrap_or(current.max_files),
+    )?;
+
+    if update.active == Some(false) {
+        if let Some(step_config) =
+            repository::get_all_step_configs()
+                .into_iter()
+                .find(|step_config| {
+                    step_config
+                        .required_upload_files
+                        .contains(&document_type_id)
+                })
+        {
+            return Err(APIError::BadRequest(format!(
+                "The document type with id: {} is required by step id: {} in step phase id: {} and can't be deactivated.",
+                document_type_id, step_config.step_id, step_config.id
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+fn check_document_type_name(
+    document_type_id: Option<DocumentTypeId>,
+    name: &str,
+) -> Result<(), APIError> {
+    let name = name.trim();
+
+    if name.is_empty() || name.len() > MAX_DOCUMENT_TYPE_NAME_BYTES {
+        return Err(APIError::BadRequest(format!(
+            "The document type name must be between 1 and {} bytes.",
+            MAX_DOCUMENT_TYPE_NAME_BYTES
+        )));
+    }
+
+    let name_taken = repository::get_all_document_types()
+        .iter()
+        .any(|document_type| {
+            Some(document_type.id) != document_type_id
+                && document_type.name.eq_ignore_ascii_case(name)
+        });
+    if name_taken {
+        return Err(APIError::BadRequest(format!(
+            "Document type \"{}\" already exists.",
+            name
+        )));
+    }
+
+    Ok(())
+}
+
+fn check_document_type_limits(
+    description: &str,
+    mime_types: &[String],
+    max_size: u64,
+    max_files: u32,
+) -> Result<(), APIError> {
+    if description.len() > MAX_DOCUMENT_TYPE_DESCRIPTION_BYTES {
+        return Err(APIError::BadRequest(format!(
+            "The document type description can't exceed {} bytes.",
+            MAX_DOCUMENT_TYPE_DESCRIPTION_BYTES
+        )));
+    }
+
+    if mime_types.is_empty() || mime_types.len() > MAX_DOCUMENT_TYPE_MIME_TYPES {
+        return Err(APIError::BadRequest(format!(
+            "A document type must allow between 1 and {} MIME types.",
+            MAX_DOCUMENT_TYPE_MIME_TYPES
+        )));
+    }
+
+    for mime_type in mime_types {
+        let valid_mime_type = mime_type.len() <= MAX_MIME_TYPE_BYTES
+            && mime_type.split_once('/').is_some_and(|(kind, subtype)| {
+                !kind.is_empty()
+                    && !subtype.is_empty()
+                    && mime_type
+                        .bytes()
+                        .all(|c| c.is_ascii_graphic() && c != b';' && c != b',')
+            });
+        if !valid_mime_type {
+            return Err(APIError::BadRequest(format!(
+                "Invalid MIME type: \"{}\".",
+                mime_type
+            )));
+        }
+    }
+
+    if max_size == 0 {
+        return Err(APIError::BadRequest(
+            "The max size of a document type must be greater than 0.".to_string(),
+        ));
+    }
+
+    if max_files == 0 || max_files > MAX_FILES_PER_DOCUMENT_TYPE {
+        return Err(APIError::BadRequest(format!(
+            "The max files of a document type must be between 1 and {}.",
+            MAX_FILES_PER_DOCUMENT_TYPE
+        )));
+    }
+
+    Ok(())
+}
+
+fn default_document_type(
+    name: &str,
+    description: &str,
+    mime_types: &[&str],
+    max_size: u64,
+    max_files: u32,
+    public: bool,
+) -> DocumentTypeModel {
+    DocumentTypeModel {
+        name: name.to_string(),
+        description: description.to_string(),
+        mime_types: mime_types.iter().map(|mime| mime.to_string()).collect(),
+        max_size,
+        max_files,
+        public,
+        active: true,
+    }
+}
+
+fn normalize_mime_types(mime_types: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for mime_type in mime_types {
+        let mime_type = mime_type.to_lowercase();
+        if !normalized.contains(&mime_type) {
+            normalized.push(mime_type);
+        }
+    }
+    normalized
+}
+
+/// Creates the document types that replaced the former built-in ones, with their limits and
+/// the max files configured for them. The steps, step configs, stored files and access log
+/// entries referencing a former document type are then stored with its ID.
+pub fn init_document_types() {
+    if !repository::get_all_document_types().is_empty() {
+        return;
+    }
+
+    let default_document_types = [
+        (
+            PITCH_DECK_DOCUMENT_TYPE_ID,
+            default_document_type(
+                "Pitch Deck",
+                "Presentation of the project to the investors.",
+                &[PDF, PPTX],
+                50 * MB,
+                3,
+                false,
+            ),
+        ),
+        (
+            LOGO_DOCUMENT_TYPE_ID,
+            default_document_type(
+                "Logo",
+                "Logo of the project, displayed on its public page.",
+                &[PNG, JPEG, WEBP],
+                2 * MB,
+                1,
+                true,
+            ),
+        ),
+        (
+            COVER_PHOTO_DOCUMENT_TYPE_ID,
+            default_document_type(
+                "Cover Photo",
+                "Cover photo of the project, displayed on its public page.",
+                &[PNG, JPEG, WEBP],
+                10 * MB,
+                1,
+                true,
+            ),
+        ),
+        (
+            FINANCIAL_MODELS_DOCUMENT_TYPE_ID,
+            default_document_type(
+                "Financial Models",
+                "Financial projections of the project.",
+                &[XLSX, CSV, PDF],
+                20 * MB,
+                5,
+                false,
+            ),
+        ),
+        (
+            PRODUCT_DEMO_DOCUMENT_TYPE_ID,
+            default_document_type(
+                "Product Demo",
+                "Demonstration of the product, as a video or a document.",
+                &[MP4, WEBM, PDF],
+                100 * MB,
+                3,
+                false,
+            ),
+        ),
+        (
+            EXPENDITURE_PLAN_DOCUMENT_TYPE_ID,
+            default_document_type(
+                "Expenditure Plan",
+                "Planned use of the funds raised.",
+                &[XLSX, CSV, PDF],
+                20 * MB,
+                5,
+                false,
+            ),
+        ),
+    ];
+
+    for (document_type_id, model) in default_document_types.iter().cloned() {
+        repository::insert_document_type(document_type_id, model);
+    }
+
+    ic_cdk::println!(
+        "{} document types initialized successfully",
+        default_document_types.len()
+    );
+}
+
+pub fn create_file_upload(
+    caller_id: UserId,
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    step_id: StepId,
+    file_upload_create: FileUploadCreate,
+) -> Result<StoredFile, APIError> {
+    if repository::get_storage_config_model().provider != StorageProviderKind::OnChain {
+        return Err(APIError::BadRequest(
+            "The on-chain storage is not enabled, use generateUploadUrl instead.".to_string(),
+        ));
+    }
+
+    step::service::check_step_accepts_uploads(
+        project_id,
+        step_phase_id,
+        step_id,
+        std::slice::from_ref(&file_upload_create.document_type),
+    )?;
+    let sha256 = check_file_upload_create(&file_upload_create)?;
+    check_pending_upload_bytes(project_id, file_upload_create.size)?;
+    check_available_stable_memory(file_upload_create.size)?;
+
+    // A new upload of a document replaces its unfinished one, with its chunks.
+    let pending_file = step::service::create_pending_upload(
+        caller_id,
+        project_id,
+        step_phase_id,
+        step_id,
+        file_upload_create.document_type,
+        file_upload_create.document_id,
+        file_upload_create.filename.clone(),
+    )?;
+    let key = pending_file.s3_key.unwrap_or_default();
+    let mut key_entry = repository::get_file_key_entry(&key);
+
+    let file_id = repository::generate_file_id();
+    let stored_file = repository::insert_stored_file(
+        file_id,
+        StoredFileModel {
+            project_id,
+            step_phase_id,
+            step_id,
+            document_type: file_upload_create.document_type,
+            key: key.clone(),
+            filename: file_upload_create.filename,
+            mime_type: file_upload_create.mime_type.to_lowercase(),
+            size: file_upload_create.size,
+            sha256,
+            chunk_count: file_upload_create.size.div_ceil(MAX_FILE_CHUNK_BYTES) as u32,
+            created_by: caller_id,
+            created_at: api::time(),
+            committed_at: None,
+        },
+    );
+
+    key_entry.pending = Some(file_id);
+    repository::set_file_key_entry(key, key_entry);
+    audit::service::record(
+        caller_id,
+        AuditAction::FileUploadCreated,
+        AuditTarget::step(project_id, step_phase_id, step_id).with_entity(file_id),
+        None,
+        Some(format!(
+            "key: {}, size: {}, chunks: {}",
+            stored_file.key, stored_file.size, stored_file.chunk_count
+        )),
+    );
+
+    Ok(stored_file)
+}
+
+pub fn upload_file_chunk(
+    caller_id: UserId,
+    file_id: FileId,
+    chunk_index: u32,
+    content: Vec<u8>,
+) -> Result<StoredFile, APIError> {
+    let stored_file = get_pending_file_of_uploader(caller_id, file_id)?;
+
+    step::service::check_step_accepts_uploads(
+        stored_file.project_id,
+        stored_file.step_phase_id,
+        stored_file.step_id,
+        std::slice::from_ref(&stored_file.document_type),
+    )?;
+
+    if chunk_index >= stored_file.chunk_count {
+        return Err(APIError::BadRequest(format!(
+            "Chunk index {} is out of range, the file with id: {} has {} chunks.",
+            chunk_index, file_id, stored_file.chunk_count
+        )));
+    }
+
+    let expected_size = if chunk_index + 1 < stored_file.chunk_count {
+        MAX_FILE_CHUNK_BYTES
+    } else {
+        stored_file.size - (stored_file.chunk_count as u64 - 1) * MAX_FILE_CHUNK_BYTES
+    };
+
+    if content.len() as u64 != expected_size {
+        return Err(APIError::BadRequest(format!(
+            "Chunk {} of the file with id: {} must be {} bytes, got {} bytes.",
+            chunk_index,
+            file_id,
+            expected_size,
+            content.len()
+        )));
+    }
+
+    repository::put_file_chunk(file_id, chunk_index, content);
+
+    get_file(file_id)
+}
+
+pub fn commit_file_upload(caller_id: UserId, file_id: FileId) -> Result<StoredFile, APIError> {
+    let mut stored_file = get_pending_file_of_uploader(caller_id, file_id)?;
+
+    step::service::check_step_accepts_uploads(
+        stored_file.project_id,
+        stored_file.step_phase_id,
+        stored_file.step_id,
+        std::slice::from_ref(&stored_file.document_type),
+    )?;
+
+    let pending_file = step::service::get_project_step_by_id(
+        stored_file.project_id,
+        stored_file.step_phase_id,
+        stored_file.step_id,
+    )?
+    .upload_files
+    .into_iter()
+    .find(|file| file.s3_key.as_ref() == Some(&stored_file.key))
+    .ok_or_else(|| {
+        APIError::BadRequest(format!(
+            "The upload of the file with id: {} was replaced or deleted.",
+            file_id
+        ))
+    })?;
+
+    let uploaded_chunks = repository::count_file_chunks(file_id);
+    if uploaded_chunks != stored_file.chunk_count {
+        return Err(APIError::BadRequest(format!(
+            "The file with id: {} is incomplete, {} of {} chunks were uploaded.",
+            file_id, uploaded_chunks, stored_file.chunk_count
+        )));
+    }
+
+    let mut hasher = Sha256::new();
+    for chunk_index in 0..stored_file.chunk_count {
+        if let Some(chunk) = repository::get_file_chunk(file_id, chunk_index) {
+            hasher.update(chunk);
+        }
+    }
+    let sha256 = hex::encode(hasher.finalize());
+
+    if sha256 != stored_file.sha256 {
+        repository::remove_file_chunks(file_id);
+        return Err(APIError::BadRequest(format!(
+            "The SHA-256 of the file with id: {} is {} instead of {}, its chunks were discarded and must be uploaded again.",
+            file_id, sha256, stored_file.sha256
+        )));
+    }
+
+    stored_file.committed_at = Some(api::time());
+    let committed_file = repository::insert_stored_file(file_id, stored_file.clone());
+
+    let mut key_entry = repository::get_file_key_entry(&stored_file.key);
+    if let Some(replaced_file_id) = key_entry.committed.filter(|id| *id != file_id) {
+        repository::remove_stored_file(replaced_file_id);
+    }
+    key_entry.committed = Some(file_id);
+    key_entry.pending = key_entry.pending.filter(|id| *id != file_id);
+    repository::set_file_key_entry(stored_file.key.clone(), key_entry);
+
+    step::service::record_upload_file(
+        caller_id,
+        stored_file.project_id,
+        stored_file.step_phase_id,
+        stored_file.step_id,
+        UploadFile {
+            filename: Some(stored_file.filename),
+            status: Some(UploadStatus::Uploaded),
+            size: Some(stored_file.size),
+            sha256: Some(stored_file.sha256),
+            mime_type: Some(stored_file.mime_type),
+            confirmed_at: stored_file.committed_at,
+            rejection_reason: None,
+            ..pending_file
+        },
+    )?;
+    audit::service::record(
+        caller_id,
+        AuditAction::FileUploadCommitted,
+        AuditTarget::step(
+            committed_file.project_id,
+            committed_file.step_phase_id,
+            committed_file.step_id,
+        )
+        .with_entity(file_id),
+        None,
+        Some(format!(
+            "key: {}, sha256: {}",
+            committed_file.key, committed_file.sha256
+        )),
+    );
+
+    Ok(committed_file)
+}
+
+pub fn get_file_by_id(caller_id: UserId, file_id: FileId) -> Result<StoredFile, APIError> {
+    let stored_file = get_file(file_id)?;
+    check_is_project_owner_reviewer_or_admin(caller_id, stored_file.project_id)?;
+
+    Ok(stored_file)
+}
+
+pub async fn get_file_access_url(
+    caller_id: UserId,
+    file_id: FileId,
+) -> Result<FileAccessUrl, APIError> {
+    let stored_file = get_file(file_id)?;
+    check_is_project_owner_reviewer_or_admin(caller_id, stored_file.project_id)?;
+
+    if stored_file.committed_at.is_none() {
+        return Err(APIError::BadRequest(format!(
+            "The upload of the file with id: {} is not committed yet.",
+            file_id
+        )));
+    }
+
+    let base_url = on_chain_base_url(&repository::get_storage_config_model());
+    let access_url = issue_file_access_url(caller_id, file_id, &base_url).await?;
+
+    log_document_access(
+        caller_id,
+        stored_file.project_id,
+        stored_file.step_phase_id,
+        stored_file.step_id,
+        stored_file.document_type,
+        stored_file.key,
+    );
+
+    Ok(access_url)
+}
+
+/// Removes the on-chain files stored under the keys of deleted or replaced documents.
+pub fn remove_stored_files(keys: &[String]) {
+    for key in keys {
+        let key_entry = repository::get_file_key_entry(key);
+        for file_id in key_entry.committed.into_iter().chain(key_entry.pending) {
+            repository::remove_stored_file(file_id);
+        }
+        repository::set_file_key_entry(key.clone(), FileKeyEntryModel::default());
+    }
+}
+
+/// Records a download URL issued for a document in the access log of its project.
+pub fn log_document_access(
+    caller_id: UserId,
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    step_id: StepId,
+    document_type: DocumentTypeId,
+    key: String,
+) -> DocumentAccess {
+    repository::insert_document_access(
+        project_id,
+        DocumentAccessModel {
+            step_phase_id,
+            step_id,
+            document_type,
+            key,
+            accessed_by: caller_id,
+            accessed_at: api::time(),
+        },
+    )
+}
+
+pub fn get_document_access_log(project_id: ProjectId) -> Vec<DocumentAccess> {
+    repository::get_document_accesses(project_id)
+}
+
+/// Creates a short-lived URL of a committed file, private files are only served with its token.
+pub async fn issue_file_access_url(
+    caller_id: UserId,
+    file_id: FileId,
+    base_url: &str,
+) -> Result<FileAccessUrl, APIError> {
+    let (random_bytes,) = raw_rand().await.map_err(|(code, message)| {
+        APIError::InternalServerError(format!(
+            "Unable to generate the access token: RejectionCode: {:?}, Error: {}",
+            code, message
+        ))
+    })?;
+    let token = hex::encode(random_bytes);
+
+    let now = api::time();
+    let expires_at = now + DOWNLOAD_URL_DURATION * 1_000_000_000;
+
+    repository::remove_expired_file_access_tokens(now);
+    repository::put_file_access_token(
+        token.clone(),
+        FileAccessTokenModel {
+            file_id,
+            issued_to: caller_id,
+            expires_at,
+        },
+    );
+
+    Ok(FileAccessUrl {
+        file_id,
+        url: format!(
+            "{}{}{}?token={}",
+            base_url.trim_end_matches('/'),
+            FILE_HTTP_PATH_PREFIX,
+            file_id,
+            token
+        ),
+        expires_at,
+    })
+}
+
+/// Serves the first chunk of a committed file, the HTTP gateway streams the other ones with
+/// `http_request_streaming_callback`.
+pub fn serve_file(request: HttpGatewayRequest) -> HttpGatewayResponse {
+    let method = request.method.to_uppercase();
+    if method != "GET" && method != "HEAD" {
+        return text_response(405, "Method not allowed.");
+    }
+
+    let (path, query) = request
+        .url
+        .split_once('?')
+        .unwrap_or((request.url.as_str(), ""));
+
+    let file_id = match path
+        .strip_prefix(FILE_HTTP_PATH_PREFIX)
+        .and_then(|id| id.parse::<FileId>().ok())
+    {
+        Some(file_id) => file_id,
+        None => return text_response(404, "Not found."),
+    };
+
+    let access_token = query
+        .split('&')
+        .find_map(|param| param.strip_prefix("token="))
+        .map(|token| token.to_string());
+
+    let stored_file = match repository::get_stored_file_model(file_id) {
+        Some(stored_file) if stored_file.committed_at.is_some() => stored_file,
+        _ => return text_response(404, "Not found."),
+    };
+
+    let public = is_public_document_type(stored_file.document_type);
+    if !public && !has_file_access(file_id, access_token.as_deref()) {
+        return text_response(403, "The access token is missing, invalid or expired.");
+    }
+
+    let headers = vec![
+        ("Content-Type".to_string(), stored_file.mime_type.clone()),
+        ("Content-Length".to_string(), stored_file.size.to_string()),
+        (
+            "Content-Disposition".to_string(),
+            format!(
+                "{}; filename=\"{}\"",
+                if public { "inline" } else { "attachment" },
+                stored_file.filename
+            ),
+        ),
+        (
+            "Cache-Control".to_string(),
+            if public {
+                "public, max-age=3600".to_string()
+            } else {
+                "private, no-store".to_string()
+            },
+        ),
+        ("X-Content-Type-Options".to_string(), "nosniff".to_string()),
+    ];
+
+    if method == "HEAD" {
+        return HttpGatewayResponse {
+            status_code: 200,
+            headers,
+            body: ByteBuf::new(),
+            streaming_strategy: None,
+        };
+    }
+
+    let body = repository::get_file_chunk(file_id, 0).unwrap_or_default();
+    let streaming_strategy =
+        next_streaming_token(file_id, 0, &stored_file, access_token).map(|token| {
+            StreamingStrategy::Callback {
+                callback: StreamingCallback::new(
+                    api::id(),
+                    "http_request_streaming_callback".to_string(),
+                ),
+                token,
+            }
+        });
+
+    HttpGatewayResponse {
+        status_code: 200,
+        headers,
+        body: ByteBuf::from(body),
+        streaming_strategy,
+    }
+}
+
+pub fn serve_file_chunk(
+    token: StreamingCallbackToken,
+) -> Result<StreamingCallbackHttpResponse, APIError> {
+    let stored_file = repository::get_stored_file_model(token.file_id)
+        .filter(|stored_file| stored_file.committed_at.is_some())
+        .ok_or_else(|| APIError::NotFound(format!("File with id: {} not found.", token.file_id)))?;
+
+    let public = is_public_document_type(stored_file.document_type);
+    if !public && !has_file_access(token.file_id, token.access_token.as_deref()) {
+        return Err(APIError::Forbidden(
+            "The access token is missing, invalid or expired.".to_string(),
+        ));
+    }
+
+    let body = repository::get_file_chunk(token.file_id, token.chunk_index).ok_or_else(|| {
+        APIError::NotFound(format!(
+            "Chunk {} of the file with id: {} not found.",
+            token.chunk_index, token.file_id
+        ))
+    })?;
+
+    Ok(StreamingCallbackHttpResponse {
+        body: ByteBuf::from(body),
+        token: next_streaming_token(
+            token.file_id,
+            token.chunk_index,
+            &stored_file,
+            token.access_token,
+        ),
+    })
+}
+
+fn next_streaming_token(
+    file_id: FileId,
+    chunk_index: u32,
+    stored_file: &StoredFileModel,
+    access_token: Option<String>,
+) -> Option<StreamingCallbackToken> {
+    if chunk_index + 1 < stored_file.chunk_count {
+        Some(StreamingCallbackToken {
+            file_id,
+            chunk_index: chunk_index + 1,
+            access_token,
+        })
+    } else {
+        None
+    }
+}
+
+fn is_public_document_type(document_type_id: DocumentTypeId) -> bool {
+    repository::get_document_type_by_id(document_type_id)
+        .is_some_and(|document_type| document_type.public)
+}
+
+fn has_file_access(file_id: FileId, access_token: Option<&str>) -> bool {
+    access_token
+        .and_then(repository::get_file_access_token)
+        .map(|token| token.file_id == file_id && token.expires_at > api::time())
+        .unwrap_or(false)
+}
+
+fn text_response(status_code: u16, message: &str) -> HttpGatewayResponse {
+    HttpGatewayResponse {
+        status_code,
+        headers: vec![("Content-Type".to_string(), "text/plain".to_string())],
+        body: ByteBuf::from(message.as_bytes().to_vec()),
+        streaming_strategy: None,
+    }
+}
+
+// Responses depend on the access token and can't be certified, so the files are served from
+// the raw domain of the canister unless another endpoint is configured.
+fn on_chain_base_url(config: &StorageConfigModel) -> String {
+    if config.provider == StorageProviderKind::OnChain {
+        config.endpoint.clone()
+    } else {
+        format!("https://{}.raw.icp0.io", api::id())
+    }
+}
+
+fn get_file(file_id: FileId) -> Result<StoredFile, APIError> {
+    repository::get_stored_file(file_id)
+        .ok_or_else(|| APIError::NotFound(format!("File with id: {} not found.", file_id)))
+}
+
+fn get_pending_file_of_uploader(
+    caller_id: UserId,
+    file_id: FileId,
+) -> Result<StoredFileModel, APIError> {
+    let stored_file = repository::get_stored_file_model(file_id)
+        .ok_or_else(|| APIError::NotFound(format!("File with id: {} not found.", file_id)))?;
+
+    if stored_file.created_by != caller_id {
+        return Err(APIError::Forbidden(
+            "Only the uploader of the file can upload its chunks.".to_string(),
+        ));
+    }
+
+    if stored_file.committed_at.is_some() {
+        return Err(APIError::BadRequest(format!(
+            "The upload of the file with id: {} is already committed.",
+            file_id
+        )));
+    }
+
+    Ok(stored_file)
+}
+
+/// Checks the announced file against the limits of its document type and returns its
+/// normalized hash.
+fn check_file_upload_create(file_upload_create: &FileUploadCreate) -> Result<String, APIError> {
+    let filename = &file_upload_create.filename;
+
+    if filename.trim().is_empty() || filename.len() > MAX_FILENAME_BYTES {
+        return Err(APIError::BadRequest(format!(
+            "The filename must be between 1 and {} bytes.",
+            MAX_FILENAME_BYTES
+        )));
+    }
+
+    // The filename ends up in the Content-Disposition header of the served file.
+    if filename
+        .chars()
+        .any(|c| c.is_control() || matches!(c, '"' | '/' | '\\'))
+    {
+        return Err(APIError::BadRequest(format!(
+            "Invalid filename: {:?}.",
+            filename
+        )));
+    }
+
+    check_file_metadata(
+        file_upload_create.document_type,
+        file_upload_create.size,
+        &file_upload_create.mime_type,
+        &file_upload_create.sha256,
+    )
+}
+
+/// Checks the size and MIME type of a file against the limits of its document type and
+/// returns its normalized hash.
+pub fn check_file_metadata(
+    document_type_id: DocumentTypeId,
+    size: u64,
+    mime_type: &str,
+    sha256: &str,
+) -> Result<String, APIError> {
+    let document_type = get_document_type(document_type_id)?;
+
+    let normalized_mime_type = mime_type.to_lowercase();
+    if normalized_mime_type.len() > MAX_MIME_TYPE_BYTES
+        || !document_type.mime_types.contains(&normalized_mime_type)
+    {
+        return Err(APIError::BadRequest(format!(
+            "The MIME type {} is not allowed for {}, expected one of: {}.",
+            mime_type,
+            document_type.name,
+            document_type.mime_types.join(", ")
+        )));
+    }
+
+    if size == 0 || size > document_type.max_size {
+        return Err(APIError::BadRequest(format!(
+            "The size of a {} file must be between 1 and {} bytes.",
+            document_type.name, document_type.max_size
+        )));
+    }
+
+    let sha256 = sha256.to_lowercase();
+    if sha256.len() != 64 || !sha256.bytes().all(|c| c.is_ascii_hexdigit()) {
+        return Err(APIError::BadRequest(
+            "The SHA-256 must be 64 hexadecimal characters.".to_string(),
+        ));
+    }
+
+    Ok(sha256)
+}
+
+/// Checks a confirmed file against what the storage reports about the object, the size and
+/// SHA-256 the storage doesn't report are left unchecked.
+pub(crate) fn check_stored_object(
+    object: &ObjectHead,
+    size: u64,
+    sha256: &str,
+) -> Result<(), APIError> {
+    if let Some(stored_size) = object.size.filter(|&stored_size| stored_size != size) {
+        return Err(APIError::BadRequest(format!(
+            "The stored file is {} bytes, not {} bytes.",
+            stored_size, size
+        )));
+    }
+
+    if object
+        .sha256
+        .as_deref()
+        .is_some_and(|stored_sha256| stored_sha256 != sha256)
+    {
+        return Err(APIError::BadRequest(
+            "The SHA-256 of the stored file doesn't match.".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+// Unfinished uploads hold their chunks until they are committed, replaced or deleted.
+fn check_pending_upload_bytes(project_id: ProjectId, size: u64) -> Result<(), APIError> {
+    let pending_bytes = repository::get_pending_upload_bytes(project_id);
+
+    if pending_bytes + size > MAX_PENDING_UPLOAD_BYTES_PER_PROJECT {
+        return Err(APIError::BadRequest(format!(
+            "The project with id: {} already has {} bytes of unfinished uploads, commit or delete them before uploading {} more bytes.",
+            project_id, pending_bytes, size
+        )));
+    }
+
+    Ok(())
+}
+
+fn check_available_stable_memory(size: u64) -> Result<(), APIError> {
+    let max_stable_memory_size = repository::get_canister_config()
+        .max_stable_memory_size
+        .unwrap_or(0);
+
+    if max_stable_memory_size == 0 {
+        return Ok(());
+    }
+
+    let used_stable_memory = api::stable::stable64_size() * WASM_PAGE_SIZE_IN_BYTES as u64;
+    if used_stable_memory + size > max_stable_memory_size {
+        return Err(APIError::BadRequest(format!(
+            "Not enough stable memory left to store {} bytes.",
+            size
+        )));
+    }
+
+    Ok(())
+}
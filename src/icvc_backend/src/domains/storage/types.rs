@@ -0,0 +1,207 @@
+use candid::{define_function, CandidType, Deserialize, Principal};
+use serde::Serialize;
+use serde_bytes::ByteBuf;
+use std::fmt;
+
+use crate::{DocumentId, ProjectId, StepId, StepPhaseId};
+
+pub type FileId = u64;
+pub type DocumentTypeId = u64;
+
+/// Service creating the URLs used to upload and download the project documents.
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum StorageProviderKind {
+    /// S3 compatible bucket, the URLs are presigned with AWS Signature Version 4 by this
+    /// canister.
+    S3Compatible,
+    /// Local HTTP server accepting unsigned uploads, the URLs point directly to it.
+    MockHttp,
+    /// Asset canister serving the documents, the endpoint is the URL of the canister.
+    AssetCanister,
+    /// Chunks stored in the stable memory of this canister, the endpoint is the raw URL of
+    /// the canister the documents are served from.
+    OnChain,
+}
+
+impl fmt::Display for StorageProviderKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            StorageProviderKind::S3Compatible => write!(f, "S3 Compatible"),
+            StorageProviderKind::MockHttp => write!(f, "Mock HTTP"),
+            StorageProviderKind::AssetCanister => write!(f, "Asset Canister"),
+            StorageProviderKind::OnChain => write!(f, "On Chain"),
+        }
+    }
+}
+
+/// Storage configuration. The secret access key is never returned, only whether it is set.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StorageConfig {
+    pub provider: StorageProviderKind,
+    pub endpoint: String,
+    pub bucket: String,
+    pub path_prefix: String,
+    pub region: String,
+    pub access_key_id: Option<String>,
+    pub secret_access_key_set: bool,
+}
+
+/// Update of the storage configuration, only the fields set are changed. An empty
+/// `access_key_id` or `secret_access_key` removes it. Proposals can't set `secret_access_key`,
+/// and the node operators of the subnet can read it in the canister state.
+#[derive(CandidType, Deserialize, Clone, Default)]
+pub struct StorageConfigUpdate {
+    pub provider: Option<StorageProviderKind>,
+    pub endpoint: Option<String>,
+    pub bucket: Option<String>,
+    pub path_prefix: Option<String>,
+    pub region: Option<String>,
+    pub access_key_id: Option<String>,
+    pub secret_access_key: Option<String>,
+}
+
+/// Type of the documents uploaded to the steps, with the size, MIME and count limits of its
+/// files. Public documents are served without an access token. Inactive types can't be
+/// required by a step anymore, the documents already uploaded stay.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DocumentType {
+    pub id: DocumentTypeId,
+    pub name: String,
+    pub description: String,
+    pub mime_types: Vec<String>,
+    pub max_size: u64,
+    pub max_files: u32,
+    pub public: bool,
+    pub active: bool,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DocumentTypeCreate {
+    pub name: String,
+    pub description: String,
+    pub mime_types: Vec<String>,
+    pub max_size: u64,
+    pub max_files: u32,
+    pub public: bool,
+}
+
+/// Update of a document type, only the fields set are changed.
+#[derive(CandidType, Deserialize, Clone, Debug, Default)]
+pub struct DocumentTypeUpdate {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub mime_types: Option<Vec<String>>,
+    pub max_size: Option<u64>,
+    pub max_files: Option<u32>,
+    pub public: Option<bool>,
+    pub active: Option<bool>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum DocumentTypeProposalPayload {
+    Create(DocumentTypeCreate),
+    Update(DocumentTypeId, DocumentTypeUpdate),
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum S3Method {
+    PUT,
+    GET,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct PresignedUrlResponse {
+    pub key: String,
+    pub url: String,
+}
+
+/// Announces a file uploaded in chunks to the on-chain storage. `sha256` is the hex encoded
+/// hash of the whole file, checked once all the chunks are uploaded. The file is a new
+/// document, or a new version of the document `document_id`.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct FileUploadCreate {
+    pub document_type: DocumentTypeId,
+    pub filename: String,
+    pub mime_type: String,
+    pub size: u64,
+    pub sha256: String,
+    pub document_id: Option<DocumentId>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StoredFile {
+    pub id: FileId,
+    pub project_id: ProjectId,
+    pub step_phase_id: StepPhaseId,
+    pub step_id: StepId,
+    pub document_type: DocumentTypeId,
+    pub key: String,
+    pub filename: String,
+    pub mime_type: String,
+    pub size: u64,
+    pub sha256: String,
+    pub chunk_count: u32,
+    pub uploaded_chunks: u32,
+    pub created_by: Principal,
+    pub created_at: u64,
+    pub committed_at: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct FileAccessUrl {
+    pub file_id: FileId,
+    pub url: String,
+    pub expires_at: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct HttpGatewayRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: ByteBuf,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct HttpGatewayResponse {
+    pub status_code: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: ByteBuf,
+    pub streaming_strategy: Option<StreamingStrategy>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StreamingCallbackToken {
+    pub file_id: FileId,
+    pub chunk_index: u32,
+    pub access_token: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StreamingCallbackHttpResponse {
+    pub body: ByteBuf,
+    pub token: Option<StreamingCallbackToken>,
+}
+
+define_function!(pub StreamingCallback : (StreamingCallbackToken) -> (StreamingCallbackHttpResponse) query);
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum StreamingStrategy {
+    Callback {
+        callback: StreamingCallback,
+        token: StreamingCallbackToken,
+    },
+}
+
+/// Download URL issued for a project document, kept in the access log of the project.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DocumentAccess {
+    pub project_id: ProjectId,
+    pub index: u64,
+    pub step_phase_id: StepPhaseId,
+    pub step_id: StepId,
+    pub document_type: DocumentTypeId,
+    pub key: String,
+    pub accessed_by: Principal,
+    pub accessed_at: u64,
+}
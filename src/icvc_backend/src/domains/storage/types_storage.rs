@@ -0,0 +1,192 @@
+use candid::{CandidType, Deserialize, Principal};
+use candid::{Decode, Encode};
+use ic_stable_structures::{storable::Bound, Storable};
+use std::borrow::Cow;
+
+use crate::{ProjectId, StepId, StepPhaseId};
+
+use super::constants::{
+    COVER_PHOTO_DOCUMENT_TYPE_ID, DEFAULT_STORAGE_BUCKET, DEFAULT_STORAGE_ENDPOINT,
+    DEFAULT_STORAGE_PATH_PREFIX, DEFAULT_STORAGE_REGION, EXPENDITURE_PLAN_DOCUMENT_TYPE_ID,
+    FINANCIAL_MODELS_DOCUMENT_TYPE_ID, LOGO_DOCUMENT_TYPE_ID, PITCH_DECK_DOCUMENT_TYPE_ID,
+    PRODUCT_DEMO_DOCUMENT_TYPE_ID,
+};
+use super::types::{DocumentTypeId, FileId, StorageProviderKind};
+
+// No Debug on purpose, the auth secret must never end up in the canister logs.
+#[derive(CandidType, Deserialize, Clone)]
+pub struct StorageConfigModel {
+    pub provider: StorageProviderKind,
+    pub endpoint: String,
+    pub bucket: String,
+    pub path_prefix: String,
+    /// Region of the S3 compatible bucket, in the signature scope.
+    pub region: String,
+    pub access_key_id: Option<String>,
+    /// Kept in plain text in the canister state, the node operators of the subnet can read it.
+    /// Only use a key restricted to the objects under the bucket and path prefix.
+    pub secret_access_key: Option<String>,
+}
+
+impl Storable for StorageConfigModel {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Default for StorageConfigModel {
+    fn default() -> Self {
+        Self {
+            provider: StorageProviderKind::S3Compatible,
+            endpoint: DEFAULT_STORAGE_ENDPOINT.to_string(),
+            bucket: DEFAULT_STORAGE_BUCKET.to_string(),
+            path_prefix: DEFAULT_STORAGE_PATH_PREFIX.to_string(),
+            region: DEFAULT_STORAGE_REGION.to_string(),
+            access_key_id: None,
+            secret_access_key: None,
+        }
+    }
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StoredFileModel {
+    pub project_id: ProjectId,
+    pub step_phase_id: StepPhaseId,
+    pub step_id: StepId,
+    pub document_type: DocumentTypeId,
+    pub key: String,
+    pub filename: String,
+    pub mime_type: String,
+    pub size: u64,
+    pub sha256: String,
+    pub chunk_count: u32,
+    pub created_by: Principal,
+    pub created_at: u64,
+    pub committed_at: Option<u64>,
+}
+
+impl Storable for StoredFileModel {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+/// Files stored under an object key: the one served and the one being uploaded to replace it.
+#[derive(CandidType, Deserialize, Clone, Debug, Default)]
+pub struct FileKeyEntryModel {
+    pub committed: Option<FileId>,
+    pub pending: Option<FileId>,
+}
+
+impl Storable for FileKeyEntryModel {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct FileAccessTokenModel {
+    pub file_id: FileId,
+    pub issued_to: Principal,
+    pub expires_at: u64,
+}
+
+impl Storable for FileAccessTokenModel {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DocumentAccessModel {
+    pub step_phase_id: StepPhaseId,
+    pub step_id: StepId,
+    pub document_type: DocumentTypeId,
+    pub key: String,
+    pub accessed_by: Principal,
+    pub accessed_at: u64,
+}
+
+impl Storable for DocumentAccessModel {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DocumentTypeModel {
+    pub name: String,
+    pub description: String,
+    pub mime_types: Vec<String>,
+    pub max_size: u64,
+    pub max_files: u32,
+    pub public: bool,
+    pub active: bool,
+}
+
+impl Storable for DocumentTypeModel {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+/// Document types of the steps before they became configurable. They are only decoded from
+/// the models stored at that time, and replaced by the document types created in their place.
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum LegacyDocumentType {
+    PitchDeck,
+    Logo,
+    CoverPhoto,
+    FinancialModels,
+    ProductDemo,
+    ExpenditurePlan,
+}
+
+impl LegacyDocumentType {
+    pub fn document_type_id(&self) -> DocumentTypeId {
+        match self {
+            LegacyDocumentType::PitchDeck => PITCH_DECK_DOCUMENT_TYPE_ID,
+            LegacyDocumentType::Logo => LOGO_DOCUMENT_TYPE_ID,
+            LegacyDocumentType::CoverPhoto => COVER_PHOTO_DOCUMENT_TYPE_ID,
+            LegacyDocumentType::FinancialModels => FINANCIAL_MODELS_DOCUMENT_TYPE_ID,
+            LegacyDocumentType::ProductDemo => PRODUCT_DEMO_DOCUMENT_TYPE_ID,
+            LegacyDocumentType::ExpenditurePlan => EXPENDITURE_PLAN_DOCUMENT_TYPE_ID,
+        }
+    }
+}
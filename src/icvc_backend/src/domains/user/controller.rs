@@ -1,4 +1,7 @@
-use crate::{utils::authenticator::check_is_owner_or_governance_id, APIError, User, UserCreate, UserId, UserUpdate};
+use crate::{
+    utils::authenticator::check_is_owner_or_governance_id, APIError, AdminProposalPayload, User,
+    UserCreate, UserId, UserUpdate,
+};
 
 use super::service;
 
@@ -65,3 +68,29 @@ pub fn delete_user(user_id: UserId) -> Result<User, APIError> {
     check_is_owner_or_governance_id(caller_id)?;
     service::delete_user(user_id)
 }
+
+#[ic_cdk::update]
+fn validate_admin_proposal(payload: AdminProposalPayload) -> Result<String, String> {
+    let caller_id = ic_cdk::caller();
+
+    match check_is_owner_or_governance_id(caller_id) {
+        Ok(_) => service::validate_admin_proposal(payload),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+#[ic_cdk::update]
+fn execute_admin_proposal(payload: AdminProposalPayload) {
+    let caller_id = ic_cdk::caller();
+
+    match check_is_owner_or_governance_id(caller_id) {
+        Ok(_) => {
+            if let Err(err) = service::execute_admin_proposal(payload) {
+                ic_cdk::println!("Unable to execute admin proposal: {}", err.to_string());
+            }
+        }
+        Err(err) => {
+            ic_cdk::println!("Unable to execute admin proposal: {}", err.to_string());
+        }
+    }
+}
@@ -1,8 +1,23 @@
-use crate::{repository, APIError, User, UserCreate, UserId, UserUpdate};
+use crate::{
+    domains::audit::{
+        self,
+        types::{AuditAction, AuditTarget},
+    },
+    repository, APIError, AdminProposalPayload, User, UserCreate, UserId, UserUpdate,
+};
 
 pub fn add_admin(user_create: UserCreate) -> Result<User, APIError> {
     match repository::save_admin(user_create.clone()) {
-        Some(user) => Ok(user),
+        Some(user) => {
+            audit::service::record(
+                ic_cdk::caller(),
+                AuditAction::AdminAdded,
+                AuditTarget::entity(user.user_id),
+                None,
+                Some(user.name.clone()),
+            );
+            Ok(user)
+        }
         None => Err(APIError::BadRequest(format!(
             "Failed to add admin. Admin {} already exists.",
             user_create.user_id
@@ -11,8 +26,19 @@ pub fn add_admin(user_create: UserCreate) -> Result<User, APIError> {
 }
 
 pub fn update_user(user_id: UserId, update_user: UserUpdate) -> Result<User, APIError> {
+    let previous_name = repository::get_user_by_id(user_id).map(|user| user.name);
+
     match repository::update_user(user_id, update_user) {
-        Some(user) => Ok(user),
+        Some(user) => {
+            audit::service::record(
+                ic_cdk::caller(),
+                AuditAction::UserUpdated,
+                AuditTarget::entity(user_id),
+                previous_name,
+                Some(user.name.clone()),
+            );
+            Ok(user)
+        }
         None => Err(APIError::NotFound(format!(
             "User with id {} not found.",
             user_id
@@ -22,7 +48,16 @@ pub fn update_user(user_id: UserId, update_user: UserUpdate) -> Result<User, API
 
 pub fn delete_user(user_id: UserId) -> Result<User, APIError> {
     match repository::delete_user(user_id) {
-        Some(user) => Ok(user),
+        Some(user) => {
+            audit::service::record(
+                ic_cdk::caller(),
+                AuditAction::UserDeleted,
+                AuditTarget::entity(user_id),
+                Some(user.name.clone()),
+                None,
+            );
+            Ok(user)
+        }
         None => Err(APIError::NotFound(format!(
             "User with id {} not found.",
             user_id
@@ -35,3 +70,59 @@ pub fn get_all_admins() -> Result<Vec<User>, APIError> {
 
     Ok(users)
 }
+
+pub fn validate_admin_proposal(payload: AdminProposalPayload) -> Result<String, String> {
+    match check_admin_proposal(&payload) {
+        Ok(()) => Ok(match payload {
+            AdminProposalPayload::Add(user_create) => format!(
+                "Add \"{}\" ({}) as an ICVC admin.",
+                user_create.name, user_create.user_id
+            ),
+            AdminProposalPayload::Remove(user_id) => {
+                let name = repository::get_user_by_id(user_id)
+                    .map(|user| user.name)
+                    .unwrap_or_default();
+                format!("Remove \"{}\" ({}) from the ICVC admins.", name, user_id)
+            }
+        }),
+        Err(e) => Err(format!("Unable to validate admin proposal: {}", e)),
+    }
+}
+
+pub fn execute_admin_proposal(payload: AdminProposalPayload) -> Result<User, APIError> {
+    check_admin_proposal(&payload)?;
+
+    match payload {
+        AdminProposalPayload::Add(user_create) => add_admin(user_create),
+        AdminProposalPayload::Remove(user_id) => delete_user(user_id),
+    }
+}
+
+fn check_admin_proposal(payload: &AdminProposalPayload) -> Result<(), APIError> {
+    match payload {
+        AdminProposalPayload::Add(user_create) => {
+            if user_create.name.trim().is_empty() {
+                return Err(APIError::BadRequest(
+                    "The admin name can't be empty.".to_string(),
+                ));
+            }
+            if repository::get_user_by_id(user_create.user_id).is_some() {
+                return Err(APIError::BadRequest(format!(
+                    "User {} already exists.",
+                    user_create.user_id
+                )));
+            }
+        }
+        AdminProposalPayload::Remove(user_id) => match repository::get_user_by_id(*user_id) {
+            Some(user) if user.is_admin => {}
+            _ => {
+                return Err(APIError::NotFound(format!(
+                    "Admin with id {} not found.",
+                    user_id
+                )))
+            }
+        },
+    }
+
+    Ok(())
+}
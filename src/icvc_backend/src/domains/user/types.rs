@@ -19,3 +19,9 @@ pub struct User {
     pub name: String,
     pub is_admin: bool,
 }
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum AdminProposalPayload {
+    Add(UserCreate),
+    Remove(UserId),
+}
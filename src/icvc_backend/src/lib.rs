@@ -6,8 +6,12 @@ use domains::canister_management::types::InitArgs;
 use domains::canister_management::types_storage::CanisterConfig;
 use domains::core;
 use domains::icvc_configuration;
-use ic_cdk::api::management_canister::http_request::{HttpResponse, TransformArgs};
+use domains::step;
+use domains::storage;
+use serde_bytes::ByteBuf;
 
+use crate::domains::appeal::types::*;
+use crate::domains::audit::types::*;
 use crate::domains::canister_management::types::*;
 use crate::domains::core::types::*;
 use crate::domains::icvc_configuration::types::*;
@@ -16,14 +20,17 @@ use crate::domains::project::types::*;
 use crate::domains::sns_integration::types::*;
 use crate::domains::sns_integration::types_sns_governance::*;
 use crate::domains::step::types::*;
+use crate::domains::storage::types::*;
 use crate::domains::user::types::*;
 use candid::Principal;
+use ic_cdk::api::management_canister::http_request::{HttpResponse, TransformArgs};
 
 /// Initializes the canister with the caller as the owner and sets up the default configuration.
 ///
 /// This function is called once when the canister is first deployed. It performs the following actions:
 /// - Sets the caller as the owner of the canister.
 /// - Configures the canister with the owner's principal ID.
+/// - Creates the default document types.
 /// - Initializes the default step phases configuration.
 /// - Starts a timer to update projects every x seconds.
 ///
@@ -53,10 +60,12 @@ fn init(init_args: Option<InitArgs>) {
         .expect("Initialization failed: Unable to set canister config!");
 
     icvc_configuration::service::ini_default_categories();
+    storage::service::init_document_types();
     icvc_configuration::service::init_default_step_phases_config();
 
     let config = icvc_configuration::service::get_icvc_config();
     core::service::start_update_projects_timer(config.projects_update_timer_interval);
+    core::service::restart_refresh_sns_neurons_timer();
 
     ic_cdk::println!(
         "Canister init successful! Owner's Principal ID: {:?}",
@@ -68,15 +77,22 @@ fn init(init_args: Option<InitArgs>) {
 ///
 /// This function is called after the canister has been upgraded. It performs the following actions:
 /// - Logs a message indicating that the timer will be restarted.
-/// - Restarts the timer to update projects every x seconds.
+/// - Rebuilds the step phase deadline index if it is empty.
+/// - Creates the document types in place of the former built-in ones.
+/// - Gives an ID to the documents uploaded before a step could hold several files of a type.
+/// - Restarts the timer to update projects every x seconds, unless the scheduler was paused.
+/// - Restarts the timer that refreshes the SNS neurons.
 ///
 /// This ensures that any necessary periodic tasks continue to run after the canister upgrade.
 #[ic_cdk::post_upgrade]
 fn post_upgrade() {
     ic_cdk::println!("Restart timer.");
-    let config = icvc_configuration::service::get_icvc_config();
 
-    core::service::start_update_projects_timer(config.projects_update_timer_interval);
+    core::service::init_step_phase_deadline_index();
+    storage::service::init_document_types();
+    step::service::init_document_ids();
+    core::service::restart_update_projects_timer();
+    core::service::restart_refresh_sns_neurons_timer();
 }
 
 ic_cdk::export_candid!();
@@ -1,24 +1,51 @@
+use crate::domains::appeal::types::{
+    Appeal, AppealCreate, AppealDecision, AppealOutcome, AppealStatus,
+};
+use crate::domains::appeal::types_storage::AppealModel;
+use crate::domains::audit::types::{AuditEntry, AuditEntryId, AuditLogPage};
+use crate::domains::audit::types_storage::AuditEntryModel;
 use crate::domains::canister_management::types::CanisterConfigUpdate;
 use crate::domains::canister_management::types_storage::CanisterConfig;
 use crate::domains::core::types_storage::CompositeKey;
 use crate::domains::icvc_configuration::types::{
-    Category, CategoryCreate, StepConfig, StepConfigCreate, StepPhaseConfig, StepPhaseConfigCreate,
+    Category, CategoryCreate, ProposalTemplate, StepConfig, StepConfigCreate, StepPhaseConfig,
+    StepPhaseConfigCreate, VoteRules,
 };
 use crate::domains::icvc_configuration::types_storage::{
     CategoryModel, ICVCConfig, StepConfigModel, StepPhaseConfigModel,
 };
-use crate::domains::project::types::{Project, ProjectCreate, ProjectStatus, ProjectUpdate};
-use crate::domains::project::types_storage::ProjectModel;
+use crate::domains::project::types::{
+    DocumentId, Project, ProjectCreate, ProjectReviewer, ProjectStatus, ProjectUpdate,
+};
+use crate::domains::project::types_storage::{ProjectModel, ProjectReviewerModel};
+use crate::domains::sns_integration::types::{
+    PendingProposal, ProjectBallot, ProposalExecutionReceipt, SnsNeuron,
+};
+use crate::domains::sns_integration::types_storage::{
+    BallotModel, PendingProposalModel, ProposalExecutionReceiptModel, SnsNeuronIdKey,
+    SnsNeuronModel,
+};
+use crate::domains::step::types::ProposalId;
 use crate::domains::step::types_storage::{
-    StepModel, StepPhaseGradeResultModel, StepPhaseModel, StepPhaseVoteResultModel,
+    StepModel, StepPhaseAdminActionModel, StepPhaseGradeResultModel, StepPhaseModel,
+    StepPhaseVoteResultModel, UploadFileModel,
+};
+use crate::domains::storage::types::{
+    DocumentAccess, DocumentType, DocumentTypeId, DocumentTypeUpdate, FileId, StorageConfig,
+    StorageConfigUpdate, StoredFile,
+};
+use crate::domains::storage::types_storage::{
+    DocumentAccessModel, DocumentTypeModel, FileAccessTokenModel, FileKeyEntryModel,
+    StorageConfigModel, StoredFileModel,
 };
 use crate::domains::user::types::{User, UserCreate, UserId, UserUpdate};
 
 use crate::domains::user::types_storage::UserModel;
 use crate::{
-    ICVCConfigUpdate, ProjectId, Step, StepCreate, StepGrade, StepId, StepPhase, StepPhaseCreate,
-    StepPhaseGradeResult, StepPhaseGradeResultCreate, StepPhaseId, StepPhaseProposal,
-    StepPhaseStatus, StepPhaseUpdate, StepPhaseVoteResult, StepPhaseVoteResultCreate, StepUpdate,
+    ICVCConfigUpdate, ProjectId, Step, StepCreate, StepGrade, StepId, StepPhase,
+    StepPhaseAdminAction, StepPhaseAdminActionCreate, StepPhaseCreate, StepPhaseGradeResult,
+    StepPhaseGradeResultCreate, StepPhaseId, StepPhaseProposal, StepPhaseStatus, StepPhaseUpdate,
+    StepPhaseVoteResult, StepPhaseVoteResultCreate, StepUpdate, UploadFile,
 };
 
 use candid::Principal;
@@ -42,8 +69,33 @@ const PHASE_GRADE_RESULT_MAP_MEM_ID: MemoryId = MemoryId::new(11);
 const PHASE_PROPOSAL_RESULT_MAP_MEM_ID: MemoryId = MemoryId::new(12);
 const CATEGORY_CONFIG_MAP_MEM_ID: MemoryId = MemoryId::new(13);
 const CATEGORY_ID_COUNTER_MAP_MEM_ID: MemoryId = MemoryId::new(14);
+const PHASE_ADMIN_ACTION_MAP_MEM_ID: MemoryId = MemoryId::new(15);
+const PHASE_APPEAL_MAP_MEM_ID: MemoryId = MemoryId::new(16);
+const PHASE_DEADLINE_INDEX_MEM_ID: MemoryId = MemoryId::new(17);
+const PROPOSAL_EXECUTION_RECEIPT_MAP_MEM_ID: MemoryId = MemoryId::new(18);
+const PENDING_PROPOSAL_MAP_MEM_ID: MemoryId = MemoryId::new(19);
+const SNS_NEURON_MAP_MEM_ID: MemoryId = MemoryId::new(20);
+const BALLOT_MAP_MEM_ID: MemoryId = MemoryId::new(21);
+const NEURON_BALLOT_INDEX_MEM_ID: MemoryId = MemoryId::new(22);
+const STORAGE_CONFIG_MEM_ID: MemoryId = MemoryId::new(23);
+const FILE_ID_COUNTER_MEM_ID: MemoryId = MemoryId::new(24);
+const STORED_FILE_MAP_MEM_ID: MemoryId = MemoryId::new(25);
+const FILE_CHUNK_MAP_MEM_ID: MemoryId = MemoryId::new(26);
+const FILE_KEY_INDEX_MEM_ID: MemoryId = MemoryId::new(27);
+const FILE_ACCESS_TOKEN_MAP_MEM_ID: MemoryId = MemoryId::new(28);
+const PROJECT_REVIEWER_MAP_MEM_ID: MemoryId = MemoryId::new(29);
+const DOCUMENT_ACCESS_MAP_MEM_ID: MemoryId = MemoryId::new(30);
+const DOCUMENT_ID_COUNTER_MEM_ID: MemoryId = MemoryId::new(31);
+const DOCUMENT_TYPE_MAP_MEM_ID: MemoryId = MemoryId::new(32);
+const AUDIT_LOG_MEM_ID: MemoryId = MemoryId::new(33);
+const PROJECT_AUDIT_INDEX_MEM_ID: MemoryId = MemoryId::new(34);
+const PENDING_FILE_INDEX_MEM_ID: MemoryId = MemoryId::new(35);
 
 type _Memory = VirtualMemory<DefaultMemoryImpl>;
+type StepPhaseDeadlineKey = ((u64, ProjectId), StepPhaseId);
+type StepPhaseBallotKey = ((ProjectId, StepPhaseId), SnsNeuronIdKey);
+type NeuronBallotKey = (SnsNeuronIdKey, (ProjectId, StepPhaseId));
+type StepPhaseAdminActionKey = ((ProjectId, StepPhaseId), u64);
 
 thread_local! {
     static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> = RefCell::new(MemoryManager::init(DefaultMemoryImpl::default()));
@@ -112,6 +164,95 @@ thread_local! {
         StableBTreeMap::init(MEMORY_MANAGER.with(|m| m.borrow().get(CATEGORY_CONFIG_MAP_MEM_ID)))
     );
 
+    static PHASE_ADMIN_ACTION_MAP: RefCell<StableBTreeMap<StepPhaseAdminActionKey, StepPhaseAdminActionModel, _Memory>> = RefCell::new(
+        StableBTreeMap::init(MEMORY_MANAGER.with(|m| m.borrow().get(PHASE_ADMIN_ACTION_MAP_MEM_ID)))
+    );
+
+    static PHASE_APPEAL_MAP: RefCell<StableBTreeMap<(ProjectId, StepPhaseId), AppealModel, _Memory>> = RefCell::new(
+        StableBTreeMap::init(MEMORY_MANAGER.with(|m| m.borrow().get(PHASE_APPEAL_MAP_MEM_ID)))
+    );
+
+    // Ordered by deadline, so the timer only has to look at the phases that are due.
+    static PHASE_DEADLINE_INDEX: RefCell<StableBTreeMap<StepPhaseDeadlineKey, (), _Memory>> = RefCell::new(
+        StableBTreeMap::init(MEMORY_MANAGER.with(|m| m.borrow().get(PHASE_DEADLINE_INDEX_MEM_ID)))
+    );
+
+    static PROPOSAL_EXECUTION_RECEIPT_MAP: RefCell<StableBTreeMap<ProposalId, ProposalExecutionReceiptModel, _Memory>> = RefCell::new(
+        StableBTreeMap::init(MEMORY_MANAGER.with(|m| m.borrow().get(PROPOSAL_EXECUTION_RECEIPT_MAP_MEM_ID)))
+    );
+
+    static PENDING_PROPOSAL_MAP: RefCell<StableBTreeMap<(ProjectId, StepPhaseId), PendingProposalModel, _Memory>> = RefCell::new(
+        StableBTreeMap::init(MEMORY_MANAGER.with(|m| m.borrow().get(PENDING_PROPOSAL_MAP_MEM_ID)))
+    );
+
+    static SNS_NEURON_MAP: RefCell<StableBTreeMap<String, SnsNeuronModel, _Memory>> = RefCell::new(
+        StableBTreeMap::init(MEMORY_MANAGER.with(|m| m.borrow().get(SNS_NEURON_MAP_MEM_ID)))
+    );
+
+    static BALLOT_MAP: RefCell<StableBTreeMap<StepPhaseBallotKey, BallotModel, _Memory>> = RefCell::new(
+        StableBTreeMap::init(MEMORY_MANAGER.with(|m| m.borrow().get(BALLOT_MAP_MEM_ID)))
+    );
+
+    static NEURON_BALLOT_INDEX: RefCell<StableBTreeMap<NeuronBallotKey, (), _Memory>> = RefCell::new(
+        StableBTreeMap::init(MEMORY_MANAGER.with(|m| m.borrow().get(NEURON_BALLOT_INDEX_MEM_ID)))
+    );
+
+    static STORAGE_CONFIG: RefCell<Cell<StorageConfigModel, _Memory>> = RefCell::new(
+        Cell::init(MEMORY_MANAGER.with(|m| m.borrow().get(STORAGE_CONFIG_MEM_ID)), StorageConfigModel::default()
+      ).expect("Failed to initialize the storage config cell")
+    );
+
+    static FILE_ID_COUNTER: RefCell<Cell<u64, _Memory>> = RefCell::new(
+        Cell::init(MEMORY_MANAGER.with(|m| m.borrow().get(FILE_ID_COUNTER_MEM_ID)), 0)
+            .expect("Failed to initialize the file id counter cell")
+    );
+
+    static STORED_FILE_MAP: RefCell<StableBTreeMap<FileId, StoredFileModel, _Memory>> = RefCell::new(
+        StableBTreeMap::init(MEMORY_MANAGER.with(|m| m.borrow().get(STORED_FILE_MAP_MEM_ID)))
+    );
+
+    // Uploads of each project that are not committed yet.
+    static PENDING_FILE_INDEX: RefCell<StableBTreeMap<(ProjectId, FileId), (), _Memory>> = RefCell::new(
+        StableBTreeMap::init(MEMORY_MANAGER.with(|m| m.borrow().get(PENDING_FILE_INDEX_MEM_ID)))
+    );
+
+    static FILE_CHUNK_MAP: RefCell<StableBTreeMap<(FileId, u32), Vec<u8>, _Memory>> = RefCell::new(
+        StableBTreeMap::init(MEMORY_MANAGER.with(|m| m.borrow().get(FILE_CHUNK_MAP_MEM_ID)))
+    );
+
+    static FILE_KEY_INDEX: RefCell<StableBTreeMap<String, FileKeyEntryModel, _Memory>> = RefCell::new(
+        StableBTreeMap::init(MEMORY_MANAGER.with(|m| m.borrow().get(FILE_KEY_INDEX_MEM_ID)))
+    );
+
+    static FILE_ACCESS_TOKEN_MAP: RefCell<StableBTreeMap<String, FileAccessTokenModel, _Memory>> = RefCell::new(
+        StableBTreeMap::init(MEMORY_MANAGER.with(|m| m.borrow().get(FILE_ACCESS_TOKEN_MAP_MEM_ID)))
+    );
+
+    static PROJECT_REVIEWER_MAP: RefCell<StableBTreeMap<(ProjectId, UserId), ProjectReviewerModel, _Memory>> = RefCell::new(
+        StableBTreeMap::init(MEMORY_MANAGER.with(|m| m.borrow().get(PROJECT_REVIEWER_MAP_MEM_ID)))
+    );
+
+    static DOCUMENT_ACCESS_MAP: RefCell<StableBTreeMap<(ProjectId, u64), DocumentAccessModel, _Memory>> = RefCell::new(
+        StableBTreeMap::init(MEMORY_MANAGER.with(|m| m.borrow().get(DOCUMENT_ACCESS_MAP_MEM_ID)))
+    );
+
+    static DOCUMENT_ID_COUNTER: RefCell<Cell<u64, _Memory>> = RefCell::new(
+        Cell::init(MEMORY_MANAGER.with(|m| m.borrow().get(DOCUMENT_ID_COUNTER_MEM_ID)), 0)
+            .expect("Failed to initialize the document id counter cell")
+    );
+
+    static DOCUMENT_TYPE_MAP: RefCell<StableBTreeMap<DocumentTypeId, DocumentTypeModel, _Memory>> = RefCell::new(
+        StableBTreeMap::init(MEMORY_MANAGER.with(|m| m.borrow().get(DOCUMENT_TYPE_MAP_MEM_ID)))
+    );
+
+    static AUDIT_LOG: RefCell<StableBTreeMap<AuditEntryId, AuditEntryModel, _Memory>> = RefCell::new(
+        StableBTreeMap::init(MEMORY_MANAGER.with(|m| m.borrow().get(AUDIT_LOG_MEM_ID)))
+    );
+
+    static PROJECT_AUDIT_INDEX: RefCell<StableBTreeMap<(ProjectId, AuditEntryId), (), _Memory>> = RefCell::new(
+        StableBTreeMap::init(MEMORY_MANAGER.with(|m| m.borrow().get(PROJECT_AUDIT_INDEX_MEM_ID)))
+    );
+
 
 
 }
@@ -148,7 +289,210 @@ pub fn update_canister_config(
     })
 }
 
-pub fn set_owner(owner: Principal) -> Result<CanisterConfig, ic_stable_structures::cell::ValueError> {
+// Storage config
+pub fn get_storage_config_model() -> StorageConfigModel {
+    STORAGE_CONFIG.with(|cell| cell.borrow().get().clone())
+}
+
+pub fn get_storage_config() -> StorageConfig {
+    convert_model_to_storage_config(get_storage_config_model())
+}
+
+pub fn set_storage_config_model(
+    config: StorageConfigModel,
+) -> Result<StorageConfigModel, ic_stable_structures::cell::ValueError> {
+    STORAGE_CONFIG.with(|cell| cell.borrow_mut().set(config))
+}
+
+pub fn update_storage_config(
+    update_storage_config: StorageConfigUpdate,
+) -> Result<StorageConfig, ic_stable_structures::cell::ValueError> {
+    STORAGE_CONFIG.with(|cell| {
+        let mut config_model = cell.borrow().get().clone();
+
+        if let Some(provider) = update_storage_config.provider {
+            config_model.provider = provider;
+        }
+        if let Some(endpoint) = update_storage_config.endpoint {
+            config_model.endpoint = endpoint;
+        }
+        if let Some(bucket) = update_storage_config.bucket {
+            config_model.bucket = bucket;
+        }
+        if let Some(path_prefix) = update_storage_config.path_prefix {
+            config_model.path_prefix = path_prefix;
+        }
+        if let Some(region) = update_storage_config.region {
+            config_model.region = region;
+        }
+        if let Some(access_key_id) = update_storage_config.access_key_id {
+            config_model.access_key_id = Some(access_key_id).filter(|key| !key.is_empty());
+        }
+        if let Some(secret_access_key) = update_storage_config.secret_access_key {
+            config_model.secret_access_key =
+                Some(secret_access_key).filter(|secret| !secret.is_empty());
+        }
+
+        cell.borrow_mut().set(config_model.clone())?;
+        Ok(convert_model_to_storage_config(config_model))
+    })
+}
+
+// On-chain file storage
+pub fn generate_file_id() -> FileId {
+    FILE_ID_COUNTER.with(|counter_cell| {
+        let new_value = *counter_cell.borrow().get() + 1;
+        counter_cell
+            .borrow_mut()
+            .set(new_value)
+            .expect("Error incrementing file ID.");
+
+        new_value
+    })
+}
+
+pub fn insert_stored_file(file_id: FileId, model: StoredFileModel) -> StoredFile {
+    PENDING_FILE_INDEX.with(|index| {
+        let mut index = index.borrow_mut();
+        if model.committed_at.is_none() {
+            index.insert((model.project_id, file_id), ());
+        } else {
+            index.remove(&(model.project_id, file_id));
+        }
+    });
+    STORED_FILE_MAP.with(|map| map.borrow_mut().insert(file_id, model.clone()));
+    convert_model_to_stored_file(file_id, model)
+}
+
+pub fn get_stored_file_model(file_id: FileId) -> Option<StoredFileModel> {
+    STORED_FILE_MAP.with(|map| map.borrow().get(&file_id))
+}
+
+pub fn get_stored_file(file_id: FileId) -> Option<StoredFile> {
+    get_stored_file_model(file_id).map(|model| convert_model_to_stored_file(file_id, model))
+}
+
+/// Removes the file and all its chunks.
+pub fn remove_stored_file(file_id: FileId) -> Option<StoredFile> {
+    let removed = STORED_FILE_MAP
+        .with(|map| map.borrow_mut().remove(&file_id))
+        .map(|model| {
+            PENDING_FILE_INDEX
+                .with(|index| index.borrow_mut().remove(&(model.project_id, file_id)));
+            convert_model_to_stored_file(file_id, model)
+        });
+    remove_file_chunks(file_id);
+
+    removed
+}
+
+/// Total size of the uploads of the project that are not committed yet.
+pub fn get_pending_upload_bytes(project_id: ProjectId) -> u64 {
+    PENDING_FILE_INDEX.with(|index| {
+        index
+            .borrow()
+            .range((project_id, 0)..=(project_id, FileId::MAX))
+            .filter_map(|((_, file_id), _)| get_stored_file_model(file_id))
+            .map(|model| model.size)
+            .sum()
+    })
+}
+
+pub fn put_file_chunk(file_id: FileId, chunk_index: u32, content: Vec<u8>) {
+    FILE_CHUNK_MAP.with(|map| map.borrow_mut().insert((file_id, chunk_index), content));
+}
+
+pub fn get_file_chunk(file_id: FileId, chunk_index: u32) -> Option<Vec<u8>> {
+    FILE_CHUNK_MAP.with(|map| map.borrow().get(&(file_id, chunk_index)))
+}
+
+pub fn count_file_chunks(file_id: FileId) -> u32 {
+    FILE_CHUNK_MAP.with(|map| {
+        map.borrow()
+            .range((file_id, 0)..=(file_id, u32::MAX))
+            .count() as u32
+    })
+}
+
+pub fn remove_file_chunks(file_id: FileId) {
+    FILE_CHUNK_MAP.with(|map| {
+        let mut map = map.borrow_mut();
+        let keys: Vec<(FileId, u32)> = map
+            .range((file_id, 0)..=(file_id, u32::MAX))
+            .map(|(key, _)| key)
+            .collect();
+
+        for key in keys {
+            map.remove(&key);
+        }
+    })
+}
+
+pub fn get_file_key_entry(key: &str) -> FileKeyEntryModel {
+    FILE_KEY_INDEX.with(|map| map.borrow().get(&key.to_string()).unwrap_or_default())
+}
+
+pub fn set_file_key_entry(key: String, entry: FileKeyEntryModel) {
+    FILE_KEY_INDEX.with(|map| {
+        let mut map = map.borrow_mut();
+        if entry.committed.is_none() && entry.pending.is_none() {
+            map.remove(&key);
+        } else {
+            map.insert(key, entry);
+        }
+    })
+}
+
+pub fn put_file_access_token(token: String, model: FileAccessTokenModel) {
+    FILE_ACCESS_TOKEN_MAP.with(|map| map.borrow_mut().insert(token, model));
+}
+
+pub fn get_file_access_token(token: &str) -> Option<FileAccessTokenModel> {
+    FILE_ACCESS_TOKEN_MAP.with(|map| map.borrow().get(&token.to_string()))
+}
+
+pub fn remove_expired_file_access_tokens(current_time: u64) {
+    FILE_ACCESS_TOKEN_MAP.with(|map| {
+        let mut map = map.borrow_mut();
+        let expired: Vec<String> = map
+            .iter()
+            .filter(|(_, model)| model.expires_at <= current_time)
+            .map(|(token, _)| token)
+            .collect();
+
+        for token in expired {
+            map.remove(&token);
+        }
+    })
+}
+
+pub fn set_project_vote_proposal_template(
+    template: ProposalTemplate,
+) -> Result<ICVCConfig, ic_stable_structures::cell::ValueError> {
+    ICVC_CONFIG.with(|cell| {
+        let mut config_model = cell.borrow().get().clone();
+        config_model.project_vote_proposal_template = Some(template);
+
+        cell.borrow_mut().set(config_model.clone())?;
+        Ok(config_model)
+    })
+}
+
+pub fn set_projects_update_timer_paused(
+    paused: bool,
+) -> Result<ICVCConfig, ic_stable_structures::cell::ValueError> {
+    ICVC_CONFIG.with(|cell| {
+        let mut config_model = cell.borrow().get().clone();
+        config_model.projects_update_timer_paused = Some(paused);
+
+        cell.borrow_mut().set(config_model.clone())?;
+        Ok(config_model)
+    })
+}
+
+pub fn set_owner(
+    owner: Principal,
+) -> Result<CanisterConfig, ic_stable_structures::cell::ValueError> {
     CANISTER_CONFIG.with(|cell| {
         let mut config_model = cell.borrow().get().clone();
 
@@ -191,6 +535,105 @@ pub fn calculate_used_stable_memory() -> u64 {
     total_bytes
 }
 
+// Document access log
+pub fn insert_document_access(project_id: ProjectId, model: DocumentAccessModel) -> DocumentAccess {
+    DOCUMENT_ACCESS_MAP.with(|map| {
+        let mut map = map.borrow_mut();
+
+        let index = map.range((project_id, 0)..=(project_id, u64::MAX)).count() as u64;
+        map.insert((project_id, index), model.clone());
+
+        convert_model_to_document_access(project_id, index, model)
+    })
+}
+
+pub fn get_document_accesses(project_id: ProjectId) -> Vec<DocumentAccess> {
+    DOCUMENT_ACCESS_MAP.with(|map| {
+        map.borrow()
+            .range((project_id, 0)..=(project_id, u64::MAX))
+            .map(|((project_id, index), model)| {
+                convert_model_to_document_access(project_id, index, model)
+            })
+            .collect()
+    })
+}
+
+// Document types
+// Document types are never removed, only deactivated, so the next ID follows the last one.
+pub fn generate_document_type_id() -> DocumentTypeId {
+    DOCUMENT_TYPE_MAP.with(|map| {
+        map.borrow()
+            .last_key_value()
+            .map_or(0, |(document_type_id, _)| document_type_id)
+            + 1
+    })
+}
+
+pub fn insert_document_type(
+    document_type_id: DocumentTypeId,
+    model: DocumentTypeModel,
+) -> DocumentType {
+    DOCUMENT_TYPE_MAP.with(|map| {
+        map.borrow_mut().insert(document_type_id, model.clone());
+
+        convert_model_to_document_type(document_type_id, model)
+    })
+}
+
+pub fn get_document_type_by_id(document_type_id: DocumentTypeId) -> Option<DocumentType> {
+    DOCUMENT_TYPE_MAP.with(|map| {
+        map.borrow()
+            .get(&document_type_id)
+            .map(|model| convert_model_to_document_type(document_type_id, model))
+    })
+}
+
+pub fn get_all_document_types() -> Vec<DocumentType> {
+    DOCUMENT_TYPE_MAP.with(|map| {
+        map.borrow()
+            .iter()
+            .map(|(document_type_id, model)| {
+                convert_model_to_document_type(document_type_id, model)
+            })
+            .collect()
+    })
+}
+
+pub fn update_document_type(
+    document_type_id: DocumentTypeId,
+    document_type_update: DocumentTypeUpdate,
+) -> Option<DocumentType> {
+    DOCUMENT_TYPE_MAP.with(|map| {
+        let mut map = map.borrow_mut();
+        let mut model = map.get(&document_type_id)?;
+
+        if let Some(name) = document_type_update.name {
+            model.name = name;
+        }
+        if let Some(description) = document_type_update.description {
+            model.description = description;
+        }
+        if let Some(mime_types) = document_type_update.mime_types {
+            model.mime_types = mime_types;
+        }
+        if let Some(max_size) = document_type_update.max_size {
+            model.max_size = max_size;
+        }
+        if let Some(max_files) = document_type_update.max_files {
+            model.max_files = max_files;
+        }
+        if let Some(public) = document_type_update.public {
+            model.public = public;
+        }
+        if let Some(active) = document_type_update.active {
+            model.active = active;
+        }
+
+        map.insert(document_type_id, model.clone());
+        Some(convert_model_to_document_type(document_type_id, model))
+    })
+}
+
 //Icvc config
 #[allow(dead_code)]
 pub fn set_icvc_config(
@@ -226,6 +669,13 @@ pub fn update_icvc_config(
         {
             config_model.projects_update_timer_interval = projects_update_timer_interval;
         }
+        if let Some(appeal_duration) = update_icvc_config.appeal_duration {
+            config_model.appeal_duration = Some(appeal_duration);
+        }
+        if let Some(sns_neurons_refresh_interval) = update_icvc_config.sns_neurons_refresh_interval
+        {
+            config_model.sns_neurons_refresh_interval = Some(sns_neurons_refresh_interval);
+        }
 
         cell.borrow_mut().set(config_model.clone())?;
         Ok(config_model)
@@ -255,6 +705,7 @@ pub fn insert_project(
                 created_at: ic_cdk::api::time(),
                 update_at: None,
                 update_by: None,
+                suspended_at: None,
             };
             map.insert((user_id, project_id), project_model.clone());
             Some(convert_model_to_project(
@@ -340,6 +791,8 @@ pub fn update_project_status(
     USER_PROJECT_MAP.with(|map| {
         let mut map = map.borrow_mut();
         if let Some(project_model) = map.get(&(user_id, project_id)).borrow_mut() {
+            project_model.suspended_at =
+                (new_status == ProjectStatus::Suspended).then(ic_cdk::api::time);
             project_model.status = new_status;
             map.insert((user_id, project_id), project_model.clone());
             Some(project_model.status.clone())
@@ -414,6 +867,47 @@ pub fn retrieve_user_projects(user_id: UserId) -> Vec<Project> {
     })
 }
 
+// Project reviewers
+pub fn insert_project_reviewer(
+    project_id: ProjectId,
+    reviewer_id: UserId,
+    model: ProjectReviewerModel,
+) -> ProjectReviewer {
+    PROJECT_REVIEWER_MAP.with(|map| {
+        map.borrow_mut()
+            .insert((project_id, reviewer_id), model.clone())
+    });
+
+    convert_model_to_project_reviewer(project_id, reviewer_id, model)
+}
+
+pub fn remove_project_reviewer(
+    project_id: ProjectId,
+    reviewer_id: UserId,
+) -> Option<ProjectReviewer> {
+    PROJECT_REVIEWER_MAP.with(|map| {
+        map.borrow_mut()
+            .remove(&(project_id, reviewer_id))
+            .map(|model| convert_model_to_project_reviewer(project_id, reviewer_id, model))
+    })
+}
+
+pub fn is_project_reviewer(project_id: ProjectId, reviewer_id: UserId) -> bool {
+    PROJECT_REVIEWER_MAP.with(|map| map.borrow().contains_key(&(project_id, reviewer_id)))
+}
+
+pub fn get_project_reviewers(project_id: ProjectId) -> Vec<ProjectReviewer> {
+    PROJECT_REVIEWER_MAP.with(|map| {
+        map.borrow()
+            .iter()
+            .filter(|((project_id_key, _), _)| *project_id_key == project_id)
+            .map(|((project_id, reviewer_id), model)| {
+                convert_model_to_project_reviewer(project_id, reviewer_id, model)
+            })
+            .collect()
+    })
+}
+
 //Categories
 pub fn insert_category(category_id: u64, category_create: CategoryCreate) -> Option<Category> {
     CATEGORY_MAP.with(|map| {
@@ -477,7 +971,9 @@ pub fn insert_step_phase_config(
             None
         } else {
             let step_phase_config_model = StepPhaseConfigModel {
+                name: step_phase_config_create.name,
                 assessement_method: step_phase_config_create.assessment_method,
+                vote_rules: step_phase_config_create.vote_rules,
             };
             map.insert(step_phase_id, step_phase_config_model.clone());
             Some(convert_model_to_step_phase_config(
@@ -488,6 +984,43 @@ pub fn insert_step_phase_config(
     })
 }
 
+pub fn update_step_phase_vote_rules(
+    step_phase_id: StepPhaseId,
+    vote_rules: VoteRules,
+) -> Option<StepPhaseConfig> {
+    PHASE_CONFIG_MAP.with(|map| {
+        let mut map = map.borrow_mut();
+        if let Some(model) = map.get(&step_phase_id).borrow_mut() {
+            model.vote_rules = Some(vote_rules);
+            map.insert(step_phase_id, model.clone());
+
+            Some(convert_model_to_step_phase_config(
+                step_phase_id,
+                model.clone(),
+            ))
+        } else {
+            None
+        }
+    })
+}
+
+pub fn update_step_phase_name(step_phase_id: StepPhaseId, name: String) -> Option<StepPhaseConfig> {
+    PHASE_CONFIG_MAP.with(|map| {
+        let mut map = map.borrow_mut();
+        if let Some(model) = map.get(&step_phase_id).borrow_mut() {
+            model.name = Some(name);
+            map.insert(step_phase_id, model.clone());
+
+            Some(convert_model_to_step_phase_config(
+                step_phase_id,
+                model.clone(),
+            ))
+        } else {
+            None
+        }
+    })
+}
+
 pub fn get_step_phase_config_by_id(step_phase_id: StepPhaseId) -> Option<StepPhaseConfig> {
     PHASE_CONFIG_MAP.with(|map| {
         map.borrow().get(&step_phase_id).map(|step_phase_config| {
@@ -537,9 +1070,10 @@ pub fn insert_step_config(
         } else {
             let step_config_model = StepConfigModel {
                 questions: step_config_create.questions,
-                required_upload_files: step_config_create.required_upload_files,
+                required_upload_files: None,
                 checkboxes: step_config_create.checkboxes,
                 numeric_values: step_config_create.decimal_values,
+                required_document_types: Some(step_config_create.required_upload_files),
             };
             map.insert((step_phase_id, step_id), step_config_model.clone());
             Some(convert_model_to_step_config(
@@ -551,6 +1085,23 @@ pub fn insert_step_config(
     })
 }
 
+pub fn update_step_required_upload_files(
+    step_phase_id: StepPhaseId,
+    step_id: StepId,
+    required_upload_files: Vec<DocumentTypeId>,
+) -> Option<StepConfig> {
+    PHASE_STEP_CONFIG_MAP.with(|map| {
+        let mut map = map.borrow_mut();
+        let mut model = map.get(&(step_phase_id, step_id))?;
+
+        model.required_upload_files = None;
+        model.required_document_types = Some(required_upload_files);
+        map.insert((step_phase_id, step_id), model.clone());
+
+        Some(convert_model_to_step_config(step_phase_id, step_id, model))
+    })
+}
+
 #[allow(dead_code)]
 pub fn get_step_config_by_id(step_phase_id: StepPhaseId, step_id: StepId) -> Option<StepConfig> {
     PHASE_STEP_CONFIG_MAP.with(|map| {
@@ -560,11 +1111,10 @@ pub fn get_step_config_by_id(step_phase_id: StepPhaseId, step_id: StepId) -> Opt
     })
 }
 
-pub fn get_all_phase_steps_config(step_phase_id: StepPhaseId) -> Vec<StepConfig> {
+pub fn get_all_step_configs() -> Vec<StepConfig> {
     PHASE_STEP_CONFIG_MAP.with(|map| {
         map.borrow()
             .iter()
-            .filter(|&((_step_phase_id, _), _)| _step_phase_id == step_phase_id)
             .map(|((step_phase_id, step_id), step_config)| {
                 convert_model_to_step_config(step_phase_id, step_id, step_config)
             })
@@ -572,12 +1122,24 @@ pub fn get_all_phase_steps_config(step_phase_id: StepPhaseId) -> Vec<StepConfig>
     })
 }
 
-#[allow(dead_code)]
-pub fn delete_step_config(step_phase_id: StepPhaseId, step_id: StepId) -> Option<StepConfig> {
+pub fn get_all_phase_steps_config(step_phase_id: StepPhaseId) -> Vec<StepConfig> {
     PHASE_STEP_CONFIG_MAP.with(|map| {
-        map.borrow_mut()
-            .remove(&(step_phase_id, step_id))
-            .map(|step_config| convert_model_to_step_config(step_phase_id, step_id, step_config))
+        map.borrow()
+            .iter()
+            .filter(|&((_step_phase_id, _), _)| _step_phase_id == step_phase_id)
+            .map(|((step_phase_id, step_id), step_config)| {
+                convert_model_to_step_config(step_phase_id, step_id, step_config)
+            })
+            .collect()
+    })
+}
+
+#[allow(dead_code)]
+pub fn delete_step_config(step_phase_id: StepPhaseId, step_id: StepId) -> Option<StepConfig> {
+    PHASE_STEP_CONFIG_MAP.with(|map| {
+        map.borrow_mut()
+            .remove(&(step_phase_id, step_id))
+            .map(|step_config| convert_model_to_step_config(step_phase_id, step_id, step_config))
     })
 }
 
@@ -602,6 +1164,8 @@ pub fn insert_step_phase(
                 assessment_method: step_phase_create.assessment_method,
             };
             map.insert((project_id, step_phase_id), model.clone());
+            update_step_phase_deadline_index(project_id, step_phase_id, None, Some(&model));
+
             Some(convert_model_to_phase(project_id, step_phase_id, model))
         }
     })
@@ -615,6 +1179,8 @@ pub fn update_step_phase(
     PROJECT_STEP_PHASE_MAP.with(|map| {
         let mut map = map.borrow_mut();
         if let Some(model) = map.get(&(project_id, step_phase_id)).borrow_mut() {
+            let previous_model = model.clone();
+
             if let Some(status) = step_phase_update.status {
                 model.status = status;
             }
@@ -635,6 +1201,12 @@ pub fn update_step_phase(
             }
 
             map.insert((project_id, step_phase_id), model.clone());
+            update_step_phase_deadline_index(
+                project_id,
+                step_phase_id,
+                Some(&previous_model),
+                Some(&*model),
+            );
 
             Some(convert_model_to_phase(
                 project_id,
@@ -655,8 +1227,16 @@ pub fn update_step_phase_status(
     PROJECT_STEP_PHASE_MAP.with(|map| {
         let mut map = map.borrow_mut();
         if let Some(model) = map.get(&(project_id, step_phase_id)).borrow_mut() {
+            let previous_model = model.clone();
+
             model.status = new_status;
             map.insert((project_id, step_phase_id), model.clone());
+            update_step_phase_deadline_index(
+                project_id,
+                step_phase_id,
+                Some(&previous_model),
+                Some(&*model),
+            );
 
             Some(model.status.clone())
         } else {
@@ -665,6 +1245,43 @@ pub fn update_step_phase_status(
     })
 }
 
+pub fn reopen_step_phase(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    end_open_date: u64,
+    start_assessment_date: u64,
+    end_assessment_date: u64,
+) -> Option<StepPhase> {
+    PROJECT_STEP_PHASE_MAP.with(|map| {
+        let mut map = map.borrow_mut();
+        if let Some(model) = map.get(&(project_id, step_phase_id)).borrow_mut() {
+            let previous_model = model.clone();
+
+            model.status = StepPhaseStatus::Open;
+            model.end_open_date = end_open_date;
+            model.submit_date = None;
+            model.start_assessment_date = start_assessment_date;
+            model.end_assessment_date = end_assessment_date;
+
+            map.insert((project_id, step_phase_id), model.clone());
+            update_step_phase_deadline_index(
+                project_id,
+                step_phase_id,
+                Some(&previous_model),
+                Some(&*model),
+            );
+
+            Some(convert_model_to_phase(
+                project_id,
+                step_phase_id,
+                model.clone(),
+            ))
+        } else {
+            None
+        }
+    })
+}
+
 pub fn get_step_phase_by_id(
     project_id: ProjectId,
     step_phase_id: StepPhaseId,
@@ -692,11 +1309,201 @@ pub fn get_all_phases_by_project(project_id: ProjectId) -> Vec<StepPhase> {
 pub fn delete_step_phase(project_id: ProjectId, step_phase_id: StepPhaseId) -> Option<()> {
     PROJECT_STEP_PHASE_MAP.with(|map| {
         let mut map = map.borrow_mut();
-        map.remove(&(project_id, step_phase_id)).map(|_| ())
+        map.remove(&(project_id, step_phase_id)).map(|model| {
+            update_step_phase_deadline_index(project_id, step_phase_id, Some(&model), None);
+        })
     })
 }
 
+// Step phase deadline index
+pub fn get_due_step_phase_deadlines(
+    current_time: u64,
+    limit: usize,
+) -> Vec<(u64, ProjectId, StepPhaseId)> {
+    PHASE_DEADLINE_INDEX.with(|index| {
+        index
+            .borrow()
+            .range(..((current_time, 0), 0))
+            .take(limit)
+            .map(|(((deadline, project_id), step_phase_id), _)| {
+                (deadline, project_id, step_phase_id)
+            })
+            .collect()
+    })
+}
+
+pub fn remove_step_phase_deadline(
+    deadline: u64,
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+) -> Option<()> {
+    PHASE_DEADLINE_INDEX.with(|index| {
+        index
+            .borrow_mut()
+            .remove(&((deadline, project_id), step_phase_id))
+    })
+}
+
+pub fn insert_step_phase_deadline(
+    deadline: u64,
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+) {
+    PHASE_DEADLINE_INDEX.with(|index| {
+        index
+            .borrow_mut()
+            .insert(((deadline, project_id), step_phase_id), ());
+    });
+}
+
+pub fn is_step_phase_deadline_index_empty() -> bool {
+    PHASE_DEADLINE_INDEX.with(|index| index.borrow().is_empty())
+}
+
+pub fn rebuild_step_phase_deadline_index() -> u64 {
+    PHASE_DEADLINE_INDEX.with(|index| index.borrow_mut().clear_new());
+
+    PROJECT_STEP_PHASE_MAP.with(|map| {
+        let mut count = 0;
+        for ((project_id, step_phase_id), model) in map.borrow().iter() {
+            if get_step_phase_deadline(&model).is_some() {
+                update_step_phase_deadline_index(project_id, step_phase_id, None, Some(&model));
+                count += 1;
+            }
+        }
+        count
+    })
+}
+
+fn update_step_phase_deadline_index(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    previous_model: Option<&StepPhaseModel>,
+    model: Option<&StepPhaseModel>,
+) {
+    let previous_deadline = previous_model.and_then(get_step_phase_deadline);
+    let deadline = model.and_then(get_step_phase_deadline);
+
+    if previous_deadline == deadline {
+        return;
+    }
+
+    PHASE_DEADLINE_INDEX.with(|index| {
+        let mut index = index.borrow_mut();
+        if let Some(previous_deadline) = previous_deadline {
+            index.remove(&((previous_deadline, project_id), step_phase_id));
+        }
+        if let Some(deadline) = deadline {
+            index.insert(((deadline, project_id), step_phase_id), ());
+        }
+    });
+}
+
+// Only open and submitted phases have a deadline the timer has to act on.
+fn get_step_phase_deadline(model: &StepPhaseModel) -> Option<u64> {
+    match model.status {
+        StepPhaseStatus::Open => Some(model.end_open_date),
+        StepPhaseStatus::Submitted => Some(model.end_assessment_date),
+        _ => None,
+    }
+}
+
+// Step phase admin actions
+pub fn insert_step_phase_admin_action(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    admin_action_create: StepPhaseAdminActionCreate,
+) -> StepPhaseAdminAction {
+    PHASE_ADMIN_ACTION_MAP.with(|map| {
+        let mut map = map.borrow_mut();
+
+        let action_index = map
+            .iter_upper_bound(&((project_id, step_phase_id), u64::MAX))
+            .next()
+            .filter(|((step_phase_key, _), _)| *step_phase_key == (project_id, step_phase_id))
+            .map_or(0, |((_, action_index), _)| action_index + 1);
+
+        let model = StepPhaseAdminActionModel {
+            action: admin_action_create.action,
+            reason: admin_action_create.reason,
+            previous_status: admin_action_create.previous_status,
+            new_status: admin_action_create.new_status,
+            previous_end_date: admin_action_create.previous_end_date,
+            new_end_date: admin_action_create.new_end_date,
+            performed_by: admin_action_create.performed_by,
+            performed_at: ic_cdk::api::time(),
+        };
+
+        map.insert(((project_id, step_phase_id), action_index), model.clone());
+        convert_model_to_step_phase_admin_action(project_id, step_phase_id, model)
+    })
+}
+
+pub fn get_step_phase_admin_actions(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+) -> Vec<StepPhaseAdminAction> {
+    PHASE_ADMIN_ACTION_MAP.with(|map| {
+        map.borrow()
+            .range(step_phase_admin_action_range(project_id, step_phase_id))
+            .map(|(_, model)| {
+                convert_model_to_step_phase_admin_action(project_id, step_phase_id, model)
+            })
+            .collect()
+    })
+}
+
+fn step_phase_admin_action_range(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+) -> std::ops::RangeInclusive<StepPhaseAdminActionKey> {
+    ((project_id, step_phase_id), 0)..=((project_id, step_phase_id), u64::MAX)
+}
+
 // Steps
+pub fn generate_document_id() -> DocumentId {
+    DOCUMENT_ID_COUNTER.with(|counter_cell| {
+        let new_value = *counter_cell.borrow().get() + 1;
+        counter_cell
+            .borrow_mut()
+            .set(new_value)
+            .expect("Error incrementing document ID.");
+
+        new_value
+    })
+}
+
+/// Gives an ID to the documents uploaded before a step could hold several files of a type.
+pub fn assign_missing_document_ids() -> u64 {
+    PROJECT_STEP_MAP.with(|map| {
+        let mut map = map.borrow_mut();
+        let legacy_steps: Vec<((ProjectId, CompositeKey), StepModel)> = map
+            .iter()
+            .filter(|(_, model)| {
+                model
+                    .upload_files
+                    .iter()
+                    .any(|file| file.s3_key.is_some() && file.document_id.is_none())
+            })
+            .collect();
+
+        let mut count = 0;
+        for (key, mut model) in legacy_steps {
+            for file in model
+                .upload_files
+                .iter_mut()
+                .filter(|file| file.s3_key.is_some() && file.document_id.is_none())
+            {
+                file.document_id = Some(generate_document_id());
+                file.version = Some(1);
+                count += 1;
+            }
+            map.insert(key, model);
+        }
+        count
+    })
+}
+
 pub fn insert_step(
     project_id: ProjectId,
     step_phase_id: StepPhaseId,
@@ -713,7 +1520,11 @@ pub fn insert_step(
                 questions_submission: step_create.questions_submission,
                 checkbox_submission: step_create.checkbox_submission,
                 numeric_submission: step_create.numeric_submission,
-                upload_files: step_create.upload_files,
+                upload_files: step_create
+                    .upload_files
+                    .into_iter()
+                    .map(UploadFileModel::from)
+                    .collect(),
                 grade_end_date: None,
                 update_by: None,
                 update_at: None,
@@ -751,17 +1562,10 @@ pub fn update_step(
             }
 
             if let Some(upload_files) = step_update.upload_files {
-                for new_file in upload_files {
-                    if let Some(pos) = step_model
-                        .upload_files
-                        .iter()
-                        .position(|file| file.document_type == new_file.document_type)
-                    {
-                        step_model.upload_files[pos] = new_file;
-                    } else {
-                        step_model.upload_files.push(new_file);
-                    }
-                }
+                step_model.upload_files = upload_files
+                    .into_iter()
+                    .map(UploadFileModel::from)
+                    .collect();
             }
 
             step_model.update_by = Some(caller_id);
@@ -885,6 +1689,28 @@ pub fn get_users_step_grades(
     })
 }
 
+/// Removes the grades of all the steps of a phase and its grade result.
+pub fn remove_step_phase_grades(project_id: ProjectId, step_phase_id: StepPhaseId) -> usize {
+    PHASE_GRADE_RESULT_MAP.with(|map| map.borrow_mut().remove(&(project_id, step_phase_id)));
+
+    STEP_GRADE_MAP.with(|map| {
+        let mut map = map.borrow_mut();
+        let keys: Vec<(UserId, CompositeKey)> = map
+            .iter()
+            .filter(|((_user_id_key, composite_key), _)| {
+                let (proj_id, phase_id, _): (u64, u64, u64) = composite_key.deconstruct_key();
+                proj_id == project_id && phase_id == step_phase_id
+            })
+            .map(|(key, _)| key)
+            .collect();
+
+        for key in &keys {
+            map.remove(key);
+        }
+        keys.len()
+    })
+}
+
 pub fn put_step_phase_grade_result(
     project_id: ProjectId,
     step_phase_id: StepPhaseId,
@@ -943,6 +1769,247 @@ pub fn get_proposal_by_step_phase_id(
     })
 }
 
+// Proposal execution receipts
+pub fn insert_proposal_execution_receipt(
+    proposal_id: ProposalId,
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    approved: bool,
+) -> Option<ProposalExecutionReceipt> {
+    PROPOSAL_EXECUTION_RECEIPT_MAP.with(|map| {
+        let mut map = map.borrow_mut();
+        if map.contains_key(&proposal_id) {
+            None
+        } else {
+            let model = ProposalExecutionReceiptModel {
+                project_id,
+                step_phase_id,
+                approved,
+                executed_at: ic_cdk::api::time(),
+            };
+            map.insert(proposal_id, model.clone());
+
+            Some(convert_model_to_proposal_execution_receipt(
+                proposal_id,
+                model,
+            ))
+        }
+    })
+}
+
+pub fn get_proposal_execution_receipt(proposal_id: ProposalId) -> Option<ProposalExecutionReceipt> {
+    PROPOSAL_EXECUTION_RECEIPT_MAP.with(|map| {
+        map.borrow()
+            .get(&proposal_id)
+            .map(|model| convert_model_to_proposal_execution_receipt(proposal_id, model))
+    })
+}
+
+// SNS neurons
+pub fn put_sns_neuron(subaccount: String, model: SnsNeuronModel) -> SnsNeuron {
+    SNS_NEURON_MAP.with(|map| {
+        map.borrow_mut().insert(subaccount.clone(), model.clone());
+        convert_model_to_sns_neuron(subaccount, model)
+    })
+}
+
+pub fn get_sns_neuron(subaccount: &str) -> Option<SnsNeuronModel> {
+    SNS_NEURON_MAP.with(|map| map.borrow().get(&subaccount.to_string()))
+}
+
+pub fn get_all_sns_neurons() -> Vec<SnsNeuron> {
+    SNS_NEURON_MAP.with(|map| {
+        map.borrow()
+            .iter()
+            .map(|(subaccount, model)| convert_model_to_sns_neuron(subaccount, model))
+            .collect()
+    })
+}
+
+/// Replaces the known neurons with the given ones, neurons that are not listed anymore are removed.
+pub fn replace_sns_neurons(neurons: Vec<(String, SnsNeuronModel)>) {
+    SNS_NEURON_MAP.with(|map| {
+        let mut map = map.borrow_mut();
+        let stale_subaccounts: Vec<String> = map
+            .iter()
+            .map(|(subaccount, _)| subaccount)
+            .filter(|subaccount| !neurons.iter().any(|(listed, _)| listed == subaccount))
+            .collect();
+
+        for subaccount in stale_subaccounts {
+            map.remove(&subaccount);
+        }
+        for (subaccount, model) in neurons {
+            map.insert(subaccount, model);
+        }
+    })
+}
+
+// Ballots
+/// Stores the ballots of a step phase, replacing the ones stored before.
+pub fn put_step_phase_ballots(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    ballots: Vec<(SnsNeuronIdKey, BallotModel)>,
+) -> usize {
+    remove_step_phase_ballots(project_id, step_phase_id);
+
+    let count = ballots.len();
+    BALLOT_MAP.with(|map| {
+        NEURON_BALLOT_INDEX.with(|index| {
+            let mut map = map.borrow_mut();
+            let mut index = index.borrow_mut();
+
+            for (neuron_id, model) in ballots {
+                map.insert(((project_id, step_phase_id), neuron_id), model);
+                index.insert((neuron_id, (project_id, step_phase_id)), ());
+            }
+        })
+    });
+
+    count
+}
+
+fn remove_step_phase_ballots(project_id: ProjectId, step_phase_id: StepPhaseId) {
+    let neuron_ids: Vec<SnsNeuronIdKey> = BALLOT_MAP.with(|map| {
+        map.borrow()
+            .range(step_phase_ballot_range(
+                project_id,
+                step_phase_id,
+                step_phase_id,
+            ))
+            .map(|((_, neuron_id), _)| neuron_id)
+            .collect()
+    });
+
+    BALLOT_MAP.with(|map| {
+        NEURON_BALLOT_INDEX.with(|index| {
+            let mut map = map.borrow_mut();
+            let mut index = index.borrow_mut();
+
+            for neuron_id in neuron_ids {
+                map.remove(&((project_id, step_phase_id), neuron_id));
+                index.remove(&(neuron_id, (project_id, step_phase_id)));
+            }
+        })
+    });
+}
+
+pub fn get_step_phase_ballots(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+) -> Vec<ProjectBallot> {
+    BALLOT_MAP.with(|map| {
+        map.borrow()
+            .range(step_phase_ballot_range(
+                project_id,
+                step_phase_id,
+                step_phase_id,
+            ))
+            .map(|((_, neuron_id), model)| {
+                convert_model_to_project_ballot(project_id, step_phase_id, neuron_id, model)
+            })
+            .collect()
+    })
+}
+
+pub fn get_project_ballots(project_id: ProjectId) -> Vec<ProjectBallot> {
+    BALLOT_MAP.with(|map| {
+        map.borrow()
+            .range(step_phase_ballot_range(project_id, 0, StepPhaseId::MAX))
+            .map(|(((_, step_phase_id), neuron_id), model)| {
+                convert_model_to_project_ballot(project_id, step_phase_id, neuron_id, model)
+            })
+            .collect()
+    })
+}
+
+fn step_phase_ballot_range(
+    project_id: ProjectId,
+    first_step_phase_id: StepPhaseId,
+    last_step_phase_id: StepPhaseId,
+) -> std::ops::RangeInclusive<StepPhaseBallotKey> {
+    ((project_id, first_step_phase_id), [0; 32])..=((project_id, last_step_phase_id), [u8::MAX; 32])
+}
+
+pub fn get_neuron_ballots(neuron_id: SnsNeuronIdKey) -> Vec<ProjectBallot> {
+    let step_phase_keys: Vec<(ProjectId, StepPhaseId)> = NEURON_BALLOT_INDEX.with(|index| {
+        index
+            .borrow()
+            .range((neuron_id, (0, 0))..=(neuron_id, (u64::MAX, u64::MAX)))
+            .map(|((_, step_phase_key), _)| step_phase_key)
+            .collect()
+    });
+
+    BALLOT_MAP.with(|map| {
+        let map = map.borrow();
+        step_phase_keys
+            .into_iter()
+            .filter_map(|(project_id, step_phase_id)| {
+                map.get(&((project_id, step_phase_id), neuron_id))
+                    .map(|model| {
+                        convert_model_to_project_ballot(project_id, step_phase_id, neuron_id, model)
+                    })
+            })
+            .collect()
+    })
+}
+
+// Pending proposals
+pub fn put_pending_proposal(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    model: PendingProposalModel,
+) -> PendingProposal {
+    PENDING_PROPOSAL_MAP.with(|map| {
+        map.borrow_mut()
+            .insert((project_id, step_phase_id), model.clone());
+        convert_model_to_pending_proposal(project_id, step_phase_id, model)
+    })
+}
+
+pub fn get_pending_proposal(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+) -> Option<PendingProposalModel> {
+    PENDING_PROPOSAL_MAP.with(|map| map.borrow().get(&(project_id, step_phase_id)))
+}
+
+pub fn remove_pending_proposal(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+) -> Option<PendingProposal> {
+    PENDING_PROPOSAL_MAP.with(|map| {
+        map.borrow_mut()
+            .remove(&(project_id, step_phase_id))
+            .map(|model| convert_model_to_pending_proposal(project_id, step_phase_id, model))
+    })
+}
+
+pub fn get_all_pending_proposals() -> Vec<PendingProposal> {
+    PENDING_PROPOSAL_MAP.with(|map| {
+        map.borrow()
+            .iter()
+            .map(|((project_id, step_phase_id), model)| {
+                convert_model_to_pending_proposal(project_id, step_phase_id, model)
+            })
+            .collect()
+    })
+}
+
+pub fn get_due_pending_proposals(current_time: u64, limit: usize) -> Vec<PendingProposal> {
+    PENDING_PROPOSAL_MAP.with(|map| {
+        map.borrow()
+            .iter()
+            .filter(|(_, model)| model.next_attempt_at <= current_time)
+            .take(limit)
+            .map(|((project_id, step_phase_id), model)| {
+                convert_model_to_pending_proposal(project_id, step_phase_id, model)
+            })
+            .collect()
+    })
+}
+
 pub fn get_all_proposals_by_step_phase(
     project_id: ProjectId,
     phase_id: StepPhaseId,
@@ -993,6 +2060,190 @@ pub fn get_vote_result_by_step_phase_id(
     })
 }
 
+pub fn remove_step_phase_vote_result(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+) -> Option<StepPhaseVoteResult> {
+    PHASE_PROPOSAL_RESULT_MAP.with(|map| {
+        map.borrow_mut()
+            .remove(&(project_id, step_phase_id))
+            .map(|result| {
+                convert_model_to_step_phase_vote_result(project_id, step_phase_id, result)
+            })
+    })
+}
+
+//Appeals
+pub fn insert_appeal(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    filed_by: UserId,
+    appeal_create: AppealCreate,
+) -> Option<Appeal> {
+    PHASE_APPEAL_MAP.with(|map| {
+        let mut map = map.borrow_mut();
+        if map.contains_key(&(project_id, step_phase_id)) {
+            None
+        } else {
+            let model = AppealModel {
+                justification: appeal_create.justification,
+                status: AppealStatus::Pending,
+                outcome: None,
+                decision_reason: None,
+                filed_by,
+                filed_at: ic_cdk::api::time(),
+                decided_by: None,
+                decided_at: None,
+            };
+            map.insert((project_id, step_phase_id), model.clone());
+            Some(convert_model_to_appeal(project_id, step_phase_id, model))
+        }
+    })
+}
+
+pub fn update_appeal_decision(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    decided_by: Option<UserId>,
+    decision: Option<AppealDecision>,
+) -> Option<Appeal> {
+    PHASE_APPEAL_MAP.with(|map| {
+        let mut map = map.borrow_mut();
+        if let Some(model) = map.get(&(project_id, step_phase_id)).borrow_mut() {
+            match decision {
+                Some(decision) => {
+                    model.status = match decision.outcome {
+                        AppealOutcome::Reject => AppealStatus::Rejected,
+                        _ => AppealStatus::Accepted,
+                    };
+                    model.outcome = Some(decision.outcome);
+                    model.decision_reason = Some(decision.reason);
+                    model.decided_at = Some(ic_cdk::api::time());
+                }
+                None => {
+                    model.status = AppealStatus::Pending;
+                    model.outcome = None;
+                    model.decision_reason = None;
+                    model.decided_at = None;
+                }
+            }
+            model.decided_by = decided_by;
+
+            map.insert((project_id, step_phase_id), model.clone());
+            Some(convert_model_to_appeal(
+                project_id,
+                step_phase_id,
+                model.clone(),
+            ))
+        } else {
+            None
+        }
+    })
+}
+
+pub fn get_appeal_by_step_phase_id(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+) -> Option<Appeal> {
+    PHASE_APPEAL_MAP.with(|map| {
+        map.borrow()
+            .get(&(project_id, step_phase_id))
+            .map(|model| convert_model_to_appeal(project_id, step_phase_id, model))
+    })
+}
+
+pub fn get_all_appeals(status: Option<AppealStatus>) -> Vec<Appeal> {
+    PHASE_APPEAL_MAP.with(|map| {
+        map.borrow()
+            .iter()
+            .filter(|(_, model)| match &status {
+                Some(status) => model.status == *status,
+                None => true,
+            })
+            .map(|((project_id, step_phase_id), model)| {
+                convert_model_to_appeal(project_id, step_phase_id, model)
+            })
+            .collect()
+    })
+}
+
+// Audit log
+// Entries are only appended, so the next ID follows the last one.
+pub fn insert_audit_entry(model: AuditEntryModel) -> AuditEntry {
+    let audit_entry_id = AUDIT_LOG.with(|log| {
+        let mut log = log.borrow_mut();
+        let audit_entry_id = log.last_key_value().map_or(0, |(id, _)| id) + 1;
+
+        log.insert(audit_entry_id, model.clone());
+        audit_entry_id
+    });
+
+    if let Some(project_id) = model.target.project_id {
+        PROJECT_AUDIT_INDEX.with(|index| {
+            index.borrow_mut().insert((project_id, audit_entry_id), ());
+        });
+    }
+
+    convert_model_to_audit_entry(audit_entry_id, model)
+}
+
+/// Entries from `start_at` on matching the filter, the entries of a project are read from the
+/// project index. The page ends after `limit` matching entries or `max_scanned` entries read.
+pub fn get_audit_entries(
+    project_id: Option<ProjectId>,
+    start_at: AuditEntryId,
+    limit: usize,
+    max_scanned: usize,
+    filter: impl Fn(&AuditEntry) -> bool,
+) -> AuditLogPage {
+    AUDIT_LOG.with(|log| {
+        let log = log.borrow();
+
+        match project_id {
+            Some(project_id) => PROJECT_AUDIT_INDEX.with(|index| {
+                let index = index.borrow();
+                let entries = index
+                    .range((project_id, start_at)..=(project_id, AuditEntryId::MAX))
+                    .filter_map(|((_, audit_entry_id), _)| {
+                        log.get(&audit_entry_id)
+                            .map(|model| convert_model_to_audit_entry(audit_entry_id, model))
+                    });
+                collect_audit_page(entries, limit, max_scanned, filter)
+            }),
+            None => {
+                let entries = log.range(start_at..).map(|(audit_entry_id, model)| {
+                    convert_model_to_audit_entry(audit_entry_id, model)
+                });
+                collect_audit_page(entries, limit, max_scanned, filter)
+            }
+        }
+    })
+}
+
+fn collect_audit_page(
+    entries: impl Iterator<Item = AuditEntry>,
+    limit: usize,
+    max_scanned: usize,
+    filter: impl Fn(&AuditEntry) -> bool,
+) -> AuditLogPage {
+    let mut page = AuditLogPage {
+        entries: vec![],
+        next_start_at: None,
+    };
+
+    for (scanned, entry) in entries.enumerate() {
+        if page.entries.len() == limit || scanned == max_scanned {
+            page.next_start_at = Some(entry.id);
+            break;
+        }
+        if filter(&entry) {
+            page.entries.push(entry);
+        }
+    }
+
+    page
+}
+
 //Users
 pub fn save_admin(create_admin: UserCreate) -> Option<User> {
     USERS_MAP.with(|map| {
@@ -1097,6 +2348,7 @@ fn convert_model_to_project(
         created_at: project_model.created_at,
         update_by: project_model.update_by,
         update_at: project_model.update_at,
+        suspended_at: project_model.suspended_at,
     }
 }
 
@@ -1114,7 +2366,9 @@ fn convert_model_to_step_phase_config(
 ) -> StepPhaseConfig {
     StepPhaseConfig {
         id: step_phase_id,
+        name: model.name,
         assessment_method: model.assessement_method,
+        vote_rules: model.vote_rules,
     }
 }
 
@@ -1126,8 +2380,8 @@ fn convert_model_to_step_config(
     StepConfig {
         id: step_phase_id,
         step_id,
+        required_upload_files: model.required_document_types(),
         questions: model.questions,
-        required_upload_files: model.required_upload_files,
         checkboxes: model.checkboxes,
         decimal_values: model.numeric_values,
     }
@@ -1164,7 +2418,11 @@ fn convert_model_to_step(
         question_submission: model.questions_submission,
         checkbox_submission: model.checkbox_submission,
         decimal_submission: model.numeric_submission,
-        upload_files: model.upload_files,
+        upload_files: model
+            .upload_files
+            .into_iter()
+            .map(UploadFile::from)
+            .collect(),
         grade_end_date: model.grade_end_date,
         update_by: model.update_by,
         update_at: model.update_at,
@@ -1228,6 +2486,199 @@ fn convert_model_to_step_phase_vote_result(
     }
 }
 
+fn convert_model_to_step_phase_admin_action(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    model: StepPhaseAdminActionModel,
+) -> StepPhaseAdminAction {
+    StepPhaseAdminAction {
+        project_id,
+        step_phase_id,
+        action: model.action,
+        reason: model.reason,
+        previous_status: model.previous_status,
+        new_status: model.new_status,
+        previous_end_date: model.previous_end_date,
+        new_end_date: model.new_end_date,
+        performed_by: model.performed_by,
+        performed_at: model.performed_at,
+    }
+}
+
+fn convert_model_to_project_ballot(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    neuron_id: SnsNeuronIdKey,
+    model: BallotModel,
+) -> ProjectBallot {
+    ProjectBallot {
+        project_id,
+        step_phase_id,
+        proposal_id: model.proposal_id,
+        neuron_id: hex::encode(neuron_id),
+        vote: model.vote,
+        voting_power: model.voting_power,
+        cast_timestamp_seconds: model.cast_timestamp_seconds,
+    }
+}
+
+fn convert_model_to_sns_neuron(subaccount: String, model: SnsNeuronModel) -> SnsNeuron {
+    SnsNeuron {
+        subaccount,
+        stake_e8s: model.stake_e8s,
+        neuron_fees_e8s: model.neuron_fees_e8s,
+        dissolve_delay_seconds: model.dissolve_delay_seconds,
+        can_submit_proposals: model.can_submit_proposals,
+        proposal_fee_e8s: model.proposal_fee_e8s,
+        min_dissolve_delay_seconds: model.min_dissolve_delay_seconds,
+        ineligible_reason: model.ineligible_reason,
+        last_error: model.last_error,
+        last_used_at: model.last_used_at,
+        checked_at: model.checked_at,
+    }
+}
+
+fn convert_model_to_audit_entry(
+    audit_entry_id: AuditEntryId,
+    model: AuditEntryModel,
+) -> AuditEntry {
+    AuditEntry {
+        id: audit_entry_id,
+        actor: model.actor,
+        action: model.action,
+        target: model.target,
+        before: model.before,
+        after: model.after,
+        timestamp: model.timestamp,
+    }
+}
+
+fn convert_model_to_storage_config(model: StorageConfigModel) -> StorageConfig {
+    StorageConfig {
+        provider: model.provider,
+        endpoint: model.endpoint,
+        bucket: model.bucket,
+        path_prefix: model.path_prefix,
+        region: model.region,
+        access_key_id: model.access_key_id,
+        secret_access_key_set: model.secret_access_key.is_some(),
+    }
+}
+
+fn convert_model_to_document_type(
+    document_type_id: DocumentTypeId,
+    model: DocumentTypeModel,
+) -> DocumentType {
+    DocumentType {
+        id: document_type_id,
+        name: model.name,
+        description: model.description,
+        mime_types: model.mime_types,
+        max_size: model.max_size,
+        max_files: model.max_files,
+        public: model.public,
+        active: model.active,
+    }
+}
+
+fn convert_model_to_stored_file(file_id: FileId, model: StoredFileModel) -> StoredFile {
+    StoredFile {
+        id: file_id,
+        project_id: model.project_id,
+        step_phase_id: model.step_phase_id,
+        step_id: model.step_id,
+        document_type: model.document_type,
+        key: model.key,
+        filename: model.filename,
+        mime_type: model.mime_type,
+        size: model.size,
+        sha256: model.sha256,
+        chunk_count: model.chunk_count,
+        uploaded_chunks: count_file_chunks(file_id),
+        created_by: model.created_by,
+        created_at: model.created_at,
+        committed_at: model.committed_at,
+    }
+}
+
+fn convert_model_to_pending_proposal(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    model: PendingProposalModel,
+) -> PendingProposal {
+    PendingProposal {
+        project_id,
+        step_phase_id,
+        proposal_id: model.proposal_id,
+        attempts: model.attempts,
+        last_error: model.last_error,
+        next_attempt_at: model.next_attempt_at,
+        created_at: model.created_at,
+    }
+}
+
+fn convert_model_to_proposal_execution_receipt(
+    proposal_id: ProposalId,
+    model: ProposalExecutionReceiptModel,
+) -> ProposalExecutionReceipt {
+    ProposalExecutionReceipt {
+        proposal_id,
+        project_id: model.project_id,
+        step_phase_id: model.step_phase_id,
+        approved: model.approved,
+        executed_at: model.executed_at,
+    }
+}
+
+fn convert_model_to_appeal(
+    project_id: ProjectId,
+    step_phase_id: StepPhaseId,
+    model: AppealModel,
+) -> Appeal {
+    Appeal {
+        project_id,
+        step_phase_id,
+        justification: model.justification,
+        status: model.status,
+        outcome: model.outcome,
+        decision_reason: model.decision_reason,
+        filed_by: model.filed_by,
+        filed_at: model.filed_at,
+        decided_by: model.decided_by,
+        decided_at: model.decided_at,
+    }
+}
+
+fn convert_model_to_project_reviewer(
+    project_id: ProjectId,
+    reviewer_id: UserId,
+    model: ProjectReviewerModel,
+) -> ProjectReviewer {
+    ProjectReviewer {
+        project_id,
+        reviewer_id,
+        assigned_by: model.assigned_by,
+        assigned_at: model.assigned_at,
+    }
+}
+
+fn convert_model_to_document_access(
+    project_id: ProjectId,
+    index: u64,
+    model: DocumentAccessModel,
+) -> DocumentAccess {
+    DocumentAccess {
+        project_id,
+        index,
+        step_phase_id: model.step_phase_id,
+        step_id: model.step_id,
+        document_type: model.document_type,
+        key: model.key,
+        accessed_by: model.accessed_by,
+        accessed_at: model.accessed_at,
+    }
+}
+
 fn convert_model_to_user(user_id: UserId, user_model: UserModel) -> User {
     User {
         user_id,
@@ -12,6 +12,16 @@ pub fn check_is_owner_or_governance_id(caller_id: Principal) -> Result<(), APIEr
     }
 }
 
+pub fn check_is_owner_or_admin(caller_id: Principal) -> Result<(), APIError> {
+    if _check_is_owner(caller_id) || _check_is_admin(caller_id) {
+        Ok(())
+    } else {
+        Err(APIError::Forbidden(
+            "Access denied: You are not an admin of the canister.".to_string(),
+        ))
+    }
+}
+
 pub fn check_is_project_owner_or_admin(
     caller_id: Principal,
     project_id: ProjectId,
@@ -28,6 +38,21 @@ pub fn check_is_project_owner_or_admin(
     }
 }
 
+pub fn check_is_project_owner_reviewer_or_admin(
+    caller_id: Principal,
+    project_id: ProjectId,
+) -> Result<(), APIError> {
+    if repository::is_project_reviewer(project_id, caller_id) {
+        return Ok(());
+    }
+
+    check_is_project_owner_or_admin(caller_id, project_id).map_err(|_| {
+        APIError::Forbidden(
+            "Access denied: You are not the project owner or an assigned reviewer.".to_string(),
+        )
+    })
+}
+
 fn _check_is_owner(caller_id: Principal) -> bool {
     let owner = repository::get_owner();
     let canister_config = canister_management::service::get_canister_config();
@@ -0,0 +1,936 @@
+//! Test harness deploying the ICVC backend next to the mock SNS governance in PocketIC.
+//!
+//! The canister wasms are read from `ICVC_BACKEND_WASM` and `MOCK_SNS_GOVERNANCE_WASM`,
+//! defaulting to the release builds of the workspace. `run_integration_tests.sh` builds them
+//! and runs the tests.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use candid::{decode_one, encode_args, encode_one, CandidType, Deserialize, Principal};
+use pocket_ic::{PocketIc, WasmResult};
+
+pub const PROJECT_VOTE_FUNCTION_ID: u64 = 4001;
+pub const PROJECTS_UPDATE_TIMER_INTERVAL: u64 = 60;
+pub const ASSESSMENT_DURATION: u64 = 60;
+const CANISTER_CYCLES: u128 = 2_000_000_000_000;
+// Enough rounds for a timer to fire and its inter-canister calls to complete.
+const TICKS_PER_ADVANCE: usize = 10;
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum APIError {
+    InternalServerError(String),
+    NotFound(String),
+    Unauthorized(String),
+    MultipleErrors(Vec<APIError>),
+    Forbidden(String),
+    BadRequest(String),
+}
+
+#[derive(CandidType)]
+pub struct InitArgs {
+    pub sns_governance_id: Option<Principal>,
+    pub subaccount: Option<String>,
+    pub max_stable_memory_size: u64,
+}
+
+#[derive(CandidType)]
+pub struct UserCreate {
+    pub name: String,
+    pub user_id: Principal,
+}
+
+#[derive(CandidType, Default)]
+pub struct ICVCConfigUpdate {
+    pub grade_max_value: Option<u32>,
+    pub grade_min_value: Option<u32>,
+    pub appeal_duration: Option<u64>,
+    pub assessment_duration: Option<u64>,
+    pub open_duration: Option<u64>,
+    pub projects_update_timer_interval: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct Link {
+    pub url: String,
+    pub kind: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct TeamMember {
+    pub first_name: String,
+    pub last_name: String,
+    pub position: String,
+    pub previous_experience: String,
+    pub profile_picture: String,
+    pub links: Vec<Link>,
+}
+
+#[derive(CandidType)]
+pub struct ProjectCreate {
+    pub title: String,
+    pub moto: String,
+    pub description: String,
+    pub team_members: Vec<TeamMember>,
+    pub links: Vec<Link>,
+    pub categories: Vec<u64>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum ProjectStatus {
+    Open,
+    Suspended,
+    NotFunded,
+    Funded,
+    UnderAppeal,
+    NotSubmitted,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct Project {
+    pub id: u64,
+    pub user_id: Principal,
+    pub title: String,
+    pub status: ProjectStatus,
+    pub current_phase: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum StepPhaseStatus {
+    Open,
+    Approved,
+    NotApproved,
+    UnderAppeal,
+    Submitted,
+    NotSubmitted,
+    PendingProposal,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum AssessmentMethod {
+    Grade,
+    None,
+    Vote,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StepPhase {
+    pub id: u64,
+    pub project_id: u64,
+    pub status: StepPhaseStatus,
+    pub end_open_date: u64,
+    pub end_assessment_date: u64,
+    pub assessment_method: AssessmentMethod,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct QuestionSubmission {
+    pub id: String,
+    pub response: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DocumentType {
+    pub id: u64,
+    pub name: String,
+    pub mime_types: Vec<String>,
+    pub max_files: u32,
+    pub active: bool,
+}
+
+#[derive(CandidType)]
+pub struct DocumentTypeCreate {
+    pub name: String,
+    pub description: String,
+    pub mime_types: Vec<String>,
+    pub max_size: u64,
+    pub max_files: u32,
+    pub public: bool,
+}
+
+#[derive(CandidType, Default)]
+pub struct DocumentTypeUpdate {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub mime_types: Option<Vec<String>>,
+    pub max_size: Option<u64>,
+    pub max_files: Option<u32>,
+    pub public: Option<bool>,
+    pub active: Option<bool>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum UploadStatus {
+    Pending,
+    Uploaded,
+    Rejected,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct UploadFile {
+    pub document_type: u64,
+    pub status: Option<UploadStatus>,
+    pub rejection_reason: Option<String>,
+    pub document_id: Option<u64>,
+    pub version: Option<u32>,
+    pub previous_versions: Option<Vec<UploadFileVersion>>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct UploadFileVersion {
+    pub version: u32,
+}
+
+#[derive(CandidType)]
+pub struct UploadUrlRequest {
+    pub filename: String,
+    pub document_type: u64,
+    pub document_id: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct UploadUrlResponse {
+    pub url: String,
+    pub document_type: u64,
+    pub document_id: u64,
+    pub version: u32,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DownloadUrlResponse {
+    pub url: String,
+    pub document_type: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ProjectReviewer {
+    pub project_id: u64,
+    pub reviewer_id: Principal,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DocumentAccess {
+    pub document_type: u64,
+    pub accessed_by: Principal,
+}
+
+#[derive(CandidType)]
+pub struct UploadConfirmation {
+    pub document_type: u64,
+    pub size: u64,
+    pub sha256: String,
+    pub mime_type: String,
+    pub document_id: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum StorageProviderKind {
+    S3Compatible,
+    MockHttp,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StorageConfig {
+    pub provider: StorageProviderKind,
+    pub endpoint: String,
+    pub bucket: String,
+    pub region: String,
+    pub access_key_id: Option<String>,
+    pub secret_access_key_set: bool,
+}
+
+#[derive(CandidType, Clone, Default)]
+pub struct StorageConfigUpdate {
+    pub provider: Option<StorageProviderKind>,
+    pub endpoint: Option<String>,
+    pub bucket: Option<String>,
+    pub region: Option<String>,
+    pub access_key_id: Option<String>,
+    pub secret_access_key: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct Step {
+    pub id: u64,
+    pub project_id: u64,
+    pub step_phase_id: u64,
+    pub question_submission: Vec<QuestionSubmission>,
+    pub upload_files: Vec<UploadFile>,
+}
+
+#[derive(CandidType, Default)]
+pub struct StepUpdate {
+    pub questions_submission: Option<Vec<QuestionSubmission>>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StepPhaseProposal {
+    pub project_id: u64,
+    pub step_phase_id: u64,
+    pub proposal_id: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StepPhaseVoteResult {
+    pub yes: u64,
+    pub no: u64,
+    pub total: u64,
+    pub approved: bool,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StepGrade {
+    pub step_id: u64,
+    pub grade: u32,
+}
+
+#[derive(CandidType)]
+pub struct StepPhaseReopen {
+    pub end_open_date: Option<u64>,
+    pub reason: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StepPhaseGradeResult {
+    pub avg_result: f64,
+    pub total_steps_grades_count: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ProjectsUpdateTimerStatus {
+    pub interval: u64,
+    pub running: bool,
+    pub paused: bool,
+}
+
+#[derive(CandidType)]
+pub struct ProjectProposalPayload {
+    pub project_id: u64,
+    pub phase_id: u64,
+}
+
+#[derive(CandidType)]
+pub struct ProjectSuspensionPayload {
+    pub project_id: u64,
+    pub suspended: bool,
+    pub reason: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum StepPhaseAdminActionKind {
+    ExtendOpenDeadline,
+    ExtendAssessmentDeadline,
+    Reopen,
+    ResetToOpen,
+    ShiftForSuspension,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StepPhaseAdminAction {
+    pub action: StepPhaseAdminActionKind,
+    pub previous_end_date: u64,
+    pub new_end_date: u64,
+}
+
+#[derive(CandidType)]
+pub struct AppealCreate {
+    pub justification: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum AppealOutcome {
+    Reject,
+    Reassess,
+    Advance,
+}
+
+#[derive(CandidType)]
+pub struct AppealDecision {
+    pub outcome: AppealOutcome,
+    pub reason: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum AppealStatus {
+    Pending,
+    Accepted,
+    Rejected,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct Appeal {
+    pub project_id: u64,
+    pub step_phase_id: u64,
+    pub status: AppealStatus,
+    pub outcome: Option<AppealOutcome>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum AuditAction {
+    ProjectCreated,
+    ProjectUpdated,
+    ProjectDeleted,
+    ProjectSuspended,
+    ProjectSuspensionLifted,
+    ProjectReviewerAssigned,
+    ProjectReviewerRemoved,
+    StepUpdated,
+    StepGraded,
+    StepPhaseSubmitted,
+    StepPhaseStatusChanged,
+    StepPhaseDeadlineExtended,
+    StepPhaseReopened,
+    StepPhaseResetToOpen,
+    DocumentUploadRequested,
+    DocumentUploadConfirmed,
+    DocumentDeleted,
+    FileUploadCreated,
+    FileUploadCommitted,
+    AppealFiled,
+    AppealDecided,
+    VoteProposalSubmitted,
+    VoteProposalExecuted,
+    CanisterConfigUpdated,
+    OwnerChanged,
+    ICVCConfigUpdated,
+    ProjectsUpdateTimerPaused,
+    ProjectsUpdateTimerResumed,
+    CategoryCreated,
+    CategoryDeactivated,
+    StepPhaseConfigUpdated,
+    ProposalTemplateUpdated,
+    StorageConfigUpdated,
+    DocumentTypeCreated,
+    DocumentTypeUpdated,
+    AdminAdded,
+    UserUpdated,
+    UserDeleted,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct AuditTarget {
+    pub project_id: Option<u64>,
+    pub step_phase_id: Option<u64>,
+    pub entity_id: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct AuditEntry {
+    pub id: u64,
+    pub actor: Principal,
+    pub action: AuditAction,
+    pub target: AuditTarget,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct AuditLogPage {
+    pub entries: Vec<AuditEntry>,
+    pub next_start_at: Option<u64>,
+}
+
+#[derive(CandidType, Default)]
+pub struct AuditLogFilter {
+    pub actor: Option<Principal>,
+    pub action: Option<AuditAction>,
+    pub project_id: Option<u64>,
+    pub step_phase_id: Option<u64>,
+}
+
+#[derive(CandidType)]
+pub struct GenericNervousSystemFunction {
+    pub validator_canister_id: Option<Principal>,
+    pub target_canister_id: Option<Principal>,
+    pub validator_method_name: Option<String>,
+    pub target_method_name: Option<String>,
+}
+
+#[derive(CandidType)]
+pub enum FunctionType {
+    GenericNervousSystemFunction(GenericNervousSystemFunction),
+}
+
+#[derive(CandidType)]
+pub struct NervousSystemFunction {
+    pub id: u64,
+    pub name: String,
+    pub description: Option<String>,
+    pub function_type: Option<FunctionType>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SnsProposalId {
+    pub id: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SnsProposalData {
+    pub id: Option<SnsProposalId>,
+    pub decided_timestamp_seconds: u64,
+    pub executed_timestamp_seconds: u64,
+    pub failed_timestamp_seconds: u64,
+}
+
+pub struct TestEnv {
+    pub pic: PocketIc,
+    pub icvc_id: Principal,
+    pub sns_governance_id: Principal,
+    pub owner: Principal,
+    pub admin: Principal,
+    pub founder: Principal,
+    pub stranger: Principal,
+    icvc_wasm: Vec<u8>,
+}
+
+impl TestEnv {
+    /// Installs the mock SNS governance with the project vote function registered, then the
+    /// ICVC backend pointing to it, with a short timer interval and one admin.
+    pub fn new() -> Self {
+        let pic = PocketIc::new();
+        let owner = Principal::from_slice(&[1; 29]);
+        let admin = Principal::from_slice(&[2; 29]);
+        let founder = Principal::from_slice(&[3; 29]);
+        let stranger = Principal::from_slice(&[4; 29]);
+
+        let sns_governance_id = pic.create_canister_with_settings(Some(owner), None);
+        pic.add_cycles(sns_governance_id, CANISTER_CYCLES);
+        pic.install_canister(
+            sns_governance_id,
+            read_wasm("MOCK_SNS_GOVERNANCE_WASM", "mock_sns_governance"),
+            encode_args(()).unwrap(),
+            Some(owner),
+        );
+
+        let icvc_wasm = read_wasm("ICVC_BACKEND_WASM", "icvc_backend");
+        let icvc_id = pic.create_canister_with_settings(Some(owner), None);
+        pic.add_cycles(icvc_id, CANISTER_CYCLES);
+        pic.install_canister(
+            icvc_id,
+            icvc_wasm.clone(),
+            encode_one(Some(InitArgs {
+                sns_governance_id: Some(sns_governance_id),
+                subaccount: Some(hex_subaccount(7)),
+                max_stable_memory_size: 0,
+            }))
+            .unwrap(),
+            Some(owner),
+        );
+
+        let env = TestEnv {
+            pic,
+            icvc_id,
+            sns_governance_id,
+            owner,
+            admin,
+            founder,
+            stranger,
+            icvc_wasm,
+        };
+
+        env.sns_update::<()>(
+            "add_generic_nervous_system_function",
+            encode_one(NervousSystemFunction {
+                id: PROJECT_VOTE_FUNCTION_ID,
+                name: "Project vote".to_string(),
+                description: None,
+                function_type: Some(FunctionType::GenericNervousSystemFunction(
+                    GenericNervousSystemFunction {
+                        validator_canister_id: Some(icvc_id),
+                        target_canister_id: Some(icvc_id),
+                        validator_method_name: Some("validate_project_vote_proposal".to_string()),
+                        target_method_name: Some("execute_project_vote_proposal".to_string()),
+                    },
+                )),
+            })
+            .unwrap(),
+        );
+
+        env.update::<Result<candid::Reserved, APIError>>(
+            owner,
+            "addAdmin",
+            encode_one(UserCreate {
+                name: "Admin".to_string(),
+                user_id: admin,
+            })
+            .unwrap(),
+        )
+        .expect("Unable to add the admin");
+
+        env.update::<Result<candid::Reserved, APIError>>(
+            owner,
+            "updateICVCConfig",
+            encode_one(ICVCConfigUpdate {
+                assessment_duration: Some(ASSESSMENT_DURATION),
+                projects_update_timer_interval: Some(PROJECTS_UPDATE_TIMER_INTERVAL),
+                ..Default::default()
+            })
+            .unwrap(),
+        )
+        .expect("Unable to update the ICVC config");
+
+        // Upload URLs point to a local server, the tests hold no bucket credentials.
+        env.update::<Result<candid::Reserved, APIError>>(
+            owner,
+            "updateStorageConfig",
+            encode_one(StorageConfigUpdate {
+                provider: Some(StorageProviderKind::MockHttp),
+                endpoint: Some("http://localhost:8080".to_string()),
+                ..Default::default()
+            })
+            .unwrap(),
+        )
+        .expect("Unable to update the storage config");
+
+        env
+    }
+
+    pub fn update<T: for<'a> Deserialize<'a> + CandidType>(
+        &self,
+        sender: Principal,
+        method: &str,
+        args: Vec<u8>,
+    ) -> T {
+        let result = self.pic.update_call(self.icvc_id, sender, method, args);
+        decode_reply(method, result)
+    }
+
+    pub fn query<T: for<'a> Deserialize<'a> + CandidType>(
+        &self,
+        sender: Principal,
+        method: &str,
+        args: Vec<u8>,
+    ) -> T {
+        let result = self.pic.query_call(self.icvc_id, sender, method, args);
+        decode_reply(method, result)
+    }
+
+    pub fn sns_update<T: for<'a> Deserialize<'a> + CandidType>(
+        &self,
+        method: &str,
+        args: Vec<u8>,
+    ) -> T {
+        let result = self
+            .pic
+            .update_call(self.sns_governance_id, self.owner, method, args);
+        decode_reply(method, result)
+    }
+
+    /// Moves the PocketIC time forward and executes enough rounds for the projects update
+    /// timer to run.
+    pub fn advance_time(&self, seconds: u64) {
+        self.pic.advance_time(Duration::from_secs(seconds));
+        for _ in 0..TICKS_PER_ADVANCE {
+            self.pic.tick();
+        }
+    }
+
+    pub fn upgrade_icvc(&self) {
+        self.pic
+            .upgrade_canister(
+                self.icvc_id,
+                self.icvc_wasm.clone(),
+                encode_args(()).unwrap(),
+                Some(self.owner),
+            )
+            .expect("Unable to upgrade the ICVC backend");
+    }
+
+    pub fn create_project(&self, sender: Principal, title: &str) -> Result<Project, APIError> {
+        self.update(
+            sender,
+            "createProject",
+            encode_one(ProjectCreate {
+                title: title.to_string(),
+                moto: "Moto".to_string(),
+                description: "Description".to_string(),
+                team_members: vec![],
+                links: vec![],
+                categories: vec![],
+            })
+            .unwrap(),
+        )
+    }
+
+    pub fn get_project(&self, project_id: u64) -> Project {
+        self.query::<Result<Project, APIError>>(
+            self.owner,
+            "getProjectById",
+            encode_one(project_id).unwrap(),
+        )
+        .expect("Project not found")
+    }
+
+    pub fn get_step_phase(&self, project_id: u64, step_phase_id: u64) -> StepPhase {
+        self.query::<Result<StepPhase, APIError>>(
+            self.owner,
+            "getStepPhaseById",
+            encode_args((project_id, step_phase_id)).unwrap(),
+        )
+        .expect("Step phase not found")
+    }
+
+    pub fn get_steps(&self, project_id: u64, step_phase_id: u64) -> Vec<Step> {
+        self.query::<Result<Vec<Step>, APIError>>(
+            self.owner,
+            "getAllSteps",
+            encode_args((project_id, step_phase_id)).unwrap(),
+        )
+        .expect("Steps not found")
+    }
+
+    /// Answers every question and uploads every document of every step of a phase that is not
+    /// uploaded yet.
+    pub fn fill_step_phase(
+        &self,
+        sender: Principal,
+        project_id: u64,
+        step_phase_id: u64,
+    ) -> Result<(), APIError> {
+        self.answer_step_phase_questions(sender, project_id, step_phase_id)?;
+
+        for step in self.get_steps(project_id, step_phase_id) {
+            for upload_file in step
+                .upload_files
+                .into_iter()
+                .filter(|file| file.status != Some(UploadStatus::Uploaded))
+            {
+                let document_type = upload_file.document_type;
+                let upload_url = self
+                    .generate_upload_url(
+                        sender,
+                        project_id,
+                        step_phase_id,
+                        step.id,
+                        document_type,
+                        upload_file.document_id,
+                    )?
+                    .remove(0);
+                self.confirm_upload(
+                    sender,
+                    project_id,
+                    step_phase_id,
+                    step.id,
+                    UploadConfirmation {
+                        mime_type: self.mime_type(document_type),
+                        document_type,
+                        size: 1024,
+                        sha256: "ab".repeat(32),
+                        document_id: Some(upload_url.document_id),
+                    },
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn answer_step_phase_questions(
+        &self,
+        sender: Principal,
+        project_id: u64,
+        step_phase_id: u64,
+    ) -> Result<(), APIError> {
+        for step in self.get_steps(project_id, step_phase_id) {
+            let questions_submission = step
+                .question_submission
+                .iter()
+                .map(|question| QuestionSubmission {
+                    id: question.id.clone(),
+                    response: Some(format!("Answer to {}", question.id)),
+                })
+                .collect();
+
+            self.update::<Result<Step, APIError>>(
+                sender,
+                "updateStep",
+                encode_args((
+                    project_id,
+                    step_phase_id,
+                    step.id,
+                    StepUpdate {
+                        questions_submission: Some(questions_submission),
+                    },
+                ))
+                .unwrap(),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// A MIME type accepted for the document type.
+    pub fn mime_type(&self, document_type: u64) -> String {
+        self.query::<Result<DocumentType, APIError>>(
+            self.owner,
+            "getDocumentTypeById",
+            encode_one(document_type).unwrap(),
+        )
+        .expect("Unable to get the document type")
+        .mime_types
+        .remove(0)
+    }
+
+    pub fn generate_upload_url(
+        &self,
+        sender: Principal,
+        project_id: u64,
+        step_phase_id: u64,
+        step_id: u64,
+        document_type: u64,
+        document_id: Option<u64>,
+    ) -> Result<Vec<UploadUrlResponse>, APIError> {
+        self.update(
+            sender,
+            "generateUploadUrl",
+            encode_args((
+                project_id,
+                step_phase_id,
+                step_id,
+                vec![UploadUrlRequest {
+                    filename: format!("{}.bin", document_type),
+                    document_type,
+                    document_id,
+                }],
+            ))
+            .unwrap(),
+        )
+    }
+
+    pub fn confirm_upload(
+        &self,
+        sender: Principal,
+        project_id: u64,
+        step_phase_id: u64,
+        step_id: u64,
+        upload_confirmation: UploadConfirmation,
+    ) -> Result<UploadFile, APIError> {
+        self.update(
+            sender,
+            "confirmUpload",
+            encode_args((project_id, step_phase_id, step_id, upload_confirmation)).unwrap(),
+        )
+    }
+
+    pub fn generate_download_urls(
+        &self,
+        sender: Principal,
+        project_id: u64,
+        step_phase_id: u64,
+        step_id: u64,
+    ) -> Result<Vec<DownloadUrlResponse>, APIError> {
+        self.update(
+            sender,
+            "generateDownloadUrls",
+            encode_args((project_id, step_phase_id, step_id)).unwrap(),
+        )
+    }
+
+    pub fn submit_step_phase(
+        &self,
+        sender: Principal,
+        project_id: u64,
+        step_phase_id: u64,
+    ) -> Result<StepPhase, APIError> {
+        self.update(
+            sender,
+            "submitStepPhase",
+            encode_args((project_id, step_phase_id)).unwrap(),
+        )
+    }
+
+    pub fn submit_step_grade(
+        &self,
+        sender: Principal,
+        project_id: u64,
+        step_phase_id: u64,
+        step_id: u64,
+        grade: u32,
+    ) -> Result<u32, APIError> {
+        self.update(
+            sender,
+            "submitStepGrade",
+            encode_args((project_id, step_phase_id, step_id, grade)).unwrap(),
+        )
+    }
+
+    pub fn get_phase_proposal_id(&self, project_id: u64, step_phase_id: u64) -> u64 {
+        self.query::<Result<StepPhaseProposal, APIError>>(
+            self.owner,
+            "getProposalByPhaseId",
+            encode_args((project_id, step_phase_id)).unwrap(),
+        )
+        .expect("Proposal not found")
+        .proposal_id
+    }
+
+    pub fn get_audit_log(
+        &self,
+        sender: Principal,
+        filter: AuditLogFilter,
+        start_at: Option<u64>,
+        limit: Option<usize>,
+    ) -> Result<AuditLogPage, APIError> {
+        self.query(
+            sender,
+            "getAuditLog",
+            encode_args((filter, start_at, limit)).unwrap(),
+        )
+    }
+
+    /// Casts one ballot on the SNS proposal of a phase and decides it, which executes the
+    /// proposal on the ICVC backend when it is adopted.
+    pub fn vote_and_decide(
+        &self,
+        project_id: u64,
+        step_phase_id: u64,
+        yes: bool,
+    ) -> SnsProposalData {
+        let proposal_id = self.get_phase_proposal_id(project_id, step_phase_id);
+        let vote: i32 = if yes { 1 } else { 2 };
+
+        self.sns_update::<Result<(), String>>(
+            "cast_ballot",
+            encode_args((proposal_id, "0a".repeat(32), vote, 100_000_000_u64)).unwrap(),
+        )
+        .expect("Unable to cast the ballot");
+
+        self.sns_update::<Result<SnsProposalData, String>>(
+            "decide_proposal",
+            encode_one(proposal_id).unwrap(),
+        )
+        .expect("Unable to decide the proposal")
+    }
+}
+
+fn read_wasm(env_var: &str, package: &str) -> Vec<u8> {
+    let path = match std::env::var_os(env_var) {
+        Some(path) => PathBuf::from(path),
+        None => PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../target/wasm32-unknown-unknown/release")
+            .join(format!("{}.wasm", package)),
+    };
+
+    std::fs::read(&path).unwrap_or_else(|e| {
+        panic!(
+            "Unable to read the {} wasm at {:?}, build it with run_integration_tests.sh: {}",
+            package, path, e
+        )
+    })
+}
+
+fn decode_reply<T: for<'a> Deserialize<'a> + CandidType>(
+    method: &str,
+    result: Result<WasmResult, pocket_ic::UserError>,
+) -> T {
+    match result {
+        Ok(WasmResult::Reply(bytes)) => decode_one(&bytes)
+            .unwrap_or_else(|e| panic!("Unable to decode the reply of {}: {}", method, e)),
+        Ok(WasmResult::Reject(msg)) => panic!("{} was rejected: {}", method, msg),
+        Err(e) => panic!("{} failed: {}", method, e),
+    }
+}
+
+fn hex_subaccount(byte: u8) -> String {
+    format!("{:02x}", byte).repeat(32)
+}
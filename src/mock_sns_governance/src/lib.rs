@@ -0,0 +1,630 @@
+//! Minimal stand-in for SNS governance, used to exercise the Vote phases of ICVC offline in
+//! PocketIC. It implements the calls made by ICVC (`manage_neuron`, `get_proposal`,
+//! `list_proposals`, `list_neurons` and `get_nervous_system_parameters`) and calls the
+//! registered generic function validators and executors the way SNS governance does.
+//!
+//! Votes and decisions are driven by the test through the `add_*`, `cast_ballot` and
+//! `decide_proposal` update calls, nothing happens on its own. The state is kept on the heap
+//! and is not preserved across upgrades.
+
+pub mod types;
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+use candid::Principal;
+use types::*;
+
+// `ErrorType` of SNS governance.
+const ERROR_TYPE_NOT_AUTHORIZED: i32 = 3;
+const ERROR_TYPE_NOT_FOUND: i32 = 4;
+const ERROR_TYPE_INVALID_COMMAND: i32 = 8;
+const ERROR_TYPE_EXTERNAL: i32 = 13;
+const ERROR_TYPE_INSUFFICIENT_FUNDS: i32 = 15;
+const ERROR_TYPE_INVALID_PROPOSAL: i32 = 17;
+// `NeuronPermissionType::SubmitProposal` of SNS governance.
+const NEURON_PERMISSION_SUBMIT_PROPOSAL: i32 = 3;
+// `Vote` of SNS governance.
+const VOTE_YES: i32 = 1;
+const VOTE_NO: i32 = 2;
+// SNS defaults: 3% of the total voting power and a simple majority of the votes cast.
+const DEFAULT_MIN_YES_OF_TOTAL_BASIS_POINTS: u64 = 300;
+const DEFAULT_MIN_YES_OF_EXERCISED_BASIS_POINTS: u64 = 5_000;
+const DEFAULT_INITIAL_VOTING_PERIOD_SECONDS: u64 = 4 * 24 * 60 * 60;
+const MAX_LIST_LIMIT: usize = 100;
+
+#[derive(Default)]
+struct State {
+    last_proposal_id: u64,
+    proposals: BTreeMap<u64, ProposalData>,
+    functions: BTreeMap<u64, NervousSystemFunction>,
+    neurons: BTreeMap<Vec<u8>, Neuron>,
+    parameters: Option<NervousSystemParameters>,
+}
+
+thread_local! {
+    static STATE: RefCell<State> = RefCell::new(State::default());
+}
+
+#[ic_cdk::update]
+async fn manage_neuron(args: ManageNeuron) -> ManageNeuronResponse {
+    let command = match args.command {
+        Some(Command::MakeProposal(proposal)) => {
+            match make_proposal(ic_cdk::caller(), args.subaccount, proposal).await {
+                Ok(proposal_id) => Command1::MakeProposal(GetProposal {
+                    proposal_id: Some(ProposalId { id: proposal_id }),
+                }),
+                Err(error) => Command1::Error(error),
+            }
+        }
+        _ => Command1::Error(governance_error(
+            ERROR_TYPE_INVALID_COMMAND,
+            "Only MakeProposal is supported by the mock SNS governance.".to_string(),
+        )),
+    };
+
+    ManageNeuronResponse {
+        command: Some(command),
+    }
+}
+
+#[ic_cdk::query]
+fn get_proposal(args: GetProposal) -> GetProposalResponse {
+    let proposal_id = args
+        .proposal_id
+        .map(|proposal_id| proposal_id.id)
+        .unwrap_or(0);
+    let proposal = STATE.with(|state| state.borrow().proposals.get(&proposal_id).cloned());
+
+    let result = match proposal {
+        Some(proposal) => Result1::Proposal(proposal),
+        None => Result1::Error(governance_error(
+            ERROR_TYPE_NOT_FOUND,
+            format!("No proposal for given ProposalId {}.", proposal_id),
+        )),
+    };
+
+    GetProposalResponse {
+        result: Some(result),
+    }
+}
+
+/// Lists the proposals from the newest to the oldest.
+#[ic_cdk::query]
+fn list_proposals(args: ListProposals) -> ListProposalsResponse {
+    let before_proposal = args
+        .before_proposal
+        .map(|proposal_id| proposal_id.id)
+        .unwrap_or(u64::MAX);
+    let limit = match args.limit as usize {
+        0 => MAX_LIST_LIMIT,
+        limit => limit.min(MAX_LIST_LIMIT),
+    };
+
+    let proposals = STATE.with(|state| {
+        state
+            .borrow()
+            .proposals
+            .range(..before_proposal)
+            .rev()
+            .map(|(_, proposal)| proposal)
+            .filter(|proposal| !args.exclude_type.contains(&proposal.action))
+            .take(limit)
+            .cloned()
+            .collect()
+    });
+
+    ListProposalsResponse {
+        include_ballots_by_caller: None,
+        proposals,
+    }
+}
+
+/// Lists the neurons ordered by id, starting right after `start_page_at`.
+#[ic_cdk::query]
+fn list_neurons(args: ListNeurons) -> ListNeuronsResponse {
+    let limit = match args.limit as usize {
+        0 => MAX_LIST_LIMIT,
+        limit => limit.min(MAX_LIST_LIMIT),
+    };
+
+    let neurons = STATE.with(|state| {
+        state
+            .borrow()
+            .neurons
+            .iter()
+            .filter(|(id, _)| match &args.start_page_at {
+                Some(start_page_at) => **id > start_page_at.id,
+                None => true,
+            })
+            .map(|(_, neuron)| neuron)
+            .filter(|neuron| match args.of_principal {
+                Some(principal) => neuron
+                    .permissions
+                    .iter()
+                    .any(|permission| permission.principal == Some(principal)),
+                None => true,
+            })
+            .take(limit)
+            .cloned()
+            .collect()
+    });
+
+    ListNeuronsResponse { neurons }
+}
+
+#[ic_cdk::query]
+fn get_nervous_system_parameters(_: ()) -> NervousSystemParameters {
+    get_parameters()
+}
+
+#[ic_cdk::query]
+fn list_nervous_system_functions() -> ListNervousSystemFunctionsResponse {
+    let functions = STATE.with(|state| state.borrow().functions.values().cloned().collect());
+
+    ListNervousSystemFunctionsResponse {
+        reserved_ids: vec![],
+        functions,
+    }
+}
+
+/// Registers a generic function, as an adopted `AddGenericNervousSystemFunction` proposal
+/// would.
+#[ic_cdk::update]
+fn add_generic_nervous_system_function(function: NervousSystemFunction) {
+    STATE.with(|state| {
+        state.borrow_mut().functions.insert(function.id, function);
+    });
+}
+
+/// Adds a neuron, or replaces the neuron with the same id.
+#[ic_cdk::update]
+fn add_neuron(neuron: Neuron) -> Result<(), String> {
+    let neuron_id = neuron
+        .id
+        .as_ref()
+        .map(|neuron_id| neuron_id.id.clone())
+        .ok_or_else(|| "The neuron must have an id.".to_string())?;
+
+    STATE.with(|state| {
+        state.borrow_mut().neurons.insert(neuron_id, neuron);
+    });
+
+    Ok(())
+}
+
+#[ic_cdk::update]
+fn set_nervous_system_parameters(parameters: NervousSystemParameters) {
+    STATE.with(|state| state.borrow_mut().parameters = Some(parameters));
+}
+
+/// Casts or replaces the ballot of a neuron and updates the tally of an open proposal. A
+/// ballot with the `Unspecified` vote only counts toward the total voting power.
+///
+/// # Arguments
+/// * `proposal_id` - The ID of the proposal.
+/// * `neuron_id` - The hex encoded ID of the neuron.
+/// * `vote` - The `Vote` of SNS governance: 0 unspecified, 1 yes, 2 no.
+/// * `voting_power` - The voting power of the ballot.
+#[ic_cdk::update]
+fn cast_ballot(
+    proposal_id: u64,
+    neuron_id: String,
+    vote: i32,
+    voting_power: u64,
+) -> Result<(), String> {
+    let now = now_seconds();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let proposal = state
+            .proposals
+            .get_mut(&proposal_id)
+            .ok_or_else(|| format!("Proposal with id {} not found.", proposal_id))?;
+
+        if proposal.decided_timestamp_seconds != 0 {
+            return Err(format!(
+                "Proposal with id {} is already decided.",
+                proposal_id
+            ));
+        }
+
+        proposal.ballots.insert(
+            neuron_id,
+            Ballot {
+                vote,
+                cast_timestamp_seconds: if vote == 0 { 0 } else { now },
+                voting_power,
+            },
+        );
+        proposal.latest_tally = Some(tally_ballots(proposal, now));
+
+        Ok(())
+    })
+}
+
+/// Decides an open proposal from its latest tally. An adopted generic function proposal is
+/// executed right away by calling the target method with the proposal payload.
+///
+/// # Returns
+/// * `Result<ProposalData, String>` - The decided proposal or an error.
+#[ic_cdk::update]
+async fn decide_proposal(proposal_id: u64) -> Result<ProposalData, String> {
+    let now = now_seconds();
+
+    let execution = STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let proposal = state
+            .proposals
+            .get_mut(&proposal_id)
+            .ok_or_else(|| format!("Proposal with id {} not found.", proposal_id))?;
+
+        if proposal.decided_timestamp_seconds != 0 {
+            return Err(format!(
+                "Proposal with id {} is already decided.",
+                proposal_id
+            ));
+        }
+
+        proposal.decided_timestamp_seconds = now;
+        if !is_accepted(proposal) {
+            return Ok(None);
+        }
+
+        // Adopted proposals get their reject cost back.
+        let reject_cost_e8s = proposal.reject_cost_e8s;
+        let proposer = proposal
+            .proposer
+            .as_ref()
+            .map(|proposer| proposer.id.clone());
+        let execution = match proposal.proposal.as_ref().and_then(|p| p.action.as_ref()) {
+            Some(Action::ExecuteGenericNervousSystemFunction(execute)) => {
+                Some((execute.function_id, execute.payload.clone()))
+            }
+            _ => None,
+        };
+
+        if let Some(neuron) = proposer.and_then(|proposer| state.neurons.get_mut(&proposer)) {
+            neuron.neuron_fees_e8s = neuron.neuron_fees_e8s.saturating_sub(reject_cost_e8s);
+        }
+
+        Ok(execution)
+    })?;
+
+    let result = match execution {
+        Some((function_id, payload)) => match get_generic_function(function_id) {
+            Ok(function) => {
+                let target_canister_id = function
+                    .target_canister_id
+                    .unwrap_or(Principal::anonymous());
+                let target_method_name = function.target_method_name.unwrap_or_default();
+
+                ic_cdk::api::call::call_raw(target_canister_id, &target_method_name, payload, 0)
+                    .await
+                    .map(|_| ())
+                    .map_err(|(code, msg)| format!("Error: {:?}: {}", code, msg))
+            }
+            Err(error) => Err(error.error_message),
+        },
+        None => Ok(()),
+    };
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let proposal = state
+            .proposals
+            .get_mut(&proposal_id)
+            .ok_or_else(|| format!("Proposal with id {} not found.", proposal_id))?;
+
+        if execution_was_attempted(proposal) {
+            match result {
+                Ok(()) => proposal.executed_timestamp_seconds = now_seconds(),
+                Err(msg) => {
+                    proposal.failed_timestamp_seconds = now_seconds();
+                    proposal.failure_reason = Some(governance_error(ERROR_TYPE_EXTERNAL, msg));
+                }
+            }
+        }
+
+        Ok(proposal.clone())
+    })
+}
+
+async fn make_proposal(
+    caller: Principal,
+    subaccount: Vec<u8>,
+    proposal: Proposal,
+) -> Result<u64, GovernanceError> {
+    let parameters = get_parameters();
+    let reject_cost_e8s = parameters.reject_cost_e8s.unwrap_or(0);
+
+    check_proposer(caller, &subaccount, reject_cost_e8s)?;
+
+    let (function_id, payload) = match &proposal.action {
+        Some(Action::ExecuteGenericNervousSystemFunction(execute)) => {
+            (execute.function_id, execute.payload.clone())
+        }
+        _ => {
+            return Err(governance_error(
+                ERROR_TYPE_INVALID_PROPOSAL,
+                "Only ExecuteGenericNervousSystemFunction proposals are supported by the mock SNS governance."
+                    .to_string(),
+            ))
+        }
+    };
+
+    let payload_text_rendering = validate_generic_function_payload(function_id, payload).await?;
+
+    let now = now_seconds();
+    let initial_voting_period_seconds = parameters
+        .initial_voting_period_seconds
+        .unwrap_or(DEFAULT_INITIAL_VOTING_PERIOD_SECONDS);
+
+    // The neuron may have been removed or charged while the validator was called.
+    check_proposer(caller, &subaccount, reject_cost_e8s)?;
+
+    let proposal_id = STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        if let Some(neuron) = state.neurons.get_mut(&subaccount) {
+            neuron.neuron_fees_e8s += reject_cost_e8s;
+        }
+
+        // Every known neuron gets a ballot, the proposer votes yes.
+        let ballots = state
+            .neurons
+            .iter()
+            .map(|(id, neuron)| {
+                let is_proposer = *id == subaccount;
+                (
+                    to_hex(id),
+                    Ballot {
+                        vote: if is_proposer { VOTE_YES } else { 0 },
+                        cast_timestamp_seconds: if is_proposer { now } else { 0 },
+                        voting_power: neuron.cached_neuron_stake_e8s,
+                    },
+                )
+            })
+            .collect();
+
+        state.last_proposal_id += 1;
+        let proposal_id = state.last_proposal_id;
+
+        let mut proposal_data = ProposalData {
+            id: Some(ProposalId { id: proposal_id }),
+            payload_text_rendering: Some(payload_text_rendering),
+            action: function_id,
+            failure_reason: None,
+            action_auxiliary: None,
+            ballots,
+            minimum_yes_proportion_of_total: None,
+            reward_event_round: 0,
+            failed_timestamp_seconds: 0,
+            reward_event_end_timestamp_seconds: None,
+            proposal_creation_timestamp_seconds: now,
+            initial_voting_period_seconds,
+            reject_cost_e8s,
+            latest_tally: None,
+            wait_for_quiet_deadline_increase_seconds: 0,
+            decided_timestamp_seconds: 0,
+            proposal: Some(proposal),
+            proposer: Some(NeuronId {
+                id: subaccount.clone(),
+            }),
+            wait_for_quiet_state: Some(WaitForQuietState {
+                current_deadline_timestamp_seconds: now + initial_voting_period_seconds,
+            }),
+            minimum_yes_proportion_of_exercised: None,
+            is_eligible_for_rewards: false,
+            executed_timestamp_seconds: 0,
+        };
+        proposal_data.latest_tally = Some(tally_ballots(&proposal_data, now));
+
+        state.proposals.insert(proposal_id, proposal_data);
+
+        proposal_id
+    });
+
+    Ok(proposal_id)
+}
+
+// Any subaccount may make proposals while no neuron was added.
+fn check_proposer(
+    caller: Principal,
+    subaccount: &[u8],
+    reject_cost_e8s: u64,
+) -> Result<(), GovernanceError> {
+    STATE.with(|state| {
+        let state = state.borrow();
+        if state.neurons.is_empty() {
+            return Ok(());
+        }
+
+        let neuron = state.neurons.get(subaccount).ok_or_else(|| {
+            governance_error(
+                ERROR_TYPE_NOT_FOUND,
+                format!("No neuron for given NeuronId {}.", to_hex(subaccount)),
+            )
+        })?;
+
+        let can_submit_proposals = neuron.permissions.iter().any(|permission| {
+            permission.principal == Some(caller)
+                && permission
+                    .permission_type
+                    .contains(&NEURON_PERMISSION_SUBMIT_PROPOSAL)
+        });
+        if !can_submit_proposals {
+            return Err(governance_error(
+                ERROR_TYPE_NOT_AUTHORIZED,
+                format!(
+                    "Caller {} is not authorized to submit proposals with neuron {}.",
+                    caller,
+                    to_hex(subaccount)
+                ),
+            ));
+        }
+
+        let available_stake_e8s = neuron
+            .cached_neuron_stake_e8s
+            .saturating_sub(neuron.neuron_fees_e8s);
+        if available_stake_e8s < reject_cost_e8s {
+            return Err(governance_error(
+                ERROR_TYPE_INSUFFICIENT_FUNDS,
+                format!(
+                    "Neuron {} doesn't have enough stake to submit a proposal: {} < {}.",
+                    to_hex(subaccount),
+                    available_stake_e8s,
+                    reject_cost_e8s
+                ),
+            ));
+        }
+
+        Ok(())
+    })
+}
+
+async fn validate_generic_function_payload(
+    function_id: u64,
+    payload: Vec<u8>,
+) -> Result<String, GovernanceError> {
+    let function = get_generic_function(function_id)?;
+    let validator_canister_id = function
+        .validator_canister_id
+        .unwrap_or(Principal::anonymous());
+    let validator_method_name = function.validator_method_name.unwrap_or_default();
+
+    let reply =
+        ic_cdk::api::call::call_raw(validator_canister_id, &validator_method_name, payload, 0)
+            .await
+            .map_err(|(code, msg)| {
+                governance_error(
+                    ERROR_TYPE_EXTERNAL,
+                    format!("Unable to call the validator: {:?}: {}", code, msg),
+                )
+            })?;
+
+    match candid::decode_one::<Result<String, String>>(&reply) {
+        Ok(Ok(rendering)) => Ok(rendering),
+        Ok(Err(msg)) => Err(governance_error(
+            ERROR_TYPE_INVALID_PROPOSAL,
+            format!("The validator rejected the payload: {}", msg),
+        )),
+        Err(e) => Err(governance_error(
+            ERROR_TYPE_INVALID_PROPOSAL,
+            format!("Unable to decode the validator reply: {}", e),
+        )),
+    }
+}
+
+fn get_generic_function(function_id: u64) -> Result<GenericNervousSystemFunction, GovernanceError> {
+    let function = STATE.with(|state| state.borrow().functions.get(&function_id).cloned());
+
+    match function.and_then(|function| function.function_type) {
+        Some(FunctionType::GenericNervousSystemFunction(function)) => Ok(function),
+        _ => Err(governance_error(
+            ERROR_TYPE_INVALID_PROPOSAL,
+            format!(
+                "No generic nervous system function with id {}.",
+                function_id
+            ),
+        )),
+    }
+}
+
+fn tally_ballots(proposal: &ProposalData, timestamp_seconds: u64) -> Tally {
+    let mut tally = Tally {
+        no: 0,
+        yes: 0,
+        total: 0,
+        timestamp_seconds,
+    };
+
+    for ballot in proposal.ballots.values() {
+        tally.total += ballot.voting_power;
+        match ballot.vote {
+            VOTE_YES => tally.yes += ballot.voting_power,
+            VOTE_NO => tally.no += ballot.voting_power,
+            _ => {}
+        }
+    }
+
+    tally
+}
+
+fn is_accepted(proposal: &ProposalData) -> bool {
+    let tally = match &proposal.latest_tally {
+        Some(tally) => tally,
+        None => return false,
+    };
+
+    let min_yes_of_total = proposal
+        .minimum_yes_proportion_of_total
+        .as_ref()
+        .and_then(|percentage| percentage.basis_points)
+        .unwrap_or(DEFAULT_MIN_YES_OF_TOTAL_BASIS_POINTS) as u128;
+    let min_yes_of_exercised = proposal
+        .minimum_yes_proportion_of_exercised
+        .as_ref()
+        .and_then(|percentage| percentage.basis_points)
+        .unwrap_or(DEFAULT_MIN_YES_OF_EXERCISED_BASIS_POINTS)
+        as u128;
+
+    let yes = tally.yes as u128;
+    let exercised = tally.yes as u128 + tally.no as u128;
+
+    yes > 0
+        && yes * 10_000 >= tally.total as u128 * min_yes_of_total
+        && yes * 10_000 > exercised * min_yes_of_exercised
+}
+
+fn execution_was_attempted(proposal: &ProposalData) -> bool {
+    is_accepted(proposal)
+        && matches!(
+            proposal.proposal.as_ref().and_then(|p| p.action.as_ref()),
+            Some(Action::ExecuteGenericNervousSystemFunction(_))
+        )
+}
+
+fn get_parameters() -> NervousSystemParameters {
+    STATE
+        .with(|state| state.borrow().parameters.clone())
+        .unwrap_or(NervousSystemParameters {
+            default_followees: None,
+            max_dissolve_delay_seconds: None,
+            max_dissolve_delay_bonus_percentage: None,
+            max_followees_per_function: None,
+            neuron_claimer_permissions: None,
+            neuron_minimum_stake_e8s: None,
+            max_neuron_age_for_age_bonus: None,
+            initial_voting_period_seconds: Some(DEFAULT_INITIAL_VOTING_PERIOD_SECONDS),
+            neuron_minimum_dissolve_delay_to_vote_seconds: Some(0),
+            reject_cost_e8s: Some(0),
+            max_proposals_to_keep_per_action: None,
+            wait_for_quiet_deadline_increase_seconds: None,
+            max_number_of_neurons: None,
+            transaction_fee_e8s: None,
+            max_number_of_proposals_with_ballots: None,
+            max_age_bonus_percentage: None,
+            neuron_grantable_permissions: None,
+            voting_rewards_parameters: None,
+            maturity_modulation_disabled: None,
+            max_number_of_principals_per_neuron: None,
+        })
+}
+
+fn governance_error(error_type: i32, error_message: String) -> GovernanceError {
+    GovernanceError {
+        error_message,
+        error_type,
+    }
+}
+
+fn now_seconds() -> u64 {
+    ic_cdk::api::time() / 1_000_000_000
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+ic_cdk::export_candid!();